@@ -1,6 +1,6 @@
 import { useCallback, useEffect, useState } from "react";
 import { useParams } from "react-router-dom";
-import { type ApiGameState, getGame, takeTurn } from "./api";
+import { type ApiBotInfo, type ApiGameState, getGame, takeTurn } from "./api";
 import "./Game.css";
 
 
@@ -166,6 +166,11 @@ const PieceDisplay = ({ type, owner }: { type: string, owner: string }) => {
     );
 };
 
+const describeBot = (bot: ApiBotInfo) => {
+    const strength = bot.elo === null ? "full strength" : `level ${bot.skill} (~${bot.elo} Elo)`;
+    return `computer, ${bot.personality}, ${strength}`;
+};
+
 const Game = () => {
     const { uuid } = useParams<{ uuid: string }>();
     const [gameState, setGameState] = useState<ApiGameState | null>(null);
@@ -241,6 +246,12 @@ const Game = () => {
                 {gameState.in_check && <span className="check-badge">CHECK</span>}
                 {gameState.status !== "InProgress" && <div className="game-over">{gameState.status}</div>}
              </div>
+             {(gameState.white_bot || gameState.black_bot) && (
+                 <div className="bot-info">
+                     {gameState.white_bot && <div>White: {describeBot(gameState.white_bot)}</div>}
+                     {gameState.black_bot && <div>Black: {describeBot(gameState.black_bot)}</div>}
+                 </div>
+             )}
              
              {gameState.dimension === 2 ? (
                  <Board2D 
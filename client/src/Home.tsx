@@ -1,19 +1,31 @@
 
 import { useState } from "react";
 import { useNavigate } from "react-router-dom";
-import { createGame } from "./api";
+import { type Personality, createGame } from "./api";
+
+const PERSONALITIES: Personality[] = ["balanced", "attacking", "positional", "materialistic", "simplifying"];
 
 const Home = () => {
     const navigate = useNavigate();
     const [mode, setMode] = useState("hc");
     const [dim, setDim] = useState(2);
     const [side, setSide] = useState(8);
+    const [skill, setSkill] = useState(20);
+    const [whitePersonality, setWhitePersonality] = useState<Personality>("balanced");
+    const [blackPersonality, setBlackPersonality] = useState<Personality>("balanced");
     const [loading, setLoading] = useState(false);
 
     const handleCreate = async () => {
         setLoading(true);
         try {
-            const resp = await createGame(mode, dim, side);
+            const resp = await createGame({
+                mode,
+                dimension: dim,
+                side,
+                skill,
+                white_personality: whitePersonality,
+                black_personality: blackPersonality,
+            });
             navigate(`/game/${resp.uuid}`);
         } catch (e) {
             console.error(e);
@@ -54,6 +66,33 @@ const Home = () => {
                     onChange={e => setSide(parseInt(e.target.value))} 
                 />
             </div>
+            {mode !== "hh" && (
+                <div className="form-group">
+                    <label>Computer Strength:</label>
+                    <select value={skill} onChange={e => setSkill(parseInt(e.target.value))}>
+                        <option value={20}>Full strength</option>
+                        {Array.from({ length: 20 }, (_, level) => 19 - level).map(level => (
+                            <option key={level} value={level}>Level {level}</option>
+                        ))}
+                    </select>
+                </div>
+            )}
+            {(mode === "cc" || mode === "ch") && (
+                <div className="form-group">
+                    <label>White Computer Style:</label>
+                    <select value={whitePersonality} onChange={e => setWhitePersonality(e.target.value as Personality)}>
+                        {PERSONALITIES.map(p => <option key={p} value={p}>{capitalize(p)}</option>)}
+                    </select>
+                </div>
+            )}
+            {(mode === "cc" || mode === "hc") && (
+                <div className="form-group">
+                    <label>Black Computer Style:</label>
+                    <select value={blackPersonality} onChange={e => setBlackPersonality(e.target.value as Personality)}>
+                        {PERSONALITIES.map(p => <option key={p} value={p}>{capitalize(p)}</option>)}
+                    </select>
+                </div>
+            )}
             <button className="create-btn" onClick={handleCreate} disabled={loading}>
                 {loading ? "Creating..." : "Start Game"}
             </button>
@@ -61,4 +100,6 @@ const Home = () => {
     );
 };
 
+const capitalize = (s: string) => s.charAt(0).toUpperCase() + s.slice(1);
+
 export default Home;
@@ -10,6 +10,17 @@ export interface ApiGameState {
     side: number;
     in_check: boolean;
     sequence: number;
+    white_bot: ApiBotInfo | null;
+    black_bot: ApiBotInfo | null;
+}
+
+export type Personality = "balanced" | "attacking" | "positional" | "materialistic" | "simplifying";
+
+export interface ApiBotInfo {
+    skill: number;
+    elo: number | null;
+    personality: Personality;
+    contempt: number;
 }
 
 export interface ApiPiece {
@@ -27,6 +38,9 @@ export interface NewGameRequest {
     mode: string;
     dimension: number;
     side: number;
+    skill?: number;
+    white_personality?: Personality;
+    black_personality?: Personality;
 }
 
 export interface NewGameResponse {
@@ -39,11 +53,11 @@ export interface TurnRequest {
     end: number[];
 }
 
-export const createGame = async (mode: string, dimension: number, side: number): Promise<NewGameResponse> => {
+export const createGame = async (request: NewGameRequest): Promise<NewGameResponse> => {
     const res = await fetch(`${BASE_URL}/new_game`, {
         method: "POST",
         headers: { "Content-Type": "application/json" },
-        body: JSON.stringify({ mode, dimension, side }),
+        body: JSON.stringify(request),
     });
     if (!res.ok) throw new Error("Failed to create game");
     return res.json();
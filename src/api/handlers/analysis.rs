@@ -0,0 +1,139 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json},
+};
+use std::time::Duration;
+
+use crate::api::models::{
+    AnalysisRequest, AnalysisResponse, ApiEvalTrace, ApiSearchInfo, EvaluationRequest,
+};
+use crate::api::state::AppState;
+use crate::config::AppConfig;
+use crate::domain::board::Board;
+use crate::domain::models::Player;
+use crate::infrastructure::ai::eval::Evaluator;
+use crate::infrastructure::ai::{MemoryError, MinimaxBot, SearchLimits};
+
+/// Upper bound on requested lines; each extra line widens the root window.
+const MAX_ANALYSIS_LINES: usize = 64;
+
+/// Search time when a request sets neither `depth` nor `movetime_ms`.
+const DEFAULT_ANALYSIS_TIME: Duration = Duration::from_secs(1);
+
+/// Memory for each request's bot, unless the board needs more. The bot is
+/// dropped with the request, so it gets a slice of `compute.memory` rather
+/// than all of it.
+const ANALYSIS_MEMORY_MB: usize = 64;
+
+pub async fn analyze_position(
+    State(state): State<AppState>,
+    Json(payload): Json<AnalysisRequest>,
+) -> impl IntoResponse {
+    let position = resolve_position(&state, payload.uuid.as_ref(), payload.hfen.as_ref()).await;
+    let (board, player) = match position {
+        Ok(position) => position,
+        Err(response) => return response,
+    };
+
+    let mut movetime = payload.movetime_ms.map(Duration::from_millis);
+    if payload.depth.is_none() && movetime.is_none() {
+        movetime = Some(DEFAULT_ANALYSIS_TIME);
+    }
+    let limits = SearchLimits {
+        depth: payload.depth,
+        movetime,
+        multi_pv: payload.multi_pv.unwrap_or(1).clamp(1, MAX_ANALYSIS_LINES),
+        ..SearchLimits::default()
+    };
+
+    let mut bot = match analysis_bot(&state.config, &board) {
+        Ok(bot) => bot,
+        Err(e) => return (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response(),
+    };
+    let result = tokio::task::spawn_blocking(move || bot.analyze(&board, player, &limits)).await;
+
+    match result {
+        Ok(lines) => (
+            StatusCode::OK,
+            Json(AnalysisResponse {
+                lines: lines.iter().map(ApiSearchInfo::from).collect(),
+            }),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Analysis failed: {}", e),
+        )
+            .into_response(),
+    }
+}
+
+/// A bot for one analysis request, within [`ANALYSIS_MEMORY_MB`] or else the
+/// least the board needs, up to the configured `compute.memory`.
+fn analysis_bot(config: &AppConfig, board: &Board) -> Result<MinimaxBot, MemoryError> {
+    let (dimension, side) = (board.dimension(), board.side());
+    let mut config = config.clone();
+    let budget = config.compute.memory;
+    config.compute.memory = budget.min(ANALYSIS_MEMORY_MB);
+    match MinimaxBot::try_new(&config, dimension, side) {
+        Err(e) if budget > ANALYSIS_MEMORY_MB => {
+            config.compute.memory = e.required_mb.min(budget);
+            MinimaxBot::try_new(&config, dimension, side)
+        }
+        result => result,
+    }
+}
+
+/// The static evaluation of a position, term by term.
+pub async fn evaluate_position(
+    State(state): State<AppState>,
+    Json(payload): Json<EvaluationRequest>,
+) -> impl IntoResponse {
+    let position = resolve_position(&state, payload.uuid.as_ref(), payload.hfen.as_ref()).await;
+    match position {
+        Ok((board, _)) => {
+            let params = state.config.eval_params(board.dimension(), board.side());
+            let trace = Evaluator::evaluate_trace(&board, &params);
+            (StatusCode::OK, Json(ApiEvalTrace::from(&trace))).into_response()
+        }
+        Err(response) => response,
+    }
+}
+
+/// The position to analyse: an explicit HFEN, or the current position of a
+/// stored game.
+async fn resolve_position(
+    state: &AppState,
+    uuid: Option<&String>,
+    hfen: Option<&String>,
+) -> Result<(Board, Player), axum::response::Response> {
+    if let Some(hfen) = hfen {
+        return Board::from_hfen(hfen).map_err(|e| {
+            (StatusCode::BAD_REQUEST, format!("Invalid HFEN: {}", e)).into_response()
+        });
+    }
+
+    let Some(uuid) = uuid else {
+        return Err((StatusCode::BAD_REQUEST, "Expected uuid or hfen").into_response());
+    };
+
+    #[cfg(feature = "distributed")]
+    if let Some(redis) = &state.redis {
+        return match redis.get_session(uuid).await {
+            Ok(Some(session)) => Ok((session.game.board().clone(), session.game.current_turn())),
+            Ok(None) => Err((StatusCode::NOT_FOUND, "Game not found").into_response()),
+            Err(e) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Redis error: {}", e),
+            )
+                .into_response()),
+        };
+    }
+
+    let Some(session_arc) = state.games.get(uuid).map(|s| s.clone()) else {
+        return Err((StatusCode::NOT_FOUND, "Game not found").into_response());
+    };
+    let session = session_arc.read().await;
+    Ok((session.game.board().clone(), session.game.current_turn()))
+}
@@ -2,7 +2,31 @@ use std::sync::Arc;
 use tokio::time::{Duration, sleep};
 
 use crate::api::state::GameSession;
-use crate::domain::models::{GameResult, Player};
+use crate::domain::models::{GameResult, Move, Player};
+use crate::infrastructure::ai::SearchLimits;
+
+/// Search the position after the reply the bot expects while the human
+/// thinks. `trigger_bot_move` keeps the search if the guess was right.
+fn start_pondering(session: &mut GameSession, bot_player: Player, reply: Option<&Move>) {
+    let Some(reply) = reply else {
+        return;
+    };
+    let mut board = session.game.board().clone();
+    if board.apply_move(reply).is_err() {
+        return;
+    }
+
+    let bot = match bot_player {
+        Player::White => session.white_bot.as_ref(),
+        Player::Black => session.black_bot.as_ref(),
+    };
+    let limits = SearchLimits {
+        ponder: true,
+        ..SearchLimits::default()
+    };
+    session.thinking = bot.map(|bot| bot.start_search(&board, bot_player, limits));
+    session.expected_reply = Some(reply.clone());
+}
 
 pub async fn trigger_bot_move(session_arc: Arc<tokio::sync::RwLock<GameSession>>) {
     loop {
@@ -39,36 +63,75 @@ pub async fn trigger_bot_move(session_arc: Arc<tokio::sync::RwLock<GameSession>>
             break;
         }
 
-        let best_move_opt = match current {
-            Player::White => {
-                if let Some(bot) = &mut session.white_bot {
-                    bot.get_move(&board_clone, current)
-                } else {
-                    None
-                }
+        // A ponder search on the move just played carries on, now on the
+        // clock. One on any other move searched the wrong position.
+        let expected = session.expected_reply.take();
+        let ponder = session.thinking.take();
+        let played = session.game.move_history().last().map(|(_, mv)| mv.clone());
+
+        let handle = match ponder {
+            Some(ponder) if expected.is_some() && expected == played => {
+                ponder.ponderhit();
+                ponder
             }
-            Player::Black => {
-                if let Some(bot) = &mut session.black_bot {
-                    bot.get_move(&board_clone, current)
-                } else {
-                    None
+            stale => {
+                // Both searches would share the bot's stop flag, so the old
+                // one must be over before the new one starts. It winds down
+                // without the lock, which means checking the game again.
+                if let Some(stale) = stale {
+                    stale.stop();
+                    drop(session);
+                    stale.await;
+                    session = session_arc.write().await;
+                    if session.game.status() != GameResult::InProgress
+                        || session.game.current_turn() != current
+                    {
+                        break;
+                    }
                 }
+                let bot = match current {
+                    Player::White => session.white_bot.as_ref(),
+                    Player::Black => session.black_bot.as_ref(),
+                };
+                let Some(bot) = bot else {
+                    break;
+                };
+                bot.start_search(&board_clone, current, SearchLimits::default())
             }
         };
 
-        if let Some(mv) = best_move_opt {
-            let _ = session.game.play_turn(mv);
-        } else {
+        // Think without holding the lock so the game stays readable and can
+        // be deleted, which stops the search.
+        session.thinking = Some(handle.clone());
+        drop(session);
+        let result = handle.clone().await;
+
+        let mut session = session_arc.write().await;
+        session.thinking = None;
+
+        if handle.was_stopped()
+            || session.game.status() != GameResult::InProgress
+            || session.game.current_turn() != current
+        {
             break;
         }
 
+        let Some(result) = result else {
+            break;
+        };
+        let _ = session.game.play_turn(result.best_move.clone());
+
         let next = session.game.current_turn();
         let next_is_bot = match next {
             Player::White => session.white_bot.is_some(),
             Player::Black => session.black_bot.is_some(),
         };
 
-        if session.game.status() != GameResult::InProgress || !next_is_bot {
+        if session.game.status() != GameResult::InProgress {
+            break;
+        }
+        if !next_is_bot {
+            start_pondering(&mut session, current, result.pv.get(1));
             break;
         }
     }
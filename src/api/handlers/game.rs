@@ -4,13 +4,13 @@ use axum::{
     response::{IntoResponse, Json},
 };
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
 use crate::api::handlers::bot::trigger_bot_move;
 use crate::api::models::{
-    ApiGameState, ApiPiece, ApiValidMove, MoveConsequence, NewGameRequest, NewGameResponse,
-    TurnRequest,
+    ApiBotInfo, ApiGameState, ApiPiece, ApiSearchInfo, ApiValidMove, MoveConsequence,
+    NewGameRequest, NewGameResponse, TurnRequest,
 };
 use crate::api::state::{AppState, GameSession};
 use crate::domain::board::Board;
@@ -18,7 +18,7 @@ use crate::domain::coordinate::Coordinate;
 use crate::domain::game::Game;
 use crate::domain::models::{GameResult, PieceType, Player};
 use crate::domain::rules::Rules;
-use crate::infrastructure::ai::MinimaxBot;
+use crate::infrastructure::ai::{MinimaxBot, Personality, SearchEvent, SearchInfo, Skill, memory};
 
 pub async fn create_game(
     State(state): State<AppState>,
@@ -26,9 +26,12 @@ pub async fn create_game(
 ) -> impl IntoResponse {
     let dimension = payload.dimension.unwrap_or(2);
     let side = payload.side.unwrap_or(8);
-
-    let board = Board::new(dimension, side);
-    let game = Game::new(board);
+    let skill = match payload.skill.map(Skill::new).transpose() {
+        Ok(skill) => skill,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let white_personality = payload.white_personality.unwrap_or_default();
+    let black_personality = payload.black_personality.unwrap_or_default();
 
     let uuid = Uuid::new_v4().to_string();
 
@@ -43,11 +46,40 @@ pub async fn create_game(
         }
     };
 
+    // Refuse boards the bots could not search within the memory budget,
+    // before building anything for them.
+    if has_white_bot || has_black_bot {
+        let compute = &state.config.compute;
+        if let Err(e) = memory::plan(dimension, side, compute.concurrency, compute.memory) {
+            return (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response();
+        }
+    }
+
+    let board = Board::new(dimension, side);
+    let game = Game::new(board);
+
     #[cfg(feature = "distributed")]
     if let Some(redis) = &state.redis {
         // Distributed / gateway mode: store session in Redis
         use crate::api::redis_store::{BotConfig, RedisSession};
 
+        // Distributed searches always play at full strength.
+        if skill.is_some_and(Skill::is_weakened) {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Skill levels are not supported in distributed mode",
+            )
+                .into_response();
+        }
+        if white_personality != Personality::Balanced || black_personality != Personality::Balanced
+        {
+            return (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Personalities are not supported in distributed mode",
+            )
+                .into_response();
+        }
+
         let session = RedisSession {
             game,
             white_bot_config: if has_white_bot {
@@ -86,18 +118,29 @@ pub async fn create_game(
     }
 
     // Standalone mode: store in-memory
-    let create_bot =
-        |config: &crate::config::AppConfig| -> Box<dyn crate::domain::services::PlayerStrategy + Send + Sync> {
-            Box::new(MinimaxBot::new(config, dimension, side))
-        };
+    let search_info: Arc<Mutex<Option<SearchInfo>>> = Arc::new(Mutex::new(None));
+    let create_bot = |config: &crate::config::AppConfig, personality: Personality| -> MinimaxBot {
+        let mut bot = MinimaxBot::new(config, dimension, side);
+        bot.set_skill(skill.unwrap_or_default());
+        bot.set_personality(personality);
+        let slot = search_info.clone();
+        bot.set_observer(Some(Arc::new(move |event: &SearchEvent| {
+            if let SearchEvent::Iteration(info) = event
+                && info.multipv == 1
+            {
+                *slot.lock().unwrap() = Some(info.clone());
+            }
+        })));
+        bot
+    };
 
     let white_bot = if has_white_bot {
-        Some(create_bot(&state.config))
+        Some(create_bot(&state.config, white_personality))
     } else {
         None
     };
     let black_bot = if has_black_bot {
-        Some(create_bot(&state.config))
+        Some(create_bot(&state.config, black_personality))
     } else {
         None
     };
@@ -106,6 +149,9 @@ pub async fn create_game(
         game,
         white_bot,
         black_bot,
+        thinking: None,
+        expected_reply: None,
+        search_info,
     };
 
     state
@@ -128,7 +174,9 @@ pub async fn get_game(
     if let Some(redis) = &state.redis {
         return match redis.get_session(&uuid).await {
             Ok(Some(session)) => {
-                let response = build_api_state_from_game(&session.game);
+                let mut response = build_api_state_from_game(&session.game);
+                response.white_bot = session.white_bot_config.as_ref().map(distributed_bot_info);
+                response.black_bot = session.black_bot_config.as_ref().map(distributed_bot_info);
                 (StatusCode::OK, Json(response)).into_response()
             }
             Ok(None) => (StatusCode::NOT_FOUND, "Game not found").into_response(),
@@ -143,13 +191,55 @@ pub async fn get_game(
     // Standalone mode
     if let Some(session_arc) = state.games.get(&uuid) {
         let session = session_arc.read().await;
-        let response = build_api_state_from_game(&session.game);
+        let mut response = build_api_state_from_game(&session.game);
+        response.search_info = session
+            .search_info
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(ApiSearchInfo::from);
+        response.white_bot = session.white_bot.as_ref().map(bot_info);
+        response.black_bot = session.black_bot.as_ref().map(bot_info);
         (StatusCode::OK, Json(response)).into_response()
     } else {
         (StatusCode::NOT_FOUND, "Game not found").into_response()
     }
 }
 
+pub async fn delete_game(
+    State(state): State<AppState>,
+    Path(uuid): Path<String>,
+) -> impl IntoResponse {
+    #[cfg(feature = "distributed")]
+    if let Some(redis) = &state.redis {
+        return match redis.delete_session(&uuid).await {
+            Ok(true) => StatusCode::NO_CONTENT.into_response(),
+            Ok(false) => (StatusCode::NOT_FOUND, "Game not found").into_response(),
+            Err(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Redis error: {}", e),
+            )
+                .into_response(),
+        };
+    }
+
+    // Standalone mode
+    let Some((_, session_arc)) = state.games.remove(&uuid) else {
+        return (StatusCode::NOT_FOUND, "Game not found").into_response();
+    };
+
+    // Removing the bots ends a bot loop that is between moves; stopping the
+    // search ends one that is thinking.
+    let mut session = session_arc.write().await;
+    session.white_bot = None;
+    session.black_bot = None;
+    if let Some(thinking) = session.thinking.take() {
+        thinking.stop();
+    }
+
+    StatusCode::NO_CONTENT.into_response()
+}
+
 pub async fn take_turn(
     State(state): State<AppState>,
     Json(payload): Json<TurnRequest>,
@@ -214,9 +304,18 @@ async fn take_turn_standalone(state: AppState, payload: TurnRequest) -> axum::re
         return (StatusCode::BAD_REQUEST, format!("Move failed: {:?}", e)).into_response();
     }
 
-    let response_state = build_api_state_from_game(&session.game);
+    let mut response_state = build_api_state_from_game(&session.game);
+    response_state.white_bot = session.white_bot.as_ref().map(bot_info);
+    response_state.black_bot = session.black_bot.as_ref().map(bot_info);
     let game_status = session.game.status();
 
+    // The bot loop picks up a ponder search; with the game over, nothing will.
+    if game_status != GameResult::InProgress
+        && let Some(ponder) = session.thinking.take()
+    {
+        ponder.stop();
+    }
+
     let next_player = session.game.current_turn();
     let next_is_bot = match next_player {
         Player::White => session.white_bot.is_some(),
@@ -316,7 +415,9 @@ async fn take_turn_distributed(
         return (StatusCode::BAD_REQUEST, format!("Move failed: {:?}", e)).into_response();
     }
 
-    let response_state = build_api_state_from_game(&session.game);
+    let mut response_state = build_api_state_from_game(&session.game);
+    response_state.white_bot = session.white_bot_config.as_ref().map(distributed_bot_info);
+    response_state.black_bot = session.black_bot_config.as_ref().map(distributed_bot_info);
 
     // Save updated session
     if let Err(e) = redis.save_session(uuid, &session).await {
@@ -395,7 +496,7 @@ async fn trigger_bot_move_distributed(
         let _ = redis.release_lock(&uuid, &holder).await;
 
         // Execute distributed search
-        let best_move = coordinator.search(&board, player).await;
+        let best_move = coordinator.search(&board, player, &uuid).await;
 
         // Re-acquire lock to apply move
         let locked = redis.acquire_lock(&uuid, &holder).await.unwrap_or(false);
@@ -432,6 +533,19 @@ async fn trigger_bot_move_distributed(
     }
 }
 
+fn bot_info(bot: &MinimaxBot) -> ApiBotInfo {
+    ApiBotInfo {
+        contempt: bot.contempt(),
+        ..ApiBotInfo::new(bot.skill(), bot.personality())
+    }
+}
+
+/// Distributed searches always play at full strength, balanced.
+#[cfg(feature = "distributed")]
+fn distributed_bot_info(_: &crate::api::redis_store::BotConfig) -> ApiBotInfo {
+    ApiBotInfo::new(Skill::default(), Personality::default())
+}
+
 fn build_api_state_from_game(game: &Game) -> ApiGameState {
     let board = game.board();
     let pieces = board
@@ -509,5 +623,8 @@ fn build_api_state_from_game(game: &Game) -> ApiGameState {
         side: board.side(),
         in_check: false,
         sequence: game.move_history().len(),
+        search_info: None,
+        white_bot: None,
+        black_bot: None,
     }
 }
@@ -1,4 +1,8 @@
+use crate::domain::board::notation;
 use crate::domain::models::{GameResult, PieceType, Player};
+use crate::infrastructure::ai::eval::{EvalTrace, TermScore};
+use crate::infrastructure::ai::result::{describe_score, mate_in};
+use crate::infrastructure::ai::{Personality, SearchInfo, Skill};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -12,6 +16,71 @@ pub struct ApiGameState {
     pub side: usize,
     pub in_check: bool,
     pub sequence: usize,
+    /// Latest completed iteration of the bot's search, if a bot has thought.
+    pub search_info: Option<ApiSearchInfo>,
+    /// The bot playing White, if any.
+    pub white_bot: Option<ApiBotInfo>,
+    /// The bot playing Black, if any.
+    pub black_bot: Option<ApiBotInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiBotInfo {
+    pub skill: u8,
+    /// Approximate Elo of a weakened bot; `None` at full strength.
+    pub elo: Option<u32>,
+    pub personality: Personality,
+    /// Centipawns a draw is worse than an equal position for the bot.
+    pub contempt: i32,
+}
+
+impl ApiBotInfo {
+    pub fn new(skill: Skill, personality: Personality) -> Self {
+        Self {
+            skill: skill.level(),
+            elo: skill.elo(),
+            personality,
+            contempt: personality.contempt(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiSearchInfo {
+    pub depth: usize,
+    pub seldepth: usize,
+    pub multipv: usize,
+    /// Centipawns from the side to move's point of view.
+    pub score: i32,
+    /// Moves to mate when `score` is a mate score: positive when the side to
+    /// move mates, negative when it gets mated.
+    pub mate: Option<i32>,
+    /// The score for display: "mate in 5", "mated in 2" or "+0.35".
+    pub score_text: String,
+    /// Principal variation in coordinate notation.
+    pub pv: Vec<String>,
+    pub nodes: u64,
+    pub nps: u64,
+    pub hashfull: u32,
+    pub time_ms: u64,
+}
+
+impl From<&SearchInfo> for ApiSearchInfo {
+    fn from(info: &SearchInfo) -> Self {
+        Self {
+            depth: info.depth,
+            seldepth: info.seldepth,
+            multipv: info.multipv,
+            score: info.score,
+            mate: mate_in(info.score),
+            score_text: describe_score(info.score),
+            pv: info.pv.iter().map(notation::format_move).collect(),
+            nodes: info.nodes,
+            nps: info.nps,
+            hashfull: info.hashfull,
+            time_ms: info.time.as_millis() as u64,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -39,6 +108,12 @@ pub struct NewGameRequest {
     pub mode: String,
     pub dimension: Option<usize>,
     pub side: Option<usize>,
+    /// Strength of the game's bots, 0 to 20; full strength (20) if unset.
+    pub skill: Option<u8>,
+    /// Style of the White bot; balanced if unset.
+    pub white_personality: Option<Personality>,
+    /// Style of the Black bot; balanced if unset.
+    pub black_personality: Option<Personality>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -52,3 +127,89 @@ pub struct TurnRequest {
 pub struct NewGameResponse {
     pub uuid: String,
 }
+
+#[derive(Deserialize, Debug)]
+pub struct AnalysisRequest {
+    /// Analyse the current position of this game...
+    pub uuid: Option<String>,
+    /// ...or an explicit position in HFEN.
+    pub hfen: Option<String>,
+    pub multi_pv: Option<usize>,
+    pub depth: Option<usize>,
+    pub movetime_ms: Option<u64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct AnalysisResponse {
+    /// Best lines first.
+    pub lines: Vec<ApiSearchInfo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct EvaluationRequest {
+    /// Evaluate the current position of this game...
+    pub uuid: Option<String>,
+    /// ...or an explicit position in HFEN.
+    pub hfen: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ApiTermScore {
+    pub mg: i32,
+    pub eg: i32,
+}
+
+impl From<TermScore> for ApiTermScore {
+    fn from(score: TermScore) -> Self {
+        Self {
+            mg: score.mg,
+            eg: score.eg,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct ApiEvalTerm {
+    pub term: &'static str,
+    /// Each side's contribution from its own point of view.
+    pub white: ApiTermScore,
+    pub black: ApiTermScore,
+}
+
+/// The static evaluation term by term, in centipawns from White's view.
+#[derive(Serialize, Debug)]
+pub struct ApiEvalTrace {
+    pub terms: Vec<ApiEvalTerm>,
+    pub mg: i32,
+    pub eg: i32,
+    pub phase: i32,
+    pub start_phase: i32,
+    pub tapered: i32,
+    pub tempo: i32,
+    pub rule50: i32,
+    pub value: i32,
+}
+
+impl From<&EvalTrace> for ApiEvalTrace {
+    fn from(trace: &EvalTrace) -> Self {
+        Self {
+            terms: trace
+                .terms
+                .iter()
+                .map(|t| ApiEvalTerm {
+                    term: t.term.name(),
+                    white: t.white.into(),
+                    black: t.black.into(),
+                })
+                .collect(),
+            mg: trace.mg,
+            eg: trace.eg,
+            phase: trace.phase,
+            start_phase: trace.start_phase,
+            tapered: trace.tapered,
+            tempo: trace.tempo,
+            rule50: trace.rule50,
+            value: trace.value,
+        }
+    }
+}
@@ -95,6 +95,19 @@ impl RedisSessionStore {
         }
     }
 
+    /// Delete a session. Returns false if it did not exist.
+    pub async fn delete_session(&self, uuid: &str) -> Result<bool, String> {
+        let key = format!("session:{}", uuid);
+        let mut conn = self.conn().await;
+
+        let removed: u64 = conn
+            .del(&key)
+            .await
+            .map_err(|e| format!("Redis DEL failed: {}", e))?;
+
+        Ok(removed > 0)
+    }
+
     /// Acquire a distributed lock for a game session.
     /// Returns true if lock was acquired.
     pub async fn acquire_lock(&self, uuid: &str, holder: &str) -> Result<bool, String> {
@@ -3,14 +3,17 @@ use axum::{
     routing::{get, post},
 };
 
-use crate::api::handlers::game::{create_game, get_game, take_turn};
+use crate::api::handlers::analysis::{analyze_position, evaluate_position};
+use crate::api::handlers::game::{create_game, delete_game, get_game, take_turn};
 use crate::api::state::AppState;
 
 pub fn app_router(state: AppState) -> Router {
     let api_routes = Router::new()
         .route("/new_game", post(create_game))
-        .route("/game/:uuid", get(get_game))
-        .route("/take_turn", post(take_turn));
+        .route("/game/:uuid", get(get_game).delete(delete_game))
+        .route("/take_turn", post(take_turn))
+        .route("/analysis", post(analyze_position))
+        .route("/evaluation", post(evaluate_position));
 
     Router::new().nest("/api/v1", api_routes).with_state(state)
 }
@@ -1,16 +1,27 @@
 use crate::domain::game::Game;
 
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 
-use crate::domain::services::PlayerStrategy;
+use crate::domain::models::Move;
+use crate::infrastructure::ai::{MinimaxBot, SearchHandle, SearchInfo};
 
 pub struct GameSession {
     pub game: Game,
 
-    pub white_bot: Option<Box<dyn PlayerStrategy + Send + Sync>>,
-    pub black_bot: Option<Box<dyn PlayerStrategy + Send + Sync>>,
+    pub white_bot: Option<MinimaxBot>,
+    pub black_bot: Option<MinimaxBot>,
+
+    /// Search for the bot's current move, kept so it can be stopped when the
+    /// game is deleted. Between moves, a bot's ponder search.
+    pub thinking: Option<SearchHandle>,
+    /// Reply the ponder search in `thinking` assumes the human will play.
+    pub expected_reply: Option<Move>,
+
+    /// Latest iteration reported by either bot's search observer. A plain
+    /// mutex because it is written from search threads.
+    pub search_info: Arc<Mutex<Option<SearchInfo>>>,
 }
 
 pub type GameStore = Arc<DashMap<String, Arc<RwLock<GameSession>>>>;
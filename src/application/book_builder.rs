@@ -0,0 +1,177 @@
+//! Opening books from self-play.
+//!
+//! For the first `depth` plies each engine picks at random among its
+//! `candidates` best root moves that score within `margin` of the best, so
+//! games branch into many sound openings; after that it plays its best move
+//! until the game ends as in [`match_runner`](super::match_runner). The
+//! games are then weighted by [`BookBuilder`].
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+use crate::application::match_runner::{Termination, adjudicate, single_thread_bot};
+use crate::domain::board::Board;
+use crate::domain::models::{GameResult, Move, Player};
+use crate::infrastructure::ai::{EvalParams, MemoryError, MinimaxBot, SearchLimits};
+use crate::infrastructure::book::{BookBuilder, OpeningBook};
+use crate::infrastructure::training_data::GameOutcome;
+
+#[derive(Clone, Debug)]
+pub struct SelfPlayBookOptions {
+    pub dimension: usize,
+    pub side: usize,
+    pub games: usize,
+    /// Plies recorded in the book, and played with variety.
+    pub depth: usize,
+    /// Root moves considered at each book ply.
+    pub candidates: usize,
+    /// Centipawns below the best move within which a candidate may be
+    /// played.
+    pub margin: i32,
+    /// Limits for every move.
+    pub limits: SearchLimits,
+    /// Games played at once.
+    pub concurrency: usize,
+    /// Search memory for each concurrent game, in MB.
+    pub memory_mb: usize,
+    /// Plies after which a game is drawn.
+    pub max_plies: usize,
+    /// Seed for the choices among candidates.
+    pub seed: u64,
+}
+
+impl Default for SelfPlayBookOptions {
+    fn default() -> Self {
+        Self {
+            dimension: 2,
+            side: 8,
+            games: 100,
+            depth: 16,
+            candidates: 4,
+            margin: 30,
+            limits: SearchLimits::depth(5),
+            concurrency: 1,
+            memory_mb: 16,
+            max_plies: 300,
+            seed: 0,
+        }
+    }
+}
+
+/// A finished self-play game.
+#[derive(Clone, Debug)]
+pub struct BookGame {
+    pub index: usize,
+    pub moves: Vec<Move>,
+    pub result: GameResult,
+    pub termination: Termination,
+}
+
+/// Play `options.games` self-play games with `params` and build a book from
+/// them, calling `on_game` as each finishes.
+pub fn build_from_self_play(
+    options: &SelfPlayBookOptions,
+    params: &EvalParams,
+    mut on_game: impl FnMut(&BookGame),
+) -> Result<OpeningBook, MemoryError> {
+    let bots = (0..options.concurrency.max(1))
+        .map(|_| {
+            single_thread_bot(
+                &options.limits,
+                params.clone(),
+                options.dimension,
+                options.side,
+                options.memory_mb,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let next_game = AtomicUsize::new(0);
+    let mut builder = BookBuilder::new(options.dimension, options.side, options.depth);
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for mut bot in bots {
+            let tx = tx.clone();
+            let next_game = &next_game;
+            scope.spawn(move || {
+                loop {
+                    let index = next_game.fetch_add(1, Ordering::Relaxed);
+                    if index >= options.games {
+                        break;
+                    }
+                    if tx.send(play_game(&mut bot, options, index)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for game in rx {
+            let outcome = match game.result {
+                GameResult::Checkmate(Player::White) => GameOutcome::WhiteWin,
+                GameResult::Checkmate(Player::Black) => GameOutcome::BlackWin,
+                _ => GameOutcome::Draw,
+            };
+            builder
+                .add_game(&game.moves, outcome)
+                .expect("self-play moves are legal");
+            on_game(&game);
+        }
+    });
+
+    Ok(builder.build())
+}
+
+fn play_game(bot: &mut MinimaxBot, options: &SelfPlayBookOptions, index: usize) -> BookGame {
+    let mut rng = StdRng::seed_from_u64(options.seed.wrapping_add(index as u64));
+    let mut board = Board::new(options.dimension, options.side);
+    let mut player = Player::White;
+    let mut moves = Vec::new();
+    bot.new_game();
+
+    let (result, termination) = loop {
+        let legal = match adjudicate(&mut board, player, moves.len(), options.max_plies) {
+            Ok(legal) => legal,
+            Err(end) => break end,
+        };
+        let limits = if moves.len() < options.depth {
+            SearchLimits {
+                multi_pv: options.candidates.max(1),
+                ..options.limits.clone()
+            }
+        } else {
+            options.limits.clone()
+        };
+        let mv = match bot.search(&board, player, &limits) {
+            Some(found) => {
+                let close: Vec<&Move> = found
+                    .lines
+                    .iter()
+                    .filter(|l| l.score >= found.score - options.margin)
+                    .map(|l| &l.pv[0])
+                    .collect();
+                close
+                    .choose(&mut rng)
+                    .map_or(found.best_move.clone(), |m| (*m).clone())
+            }
+            None => legal[0].clone(),
+        };
+        board.apply_move(&mv).expect("searched moves are legal");
+        moves.push(mv);
+        player = player.opponent();
+    };
+
+    BookGame {
+        index,
+        moves,
+        result,
+        termination,
+    }
+}
@@ -0,0 +1,233 @@
+//! Self-play training data: each position's search score and best move,
+//! labelled with the result of its game.
+//!
+//! Games start from random openings and end as in
+//! [`match_runner`](super::match_runner). The opening plies are not recorded.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::application::match_runner::{
+    Termination, adjudicate, random_opening, single_thread_bot,
+};
+use crate::domain::board::Board;
+use crate::domain::models::{GameResult, PieceType, Player};
+use crate::domain::rules::Rules;
+use crate::infrastructure::ai::{EvalParams, MemoryError, MinimaxBot, SearchLimits, SearchResult};
+use crate::infrastructure::training_data::{GameOutcome, TrainingDataWriter, TrainingRecord};
+
+#[derive(Clone, Debug)]
+pub struct DatagenOptions {
+    pub dimension: usize,
+    pub side: usize,
+    pub games: usize,
+    /// Games played at once.
+    pub concurrency: usize,
+    /// Search memory for each concurrent game, in MB.
+    pub memory_mb: usize,
+    /// Limits for every move; keep them small, as volume matters more than
+    /// depth.
+    pub limits: SearchLimits,
+    /// Random legal plies played before the engine takes over.
+    pub random_plies: usize,
+    /// Plies, openings included, after which a game is drawn.
+    pub max_plies: usize,
+    /// Seed for the openings.
+    pub seed: u64,
+    /// Record only quiet positions: not in check, a best move that neither
+    /// captures nor promotes, and no mate score.
+    pub quiet_only: bool,
+}
+
+impl Default for DatagenOptions {
+    fn default() -> Self {
+        Self {
+            dimension: 2,
+            side: 8,
+            games: 100,
+            concurrency: 1,
+            memory_mb: 16,
+            limits: SearchLimits::depth(4),
+            random_plies: 8,
+            max_plies: 400,
+            seed: 0,
+            quiet_only: false,
+        }
+    }
+}
+
+/// A finished self-play game and the positions recorded from it.
+#[derive(Clone, Debug)]
+pub struct DatagenGame {
+    pub index: usize,
+    pub result: GameResult,
+    pub termination: Termination,
+    pub plies: usize,
+    pub records: Vec<TrainingRecord>,
+}
+
+#[derive(Debug)]
+pub enum DatagenError {
+    Memory(MemoryError),
+    Io(io::Error),
+}
+
+impl fmt::Display for DatagenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DatagenError::Memory(e) => write!(f, "{e}"),
+            DatagenError::Io(e) => write!(f, "cannot write training data: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DatagenError {}
+
+/// Play `options.games` self-play games with `params`, write their
+/// positions to `writer` as each game finishes and call `on_game` after.
+/// Returns the number of positions written.
+pub fn generate<W: Write>(
+    options: &DatagenOptions,
+    params: &EvalParams,
+    writer: &mut TrainingDataWriter<W>,
+    mut on_game: impl FnMut(&DatagenGame),
+) -> Result<usize, DatagenError> {
+    let bots = (0..options.concurrency.max(1))
+        .map(|_| {
+            single_thread_bot(
+                &options.limits,
+                params.clone(),
+                options.dimension,
+                options.side,
+                options.memory_mb,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(DatagenError::Memory)?;
+
+    let next_game = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let mut written = 0;
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for mut bot in bots {
+            let tx = tx.clone();
+            let (next_game, stop) = (&next_game, &stop);
+            scope.spawn(move || {
+                loop {
+                    let index = next_game.fetch_add(1, Ordering::Relaxed);
+                    if index >= options.games || stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let game = play_game(&mut bot, options, index);
+                    if tx.send(game).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for game in rx {
+            for record in &game.records {
+                if let Err(e) = writer.write(record) {
+                    // Dropping the receiver stops the workers after their
+                    // current game.
+                    stop.store(true, Ordering::Relaxed);
+                    return Err(DatagenError::Io(e));
+                }
+            }
+            written += game.records.len();
+            on_game(&game);
+        }
+        Ok(())
+    })?;
+
+    Ok(written)
+}
+
+/// Play game `index` from its random opening, recording every position the
+/// engine searches (or only the quiet ones).
+fn play_game(bot: &mut MinimaxBot, options: &DatagenOptions, index: usize) -> DatagenGame {
+    let (mut board, mut player) = random_opening(
+        options.dimension,
+        options.side,
+        options.random_plies,
+        options.seed.wrapping_add(index as u64),
+    );
+    bot.new_game();
+    let mut plies = board.state.history.len();
+    let mut positions = Vec::new();
+
+    let (result, termination) = loop {
+        let moves = match adjudicate(&mut board, player, plies, options.max_plies) {
+            Ok(moves) => moves,
+            Err(end) => break end,
+        };
+        let Some(found) = bot.search(&board, player, &options.limits) else {
+            board
+                .apply_move(&moves[0])
+                .expect("generated moves are legal");
+            player = player.opponent();
+            plies += 1;
+            continue;
+        };
+        if !options.quiet_only || is_quiet(&board, player, &found) {
+            let score = match player {
+                Player::White => found.score,
+                Player::Black => -found.score,
+            };
+            positions.push((
+                board.to_hfen(player),
+                score.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+                found.best_move.clone(),
+            ));
+        }
+        board
+            .apply_move(&found.best_move)
+            .expect("searched moves are legal");
+        player = player.opponent();
+        plies += 1;
+    };
+
+    let outcome = match result {
+        GameResult::Checkmate(Player::White) => GameOutcome::WhiteWin,
+        GameResult::Checkmate(Player::Black) => GameOutcome::BlackWin,
+        _ => GameOutcome::Draw,
+    };
+    DatagenGame {
+        index,
+        result,
+        termination,
+        plies,
+        records: positions
+            .into_iter()
+            .map(|(hfen, score, best_move)| TrainingRecord {
+                hfen,
+                score,
+                best_move,
+                result: outcome,
+            })
+            .collect(),
+    }
+}
+
+/// The position is neither tactical nor decided, so its static evaluation
+/// should agree with the search.
+fn is_quiet(board: &Board, player: Player, found: &SearchResult) -> bool {
+    let mv = &found.best_move;
+    let en_passant = board.get_piece(&mv.from).map(|p| p.piece_type) == Some(PieceType::Pawn)
+        && board
+            .state
+            .en_passant_target
+            .is_some_and(|(target, _)| board.coords_to_index(&mv.to.values) == Some(target));
+    found.mate.is_none()
+        && mv.promotion.is_none()
+        && board.get_piece(&mv.to).is_none()
+        && !en_passant
+        && !Rules::is_in_check(board, player)
+}
@@ -0,0 +1,348 @@
+//! Engine-versus-engine matches for measuring engine changes.
+//!
+//! Games are played in pairs from the same randomised opening with the
+//! colours swapped, so a lopsided opening favours neither engine. Games end
+//! by mate, stalemate, threefold repetition, the fifty-move rule or a ply
+//! limit, which is scored as a draw.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::application::sprt::{MatchScore, Sprt, SprtVerdict};
+use crate::domain::board::Board;
+use crate::domain::models::{GameResult, Player};
+use crate::domain::rules::{MoveList, Rules};
+use crate::infrastructure::ai::{
+    EvalParamSet, EvalParams, MemoryError, MinimaxBot, Network, Personality, SearchLimits, Skill,
+};
+
+/// One side of a match: a `MinimaxBot` with its own limits and weights.
+#[derive(Clone, Debug, Default)]
+pub struct MatchEngine {
+    pub name: String,
+    /// Limits for every move. Should set a depth, move time or node count;
+    /// a search with none of them stops after one ply.
+    pub limits: SearchLimits,
+    /// Evaluation weights, resolved for the match's board.
+    pub params: EvalParamSet,
+    /// Network evaluating in place of the weights on the board it was
+    /// trained for.
+    pub network: Option<Arc<Network>>,
+    /// Playing strength; full strength by default.
+    pub skill: Skill,
+    /// Playing style; balanced by default.
+    pub personality: Personality,
+    /// Contempt in place of the personality's.
+    pub contempt: Option<i32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MatchOptions {
+    pub dimension: usize,
+    pub side: usize,
+    /// Game pairs to play unless the SPRT stops the match first.
+    pub pairs: usize,
+    /// Games played at once.
+    pub concurrency: usize,
+    /// Search memory for each engine in each concurrent game, in MB.
+    pub memory_mb: usize,
+    /// Random legal plies played before the engines take over.
+    pub random_plies: usize,
+    /// Plies, openings included, after which a game is drawn.
+    pub max_plies: usize,
+    /// Seed for the openings; the same seed gives the same openings.
+    pub seed: u64,
+    pub sprt: Option<Sprt>,
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            dimension: 2,
+            side: 8,
+            pairs: 100,
+            concurrency: 1,
+            memory_mb: 16,
+            random_plies: 8,
+            max_plies: 400,
+            seed: 0,
+            sprt: None,
+        }
+    }
+}
+
+/// Why a game ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Termination {
+    Checkmate,
+    Stalemate,
+    Repetition,
+    FiftyMoves,
+    PlyLimit,
+}
+
+/// A finished game of a match.
+#[derive(Clone, Debug)]
+pub struct MatchGame {
+    /// Index of the opening; both games of a pair share it.
+    pub pair: usize,
+    pub a_is_white: bool,
+    /// HFEN of the position the engines started from.
+    pub opening: String,
+    pub result: GameResult,
+    pub termination: Termination,
+    pub plies: usize,
+}
+
+impl MatchGame {
+    /// Points scored by engine A: 1, 0.5 or 0.
+    pub fn points_for_a(&self) -> f64 {
+        match self.result {
+            GameResult::Checkmate(winner) => {
+                let a = if self.a_is_white {
+                    Player::White
+                } else {
+                    Player::Black
+                };
+                if winner == a { 1.0 } else { 0.0 }
+            }
+            _ => 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MatchReport {
+    /// Engine A's results against B.
+    pub score: MatchScore,
+    /// Every game, ordered by pair with A as White first.
+    pub games: Vec<MatchGame>,
+    pub verdict: Option<SprtVerdict>,
+}
+
+/// Play `a` against `b`, calling `on_game` with each game as it finishes
+/// and the score so far. Stops early once the SPRT, if any, reaches a
+/// verdict; games already under way are still counted.
+pub fn play_match(
+    a: &MatchEngine,
+    b: &MatchEngine,
+    options: &MatchOptions,
+    mut on_game: impl FnMut(&MatchGame, &MatchScore),
+) -> Result<MatchReport, MemoryError> {
+    // Allocate every engine up front so a memory error stops the match
+    // before any game starts.
+    let seats = (0..options.concurrency.max(1))
+        .map(|_| Ok((new_bot(a, options)?, new_bot(b, options)?)))
+        .collect::<Result<Vec<_>, MemoryError>>()?;
+
+    let next_pair = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let mut report = MatchReport {
+        score: MatchScore::default(),
+        games: Vec::new(),
+        verdict: None,
+    };
+
+    thread::scope(|scope| {
+        let (tx, rx) = mpsc::channel();
+        for (mut bot_a, mut bot_b) in seats {
+            let tx = tx.clone();
+            let (next_pair, stop) = (&next_pair, &stop);
+            scope.spawn(move || {
+                loop {
+                    let pair = next_pair.fetch_add(1, Ordering::Relaxed);
+                    if pair >= options.pairs || stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let (opening, to_move) = random_opening(
+                        options.dimension,
+                        options.side,
+                        options.random_plies,
+                        options.seed.wrapping_add(pair as u64),
+                    );
+                    for a_is_white in [true, false] {
+                        let game = play_game(
+                            &opening,
+                            to_move,
+                            (&mut bot_a, a),
+                            (&mut bot_b, b),
+                            a_is_white,
+                            options,
+                        );
+                        let game = MatchGame {
+                            pair,
+                            a_is_white,
+                            opening: opening.to_hfen(to_move),
+                            ..game
+                        };
+                        if tx.send(game).is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for game in rx {
+            report.score.record(game.points_for_a());
+            on_game(&game, &report.score);
+            report.games.push(game);
+            if report.verdict.is_none()
+                && let Some(sprt) = &options.sprt
+            {
+                report.verdict = sprt.verdict(&report.score);
+                if report.verdict.is_some() {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            }
+        }
+    });
+
+    report.games.sort_by_key(|g| (g.pair, !g.a_is_white));
+    Ok(report)
+}
+
+fn new_bot(engine: &MatchEngine, options: &MatchOptions) -> Result<MinimaxBot, MemoryError> {
+    let (dimension, side) = (options.dimension, options.side);
+    let params = engine.params.resolve(dimension, side);
+    let mut bot = single_thread_bot(&engine.limits, params, dimension, side, options.memory_mb)?;
+    bot.set_network(engine.network.clone());
+    bot.set_skill(engine.skill);
+    bot.set_personality(engine.personality);
+    if let Some(contempt) = engine.contempt {
+        bot.set_contempt(contempt);
+    }
+    Ok(bot)
+}
+
+/// A one-thread bot that searches within `limits`, for running many games
+/// side by side.
+pub(crate) fn single_thread_bot(
+    limits: &SearchLimits,
+    params: EvalParams,
+    dimension: usize,
+    side: usize,
+    memory_mb: usize,
+) -> Result<MinimaxBot, MemoryError> {
+    let mut bot = MinimaxBot::try_new_from_params(
+        limits.depth.unwrap_or(1),
+        limits.movetime.unwrap_or(Duration::ZERO),
+        memory_mb,
+        1,
+        dimension,
+        side,
+    )?;
+    bot.set_eval_params(params);
+    Ok(bot)
+}
+
+/// The start position after `random_plies` random legal moves, the same for
+/// every call with the same seed. Lines that end the game are redrawn.
+pub(crate) fn random_opening(
+    dimension: usize,
+    side: usize,
+    random_plies: usize,
+    seed: u64,
+) -> (Board, Player) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    'draw: loop {
+        let mut board = Board::new(dimension, side);
+        let mut player = Player::White;
+        for _ in 0..random_plies {
+            let moves = Rules::generate_legal_moves(&mut board, player);
+            if moves.is_empty() {
+                continue 'draw;
+            }
+            let mv = &moves[rng.gen_range(0..moves.len())];
+            board.apply_move(mv).expect("generated moves are legal");
+            player = player.opponent();
+        }
+        if !Rules::generate_legal_moves(&mut board, player).is_empty() {
+            return (board, player);
+        }
+    }
+}
+
+/// How the game stands with `player` to move after `plies` plies, if it is
+/// over, along with the legal moves.
+pub(crate) fn adjudicate(
+    board: &mut Board,
+    player: Player,
+    plies: usize,
+    max_plies: usize,
+) -> Result<MoveList, (GameResult, Termination)> {
+    if board.state.halfmove_clock >= 100 {
+        return Err((GameResult::Draw, Termination::FiftyMoves));
+    }
+    let hash = board.state.hash;
+    if board.state.history.iter().filter(|&&h| h == hash).count() >= 2 {
+        return Err((GameResult::Draw, Termination::Repetition));
+    }
+    let moves = Rules::generate_legal_moves(board, player);
+    if moves.is_empty() {
+        return Err(if Rules::is_in_check(board, player) {
+            (
+                GameResult::Checkmate(player.opponent()),
+                Termination::Checkmate,
+            )
+        } else {
+            (GameResult::Stalemate, Termination::Stalemate)
+        });
+    }
+    if plies >= max_plies {
+        return Err((GameResult::Draw, Termination::PlyLimit));
+    }
+    Ok(moves)
+}
+
+/// Play one game from `opening`, with engine A as White if `a_is_white`.
+/// The returned game has its pair, colours and opening left to fill in.
+fn play_game(
+    opening: &Board,
+    to_move: Player,
+    (bot_a, a): (&mut MinimaxBot, &MatchEngine),
+    (bot_b, b): (&mut MinimaxBot, &MatchEngine),
+    a_is_white: bool,
+    options: &MatchOptions,
+) -> MatchGame {
+    bot_a.new_game();
+    bot_b.new_game();
+    let mut board = opening.clone();
+    let mut player = to_move;
+    let mut plies = opening.state.history.len();
+
+    let (result, termination) = loop {
+        let moves = match adjudicate(&mut board, player, plies, options.max_plies) {
+            Ok(moves) => moves,
+            Err(end) => break end,
+        };
+
+        let (bot, engine) = if (player == Player::White) == a_is_white {
+            (&mut *bot_a, a)
+        } else {
+            (&mut *bot_b, b)
+        };
+        let mv = bot
+            .search(&board, player, &engine.limits)
+            .map(|result| result.best_move)
+            .unwrap_or_else(|| moves[0].clone());
+        board.apply_move(&mv).expect("searched moves are legal");
+        player = player.opponent();
+        plies += 1;
+    };
+
+    MatchGame {
+        pair: 0,
+        a_is_white,
+        opening: String::new(),
+        result,
+        termination,
+        plies,
+    }
+}
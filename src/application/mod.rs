@@ -1 +1,5 @@
+pub mod book_builder;
+pub mod datagen;
 pub mod game_service;
+pub mod match_runner;
+pub mod sprt;
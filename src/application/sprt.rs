@@ -0,0 +1,207 @@
+//! Elo estimates and the sequential probability ratio test for engine
+//! matches.
+//!
+//! The SPRT uses the normal approximation to the trinomial log-likelihood
+//! ratio, with logistic Elo for both hypotheses.
+
+use std::fmt;
+
+/// Games won, drawn and lost by one side of a match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatchScore {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MatchScore {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Record a game scored 1, 0.5 or 0.
+    pub fn record(&mut self, points: f64) {
+        if points > 0.75 {
+            self.wins += 1;
+        } else if points < 0.25 {
+            self.losses += 1;
+        } else {
+            self.draws += 1;
+        }
+    }
+
+    /// Mean points per game; 0.5 before any game is played.
+    pub fn score(&self) -> f64 {
+        match self.games() {
+            0 => 0.5,
+            n => (self.wins as f64 + 0.5 * self.draws as f64) / n as f64,
+        }
+    }
+
+    /// Variance of the points of a single game.
+    fn variance(&self) -> f64 {
+        let n = self.games().max(1) as f64;
+        let s = self.score();
+        (self.wins as f64 * (1.0 - s).powi(2)
+            + self.draws as f64 * (0.5 - s).powi(2)
+            + self.losses as f64 * s.powi(2))
+            / n
+    }
+
+    /// Elo difference implied by the score; infinite after a clean sweep.
+    pub fn elo(&self) -> f64 {
+        score_to_elo(self.score())
+    }
+
+    /// Half-width of the 95% confidence interval around [`Self::elo`].
+    pub fn elo_error(&self) -> f64 {
+        let n = self.games();
+        if n == 0 {
+            return f64::INFINITY;
+        }
+        let margin = 1.96 * (self.variance() / n as f64).sqrt();
+        let s = self.score();
+        (score_to_elo(s + margin) - score_to_elo(s - margin)) / 2.0
+    }
+}
+
+impl fmt::Display for MatchScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "+{} ={} -{} ({:.1}%)",
+            self.wins,
+            self.draws,
+            self.losses,
+            100.0 * self.score()
+        )
+    }
+}
+
+/// Expected score of a side `elo` points stronger.
+pub fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo difference for an expected score, saturating at 0 and 1.
+pub fn score_to_elo(score: f64) -> f64 {
+    if score <= 0.0 {
+        f64::NEG_INFINITY
+    } else if score >= 1.0 {
+        f64::INFINITY
+    } else {
+        400.0 * (score / (1.0 - score)).log10()
+    }
+}
+
+/// Test `H0: elo = elo0` against `H1: elo = elo1` with false positive rate
+/// `alpha` and false negative rate `beta`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprt {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SprtVerdict {
+    /// No better than `elo0`.
+    AcceptH0,
+    /// At least `elo1`.
+    AcceptH1,
+}
+
+impl fmt::Display for SprtVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SprtVerdict::AcceptH0 => write!(f, "H0 accepted"),
+            SprtVerdict::AcceptH1 => write!(f, "H1 accepted"),
+        }
+    }
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Self {
+            elo0: 0.0,
+            elo1: 5.0,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+}
+
+impl Sprt {
+    /// Log-likelihood ratio at which H0 and H1 are accepted.
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Log-likelihood ratio of H1 over H0 given `score`.
+    pub fn llr(&self, score: &MatchScore) -> f64 {
+        let variance = score.variance();
+        if score.games() == 0 || variance == 0.0 {
+            return 0.0;
+        }
+        let (s0, s1) = (elo_to_score(self.elo0), elo_to_score(self.elo1));
+        0.5 * score.games() as f64 * (s1 - s0) * (2.0 * score.score() - s0 - s1) / variance
+    }
+
+    /// The hypothesis `score` accepts, or `None` to keep playing.
+    pub fn verdict(&self, score: &MatchScore) -> Option<SprtVerdict> {
+        let (lower, upper) = self.bounds();
+        let llr = self.llr(score);
+        if llr >= upper {
+            Some(SprtVerdict::AcceptH1)
+        } else if llr <= lower {
+            Some(SprtVerdict::AcceptH0)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elo_round_trips_through_score() {
+        assert_eq!(score_to_elo(0.5), 0.0);
+        assert!((score_to_elo(elo_to_score(100.0)) - 100.0).abs() < 1e-9);
+        assert!((elo_to_score(400.0) - 10.0 / 11.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sprt_decides_lopsided_matches() {
+        let sprt = Sprt::default();
+        let (lower, upper) = sprt.bounds();
+        assert!((lower + 2.944).abs() < 1e-3 && (upper - 2.944).abs() < 1e-3);
+
+        let even = MatchScore {
+            wins: 10,
+            draws: 20,
+            losses: 10,
+        };
+        assert_eq!(sprt.verdict(&even), None);
+        assert!(even.elo_error() > 50.0);
+
+        let strong = MatchScore {
+            wins: 900,
+            draws: 1200,
+            losses: 600,
+        };
+        assert_eq!(sprt.verdict(&strong), Some(SprtVerdict::AcceptH1));
+
+        let weak = MatchScore {
+            wins: 600,
+            draws: 1200,
+            losses: 900,
+        };
+        assert_eq!(sprt.verdict(&weak), Some(SprtVerdict::AcceptH0));
+    }
+}
@@ -1,9 +1,11 @@
 use hyperchess::config::AppConfig;
 use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation;
 use hyperchess::domain::models::Player;
-use hyperchess::domain::services::PlayerStrategy;
-use hyperchess::infrastructure::ai::MinimaxBot;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::infrastructure::console::ConsoleSearchObserver;
 use std::env;
+use std::sync::Arc;
 use std::time::Instant;
 
 fn main() {
@@ -12,8 +14,7 @@ fn main() {
     let dimension: usize = args.get(1).and_then(|s| s.parse().ok()).unwrap_or(2);
     let depth: usize = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(10);
 
-    let mut config = AppConfig::load();
-    config.minimax.depth = depth;
+    let config = AppConfig::load();
 
     println!(
         "Profiling {}D chess, depth {}, concurrency {}",
@@ -22,13 +23,23 @@ fn main() {
 
     let board = Board::new(dimension, 8);
     let mut bot = MinimaxBot::new(&config, dimension, 8);
+    bot.set_observer(Some(Arc::new(ConsoleSearchObserver)));
 
     let start = Instant::now();
-    let mv = bot.get_move(&board, Player::White);
+    let result = bot.search(&board, Player::White, &SearchLimits::depth(depth));
     let elapsed = start.elapsed();
 
-    match mv {
-        Some(m) => println!("Best move: {:?} in {:.2?}", m, elapsed),
+    match result {
+        Some(r) => {
+            let pv: Vec<String> = r.pv.iter().map(notation::format_move).collect();
+            println!(
+                "Best move: {} ({} nodes) in {:.2?}, pv {}",
+                notation::format_move(&r.best_move),
+                r.nodes,
+                elapsed,
+                pv.join(" ")
+            );
+        }
         None => println!("No move found in {:.2?}", elapsed),
     }
 }
@@ -0,0 +1,159 @@
+use hyperchess::application::book_builder::{SelfPlayBookOptions, build_from_self_play};
+use hyperchess::config::AppConfig;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation::format_move;
+use hyperchess::domain::models::{GameResult, Player};
+use hyperchess::infrastructure::book::{BookBuilder, OpeningBook, parse_game};
+use std::env;
+use std::fs;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "Usage:
+  hyperchess-book selfplay <output> [--dim D] [--side S] [--games N] [--book-depth N] \
+[--candidates N] [--margin CP] [--depth N] [--nodes N] [--movetime MS] [--concurrency N] \
+[--hash MB] [--max-plies N] [--seed N]
+  hyperchess-book import <games.txt> <output> [--dim D] [--side S] [--book-depth N]
+  hyperchess-book show <book> [hfen]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("selfplay") if args.len() >= 2 => selfplay(&args[1], &args[2..]),
+        Some("import") if args.len() >= 3 => import(&args[1], &args[2], &args[3..]),
+        Some("show") if args.len() >= 2 => show(&args[1], args.get(2)),
+        _ => exit_with(USAGE),
+    }
+}
+
+fn selfplay(output: &str, args: &[String]) {
+    let config = AppConfig::load();
+    let mut options = SelfPlayBookOptions {
+        depth: config.book.depth,
+        concurrency: config.compute.concurrency.max(1),
+        ..SelfPlayBookOptions::default()
+    };
+    let mut hash = None;
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().unwrap_or_else(|| exit_with(USAGE));
+        match flag.as_str() {
+            "--dim" => options.dimension = parse_number(value),
+            "--side" => options.side = parse_number(value),
+            "--games" => options.games = parse_number(value),
+            "--book-depth" => options.depth = parse_number(value),
+            "--candidates" => options.candidates = parse_number(value),
+            "--margin" => options.margin = parse_number(value),
+            "--depth" => options.limits.depth = Some(parse_number(value)),
+            "--nodes" => options.limits.nodes = Some(parse_number(value)),
+            "--movetime" => {
+                let millis = parse_number(value);
+                options.limits.movetime = Some(Duration::from_millis(millis));
+            }
+            "--concurrency" => options.concurrency = parse_number::<usize>(value).max(1),
+            "--hash" => hash = Some(parse_number(value)),
+            "--max-plies" => options.max_plies = parse_number(value),
+            "--seed" => options.seed = parse_number(value),
+            _ => exit_with(USAGE),
+        }
+    }
+    options.memory_mb = hash.unwrap_or(config.compute.memory / options.concurrency);
+
+    let params = config.eval_params(options.dimension, options.side);
+    let started = Instant::now();
+    let book = build_from_self_play(&options, &params, |game| {
+        let result = match game.result {
+            GameResult::Checkmate(winner) => format!("{winner:?} wins"),
+            _ => "draw".to_string(),
+        };
+        let opening: Vec<String> = game.moves.iter().take(6).map(format_move).collect();
+        println!(
+            "Game {:>5}: {result} by {:?} after {} plies, {} ... ({:.0?})",
+            game.index + 1,
+            game.termination,
+            game.moves.len(),
+            opening.join(" "),
+            started.elapsed()
+        );
+    })
+    .unwrap_or_else(|e| exit_with(&e.to_string()));
+    save(&book, output);
+}
+
+fn import(input: &str, output: &str, args: &[String]) {
+    let config = AppConfig::load();
+    let (mut dimension, mut side, mut depth) = (2, 8, config.book.depth);
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().unwrap_or_else(|| exit_with(USAGE));
+        match flag.as_str() {
+            "--dim" => dimension = parse_number(value),
+            "--side" => side = parse_number(value),
+            "--book-depth" => depth = parse_number(value),
+            _ => exit_with(USAGE),
+        }
+    }
+
+    let text = fs::read_to_string(input).unwrap_or_else(|e| exit_with(&format!("{input}: {e}")));
+    let mut builder = BookBuilder::new(dimension, side, depth);
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let added = parse_game(line, dimension, side)
+            .and_then(|(moves, outcome)| builder.add_game(&moves, outcome));
+        if let Err(e) = added {
+            eprintln!("{input}:{}: skipped: {e}", number + 1);
+        }
+    }
+    println!("Imported {} games", builder.games());
+    save(&builder.build(), output);
+}
+
+fn show(path: &str, hfen: Option<&String>) {
+    let book = OpeningBook::load(path).unwrap_or_else(|e| exit_with(&format!("{path}: {e}")));
+    println!(
+        "{path}: {}D side {}, {} positions, {} moves",
+        book.dimension(),
+        book.side(),
+        book.positions(),
+        book.entries().len()
+    );
+    let (board, player) = match hfen {
+        Some(hfen) => Board::from_hfen(hfen).unwrap_or_else(|e| exit_with(&e.to_string())),
+        None => (Board::new(book.dimension(), book.side()), Player::White),
+    };
+    let moves = book.moves(&board, player);
+    let total: u32 = moves.iter().map(|(_, w)| *w as u32).sum();
+    for (mv, weight) in &moves {
+        let share = 100.0 * *weight as f64 / total as f64;
+        println!("  {:<12} {weight:>6}  {share:5.1}%", format_move(mv));
+    }
+    if moves.is_empty() {
+        println!("  (position not in book)");
+    }
+}
+
+fn save(book: &OpeningBook, output: &str) {
+    book.save(output)
+        .unwrap_or_else(|e| exit_with(&format!("{output}: {e}")));
+    let format = if book.is_polyglot() {
+        "Polyglot"
+    } else {
+        "HCBK"
+    };
+    println!(
+        "Wrote {} positions, {} moves to {output} ({format})",
+        book.positions(),
+        book.entries().len()
+    );
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(USAGE))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
@@ -0,0 +1,87 @@
+use hyperchess::application::datagen::{DatagenOptions, generate};
+use hyperchess::config::AppConfig;
+use hyperchess::domain::models::GameResult;
+use hyperchess::infrastructure::training_data::TrainingDataWriter;
+use std::env;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "Usage: hyperchess-datagen <output.hctd> [--dim D] [--side S] [--games N] \
+    [--depth N] [--nodes N] [--movetime MS] [--concurrency N] [--hash MB] [--random-plies N] \
+    [--max-plies N] [--seed N] [--quiet]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(output) = args.first() else {
+        exit_with(USAGE);
+    };
+    let config = AppConfig::load();
+    let mut options = DatagenOptions {
+        concurrency: config.compute.concurrency.max(1),
+        ..DatagenOptions::default()
+    };
+    let mut hash = None;
+
+    let mut flags = args[1..].iter();
+    while let Some(flag) = flags.next() {
+        if flag == "--quiet" {
+            options.quiet_only = true;
+            continue;
+        }
+        let value = flags.next().unwrap_or_else(|| exit_with(USAGE));
+        match flag.as_str() {
+            "--dim" => options.dimension = parse_number(value),
+            "--side" => options.side = parse_number(value),
+            "--games" => options.games = parse_number(value),
+            "--depth" => options.limits.depth = Some(parse_number(value)),
+            "--nodes" => options.limits.nodes = Some(parse_number(value)),
+            "--movetime" => {
+                let millis = parse_number(value);
+                options.limits.movetime = Some(Duration::from_millis(millis));
+            }
+            "--concurrency" => options.concurrency = parse_number::<usize>(value).max(1),
+            "--hash" => hash = Some(parse_number(value)),
+            "--random-plies" => options.random_plies = parse_number(value),
+            "--max-plies" => options.max_plies = parse_number(value),
+            "--seed" => options.seed = parse_number(value),
+            _ => exit_with(USAGE),
+        }
+    }
+    options.memory_mb = hash.unwrap_or(config.compute.memory / options.concurrency);
+
+    let (dimension, side) = (options.dimension, options.side);
+    let params = config.eval_params(dimension, side);
+    let mut writer = TrainingDataWriter::create(output, dimension, side)
+        .unwrap_or_else(|e| exit_with(&format!("{output}: {e}")));
+
+    let started = Instant::now();
+    let mut positions = 0;
+    let written = generate(&options, &params, &mut writer, |game| {
+        positions += game.records.len();
+        let result = match game.result {
+            GameResult::Checkmate(winner) => format!("{winner:?} wins"),
+            _ => "draw".to_string(),
+        };
+        println!(
+            "Game {:>5}: {result} by {:?} after {} plies, {} positions ({positions} total, {:.0?})",
+            game.index + 1,
+            game.termination,
+            game.plies,
+            game.records.len(),
+            started.elapsed()
+        );
+    })
+    .unwrap_or_else(|e| exit_with(&e.to_string()));
+    writer
+        .finish()
+        .unwrap_or_else(|e| exit_with(&format!("{output}: {e}")));
+    println!("Wrote {written} positions to {output}");
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(USAGE))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
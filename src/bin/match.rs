@@ -0,0 +1,180 @@
+use hyperchess::application::match_runner::{MatchEngine, MatchOptions, play_match};
+use hyperchess::application::sprt::Sprt;
+use hyperchess::config::AppConfig;
+use hyperchess::domain::models::GameResult;
+use hyperchess::infrastructure::ai::{EvalParamSet, Network, Personality, Skill};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: hyperchess-match --a <engine> --b <engine> [--dim D] [--side S] \
+    [--pairs N] [--concurrency N] [--hash MB] [--random-plies N] [--max-plies N] [--seed N] \
+    [--sprt elo0,elo1[,alpha,beta]]
+  <engine>: comma-separated name=..., depth=N, movetime=MS, nodes=N, params=FILE,
+    network=FILE|none, skill=0-20, personality=NAME, contempt=CP";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let config = AppConfig::load();
+    let mut options = MatchOptions {
+        concurrency: config.compute.concurrency.max(1),
+        ..MatchOptions::default()
+    };
+    let mut hash = None;
+    let (mut a, mut b) = (None, None);
+
+    let mut flags = args.iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().unwrap_or_else(|| exit_with(USAGE));
+        match flag.as_str() {
+            "--a" => a = Some(parse_engine(value, "A", &config)),
+            "--b" => b = Some(parse_engine(value, "B", &config)),
+            "--dim" => options.dimension = parse_number(value),
+            "--side" => options.side = parse_number(value),
+            "--pairs" => options.pairs = parse_number(value),
+            "--concurrency" => options.concurrency = parse_number::<usize>(value).max(1),
+            "--hash" => hash = Some(parse_number(value)),
+            "--random-plies" => options.random_plies = parse_number(value),
+            "--max-plies" => options.max_plies = parse_number(value),
+            "--seed" => options.seed = parse_number(value),
+            "--sprt" => options.sprt = Some(parse_sprt(value)),
+            _ => exit_with(USAGE),
+        }
+    }
+    let (Some(a), Some(b)) = (a, b) else {
+        exit_with(USAGE);
+    };
+    for engine in [&a, &b] {
+        if let Some(network) = &engine.network
+            && let Err(e) = network.check_board(options.dimension, options.side)
+        {
+            eprintln!("{}: {e}; using the classical evaluation", engine.name);
+        }
+    }
+    // By default the configured memory is shared by every engine in play.
+    options.memory_mb = hash.unwrap_or(config.compute.memory / (2 * options.concurrency));
+
+    println!(
+        "{} vs {}: {} pairs on {}D side {}, {} at once",
+        a.name, b.name, options.pairs, options.dimension, options.side, options.concurrency
+    );
+    let report = play_match(&a, &b, &options, |game, score| {
+        let (white, black) = if game.a_is_white {
+            (&a.name, &b.name)
+        } else {
+            (&b.name, &a.name)
+        };
+        let result = match game.result {
+            GameResult::Checkmate(winner) => format!("{winner:?} wins"),
+            _ => "draw".to_string(),
+        };
+        println!(
+            "Game {:>4} ({white} vs {black}): {result} by {:?} after {} plies   {score}   Elo {}",
+            score.games(),
+            game.termination,
+            game.plies,
+            format_elo(score.elo(), score.elo_error()),
+        );
+    })
+    .unwrap_or_else(|e| exit_with(&e.to_string()));
+
+    let score = &report.score;
+    println!("----------------------------------------");
+    println!("{} vs {}: {score}", a.name, b.name);
+    println!("Elo: {}", format_elo(score.elo(), score.elo_error()));
+    if let Some(sprt) = &options.sprt {
+        let (lower, upper) = sprt.bounds();
+        let verdict = report
+            .verdict
+            .map_or("inconclusive".to_string(), |v| v.to_string());
+        println!(
+            "SPRT [{}, {}]: LLR {:.2} ({lower:.2}, {upper:.2}), {verdict}",
+            sprt.elo0,
+            sprt.elo1,
+            sprt.llr(score)
+        );
+    }
+}
+
+/// `name=new,depth=6,movetime=100,nodes=50000,params=tuned.toml,network=n.nnue`:
+/// every key is optional, but at least one limit must be given.
+fn parse_engine(spec: &str, default_name: &str, config: &AppConfig) -> MatchEngine {
+    let mut engine = MatchEngine {
+        name: default_name.to_string(),
+        params: config.eval.set.clone(),
+        network: config.eval.loaded_network.clone(),
+        ..MatchEngine::default()
+    };
+    for option in spec.split(',') {
+        let Some((key, value)) = option.split_once('=') else {
+            exit_with(&format!("expected key=value in '{option}'"));
+        };
+        match key {
+            "name" => engine.name = value.to_string(),
+            "depth" => engine.limits.depth = Some(parse_number(value)),
+            "movetime" => {
+                let millis = parse_number(value);
+                engine.limits.movetime = Some(Duration::from_millis(millis));
+            }
+            "nodes" => engine.limits.nodes = Some(parse_number(value)),
+            "params" => {
+                let file = EvalParamSet::load(value);
+                engine
+                    .params
+                    .merge(file.unwrap_or_else(|e| exit_with(&e.to_string())));
+            }
+            "skill" => {
+                let skill = Skill::new(parse_number(value));
+                engine.skill = skill.unwrap_or_else(|e| exit_with(&e.to_string()));
+            }
+            "personality" => {
+                let personality = value.parse::<Personality>();
+                engine.personality = personality.unwrap_or_else(|e| exit_with(&e.to_string()));
+            }
+            "contempt" => engine.contempt = Some(parse_number(value)),
+            "network" if value == "none" => engine.network = None,
+            "network" => {
+                let network = Network::load(value);
+                let network = network.unwrap_or_else(|e| exit_with(&format!("{value}: {e}")));
+                engine.network = Some(Arc::new(network));
+            }
+            _ => exit_with(&format!("unknown engine option '{key}'")),
+        }
+    }
+    let limits = &engine.limits;
+    if limits.depth.is_none() && limits.movetime.is_none() && limits.nodes.is_none() {
+        exit_with(&format!("{}: set depth, movetime or nodes", engine.name));
+    }
+    engine
+}
+
+fn parse_sprt(value: &str) -> Sprt {
+    let numbers: Vec<f64> = value.split(',').map(parse_number).collect();
+    match numbers[..] {
+        [elo0, elo1] => Sprt {
+            elo0,
+            elo1,
+            ..Sprt::default()
+        },
+        [elo0, elo1, alpha, beta] => Sprt {
+            elo0,
+            elo1,
+            alpha,
+            beta,
+        },
+        _ => exit_with(USAGE),
+    }
+}
+
+fn format_elo(elo: f64, error: f64) -> String {
+    format!("{elo:+.1} ± {error:.1}")
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(USAGE))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
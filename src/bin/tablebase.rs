@@ -0,0 +1,107 @@
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation::format_move;
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::tablebase::{Material, Tablebases, TbValue};
+use std::env;
+use std::path::Path;
+use std::time::Instant;
+
+const USAGE: &str = "Usage:
+  hyperchess-tablebase generate <dir> <material>... [--dim D] [--side S]
+  hyperchess-tablebase probe <dir> <hfen>
+
+Materials are written White first, e.g. KQvK or KRvKN.";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("generate") if args.len() >= 3 => generate(&args[1], &args[2..]),
+        Some("probe") if args.len() == 3 => probe(&args[1], &args[2]),
+        _ => exit_with(USAGE),
+    }
+}
+
+fn generate(dir: &str, args: &[String]) {
+    let (mut dimension, mut side) = (3, 4);
+    let mut materials = Vec::new();
+    let mut flags = args.iter();
+    while let Some(arg) = flags.next() {
+        match arg.as_str() {
+            "--dim" => dimension = parse_number(flags.next().unwrap_or_else(|| exit_with(USAGE))),
+            "--side" => side = parse_number(flags.next().unwrap_or_else(|| exit_with(USAGE))),
+            _ => materials.push(Material::parse(arg).unwrap_or_else(|e| exit_with(&e.to_string()))),
+        }
+    }
+    if materials.is_empty() {
+        exit_with(USAGE);
+    }
+    std::fs::create_dir_all(dir).unwrap_or_else(|e| exit_with(&format!("{dir}: {e}")));
+
+    // Tables already in `dir` are reused rather than generated again.
+    let mut tables = load(dir)
+        .into_iter()
+        .find(|t| (t.dimension(), t.side()) == (dimension, side))
+        .unwrap_or_else(|| Tablebases::new(dimension, side));
+    let started = Instant::now();
+    for material in &materials {
+        tables
+            .generate(material, &mut |table| {
+                let path = Path::new(dir).join(table.file_name());
+                table
+                    .save(&path)
+                    .unwrap_or_else(|e| exit_with(&format!("{}: {e}", path.display())));
+                let summary = table.summary();
+                println!(
+                    "{}: {} wins, {} draws, {} losses, longest mate {} plies ({:.1?})",
+                    path.display(),
+                    summary.wins,
+                    summary.draws,
+                    summary.losses,
+                    summary.longest,
+                    started.elapsed()
+                );
+            })
+            .unwrap_or_else(|e| exit_with(&e.to_string()));
+    }
+}
+
+fn probe(dir: &str, hfen: &str) {
+    let (board, player) = Board::from_hfen(hfen).unwrap_or_else(|e| exit_with(&e.to_string()));
+    let tables = load(dir)
+        .into_iter()
+        .find(|t| (t.dimension(), t.side()) == (board.dimension(), board.side()))
+        .unwrap_or_else(|| exit_with("no tables for this board"));
+    let Some(value) = tables.probe(&board, player) else {
+        exit_with("position not in the tables");
+    };
+    println!("{player:?} to move: {}", describe(value));
+    let moves = Rules::generate_legal_moves(&mut board.clone(), player);
+    for mv in &moves {
+        let mut after = board.clone();
+        after.apply_move(mv).unwrap_or_else(|e| exit_with(&e));
+        if let Some(reply) = tables.probe(&after, player.opponent()) {
+            println!("  {:<12} opponent {}", format_move(mv), describe(reply));
+        }
+    }
+}
+
+fn describe(value: TbValue) -> String {
+    match value {
+        TbValue::Win(plies) => format!("wins, mate in {} plies", plies),
+        TbValue::Draw => "draws".to_string(),
+        TbValue::Loss(plies) => format!("loses, mated in {} plies", plies),
+    }
+}
+
+fn load(dir: &str) -> Vec<Tablebases> {
+    Tablebases::load_dir(dir).unwrap_or_else(|e| exit_with(&format!("{dir}: {e}")))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(USAGE))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
@@ -0,0 +1,66 @@
+use hyperchess::infrastructure::ai::nnue_trainer::{NnueTrainer, TrainerOptions, TrainingSet};
+use std::env;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: hyperchess-train-nnue <data.hctd> <output.nnue> [--hidden N] \
+    [--epochs N] [--batch N] [--lr R] [--lambda L] [--seed N]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (Some(input), Some(output)) = (args.first(), args.get(1)) else {
+        exit_with(USAGE);
+    };
+    let mut options = TrainerOptions::default();
+    let mut flags = args[2..].iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().unwrap_or_else(|| exit_with(USAGE));
+        match flag.as_str() {
+            "--hidden" => options.hidden = parse_number(value),
+            "--epochs" => options.epochs = parse_number(value),
+            "--batch" => options.batch_size = parse_number(value),
+            "--lr" => options.learning_rate = parse_number(value),
+            "--lambda" => options.lambda = parse_number(value),
+            "--seed" => options.seed = parse_number(value),
+            _ => exit_with(USAGE),
+        }
+    }
+
+    let mut set = TrainingSet::load(input, options.lambda)
+        .unwrap_or_else(|e| exit_with(&format!("{input}: {e}")));
+    if set.samples.is_empty() {
+        exit_with(&format!("{input}: no positions"));
+    }
+    let (dimension, side) = (set.dimension, set.side);
+    let mut trainer = NnueTrainer::new(dimension, side, options.clone());
+    println!(
+        "{} positions, {dimension}D side {side}, {} hidden: loss {:.6}",
+        set.samples.len(),
+        options.hidden,
+        trainer.loss(&set.samples)
+    );
+
+    let started = Instant::now();
+    trainer.train(&mut set.samples, |epoch| {
+        println!(
+            "epoch {:>3}  loss {:.6}  {:.1?}",
+            epoch.epoch,
+            epoch.loss,
+            started.elapsed()
+        );
+    });
+
+    trainer
+        .network()
+        .save(output)
+        .unwrap_or_else(|e| exit_with(&format!("{output}: {e}")));
+    println!("Wrote {output}");
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(USAGE))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
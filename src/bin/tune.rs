@@ -0,0 +1,91 @@
+use hyperchess::config::AppConfig;
+use hyperchess::infrastructure::ai::EvalParams;
+use hyperchess::infrastructure::ai::eval_params::EvalOverride;
+use hyperchess::infrastructure::ai::tuner::{Tuner, read_labelled_positions};
+use std::env;
+use std::time::Instant;
+
+const USAGE: &str = "Usage: hyperchess-tune <positions> <output.toml|output.json> \
+    [--epochs N] [--step S] [--params name,name,...]";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (Some(input), Some(output)) = (args.first(), args.get(1)) else {
+        exit_with(USAGE);
+    };
+    let mut epochs = 100;
+    let mut step = 8;
+    let mut names: Vec<&str> = EvalParams::NAMES.to_vec();
+    let mut flags = args[2..].iter();
+    while let Some(flag) = flags.next() {
+        let value = flags.next().unwrap_or_else(|| exit_with(USAGE));
+        match flag.as_str() {
+            "--epochs" => epochs = parse_number(value),
+            "--step" => step = parse_number(value),
+            "--params" => {
+                names = value.split(',').collect();
+                let unknown = names.iter().find(|n| !EvalParams::NAMES.contains(n));
+                if let Some(unknown) = unknown {
+                    exit_with(&format!("unknown evaluation parameter '{unknown}'"));
+                }
+            }
+            _ => exit_with(USAGE),
+        }
+    }
+
+    let config = AppConfig::load();
+    let positions = read_labelled_positions(input).unwrap_or_else(|e| exit_with(&e.to_string()));
+    let (dimension, side) = (positions[0].board.dimension(), positions[0].board.side());
+    let start = config.eval_params(dimension, side);
+
+    let mut tuner = Tuner::new(&positions);
+    let k = tuner.fit_scale(&start);
+    println!(
+        "{} positions, {dimension}D side {side}: K = {k:.4}, error {:.6}",
+        positions.len(),
+        tuner.error(&start)
+    );
+
+    let started = Instant::now();
+    let tuned = tuner.tune(&start, &names, step, epochs, |report| {
+        println!(
+            "epoch {:>3}  step {:>3}  error {:.6}  improved {:>3}  {:.1?}",
+            report.epoch,
+            report.step,
+            report.error,
+            report.improved,
+            started.elapsed()
+        );
+    });
+
+    let changed: Vec<(String, i32)> = EvalParams::NAMES
+        .iter()
+        .filter_map(|&name| {
+            let value = tuned.get(name)?;
+            (start.get(name) != Some(value)).then(|| (name.to_string(), value))
+        })
+        .collect();
+    for (name, value) in &changed {
+        println!("{name}: {} -> {value}", start.get(name).unwrap());
+    }
+
+    // The configured weights with the tuned ones for this board on top.
+    let mut set = config.eval.set.clone();
+    set.overrides.push(EvalOverride {
+        dimension,
+        side: Some(side),
+        params: changed.into_iter().collect(),
+    });
+    set.save(output)
+        .unwrap_or_else(|e| exit_with(&e.to_string()));
+    println!("Wrote {output}");
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> T {
+    value.parse().unwrap_or_else(|_| exit_with(USAGE))
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{message}");
+    std::process::exit(2);
+}
@@ -0,0 +1,11 @@
+use hyperchess::config::AppConfig;
+use hyperchess::interface::uci::UciInterface;
+use std::io::{self, BufReader};
+
+fn main() {
+    let config = AppConfig::load();
+    let mut stdout = io::stdout();
+    if let Err(e) = UciInterface::new(config).run(BufReader::new(io::stdin()), &mut stdout) {
+        eprintln!("UCI error: {}", e);
+    }
+}
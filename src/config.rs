@@ -1,6 +1,13 @@
+use crate::infrastructure::ai::eval_params::{EvalParamSet, EvalParams};
+use crate::infrastructure::ai::nnue::Network;
+use crate::infrastructure::book::{BookProbe, OpeningBook};
+use crate::infrastructure::syzygy::SyzygyTables;
+use crate::infrastructure::tablebase::Tablebases;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct AppConfig {
@@ -9,6 +16,124 @@ pub struct AppConfig {
     pub api: ApiConfig,
     #[serde(default)]
     pub distributed: DistributedConfig,
+    #[serde(default)]
+    pub engine: EngineConfig,
+    #[serde(default)]
+    pub mcts: MctsConfig,
+    #[serde(default)]
+    pub eval: EvalConfig,
+    #[serde(default)]
+    pub book: BookConfig,
+    #[serde(default)]
+    pub tablebase: TablebaseConfig,
+}
+
+/// Endgame tablebases probed by minimax bots, and generated by
+/// `hyperchess-tablebase`.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TablebaseConfig {
+    /// Directory of HCTB tables; see `Tablebase`.
+    pub path: Option<String>,
+    /// The tables in `path`, once loaded, one set per board.
+    #[serde(skip)]
+    pub loaded: Vec<Arc<Tablebases>>,
+    /// Directory of Syzygy tables for 2D 8×8 play; see `SyzygyTables`.
+    pub syzygy: Option<String>,
+    /// `syzygy`, once opened.
+    #[serde(skip)]
+    pub loaded_syzygy: Option<Arc<SyzygyTables>>,
+}
+
+/// Opening book played by minimax bots, and built by `hyperchess-book`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct BookConfig {
+    /// Polyglot or HCBK book; see `OpeningBook`.
+    pub file: Option<String>,
+    /// Plies from the start of a game that are played from the book, and
+    /// that `hyperchess-book` records.
+    pub depth: usize,
+    /// 0 always plays the heaviest book move, 1 plays each in proportion to
+    /// its weight, and higher values flatten the odds.
+    pub variety: f64,
+    /// `file`, once loaded.
+    #[serde(skip)]
+    pub loaded: Option<Arc<OpeningBook>>,
+}
+
+impl Default for BookConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            depth: 16,
+            variety: 1.0,
+            loaded: None,
+        }
+    }
+}
+
+/// Evaluation weights: inline `[eval.params]` and `[[eval.overrides]]`, on
+/// top of those read from `file`.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct EvalConfig {
+    /// TOML or JSON parameter file; see `EvalParamSet`.
+    pub file: Option<String>,
+    /// HCNN network used by minimax bots on the board it was trained for;
+    /// other boards keep the classical evaluation.
+    pub network: Option<String>,
+    #[serde(flatten)]
+    pub set: EvalParamSet,
+    /// `network`, once loaded.
+    #[serde(skip)]
+    pub loaded_network: Option<Arc<Network>>,
+}
+
+/// Monte Carlo Tree Search used by the `m` player type.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct MctsConfig {
+    /// PUCT exploration constant: higher values spread visits over more moves.
+    pub exploration: f32,
+    /// Value leaves with a quiescence search rather than the static evaluation.
+    pub quiescence: bool,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            exploration: 1.5,
+            quiescence: true,
+        }
+    }
+}
+
+/// External engine used by the `e` player type.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EngineConfig {
+    pub command: String,
+    pub args: Vec<String>,
+    /// Time per move passed to the engine as `go movetime`.
+    pub move_time_ms: u64,
+    /// Grace period past the move time before `stop`, and again before the
+    /// process is killed.
+    pub timeout_ms: u64,
+    /// UCI options sent with `setoption` after the handshake.
+    pub options: BTreeMap<String, String>,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            command: String::new(),
+            args: Vec::new(),
+            move_time_ms: 1000,
+            timeout_ms: 2000,
+            options: BTreeMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -42,6 +167,8 @@ pub struct MinimaxConfig {
 pub struct ComputeConfig {
     pub minutes: u64,
     pub concurrency: usize,
+    /// Total search memory in MB: transposition table, per-thread history
+    /// tables and board geometry together.
     pub memory: usize,
 }
 
@@ -62,6 +189,9 @@ impl AppConfig {
         };
 
         config.merge_env();
+        config.load_eval_params();
+        config.load_book();
+        config.load_tablebases();
 
         eprintln!("----------------------------------------");
         eprintln!("HyperChess Configuration:");
@@ -77,11 +207,134 @@ impl AppConfig {
             eprintln!("  Worker DNS: {}", config.distributed.worker_dns);
             eprintln!("  gRPC Port: {}", config.distributed.grpc_port);
         }
+        if !config.engine.command.is_empty() {
+            eprintln!(
+                "  Engine: {} ({} ms/move)",
+                config.engine.command, config.engine.move_time_ms
+            );
+        }
+        if let Some(file) = &config.eval.file {
+            eprintln!("  Eval params: {}", file);
+        }
+        if let Some(network) = &config.eval.loaded_network {
+            eprintln!(
+                "  Eval network: {} ({}D side {}, {} hidden)",
+                config.eval.network.as_deref().unwrap_or_default(),
+                network.dimension(),
+                network.side(),
+                network.hidden()
+            );
+        }
+        if let Some(book) = &config.book.loaded {
+            eprintln!(
+                "  Book: {} ({}D side {}, {} positions, {} plies)",
+                config.book.file.as_deref().unwrap_or_default(),
+                book.dimension(),
+                book.side(),
+                book.positions(),
+                config.book.depth
+            );
+        }
+        for tables in &config.tablebase.loaded {
+            eprintln!(
+                "  Tablebases: {} ({}D side {}, {} tables, up to {} pieces)",
+                config.tablebase.path.as_deref().unwrap_or_default(),
+                tables.dimension(),
+                tables.side(),
+                tables.len(),
+                tables.max_pieces()
+            );
+        }
+        if let Some(syzygy) = &config.tablebase.loaded_syzygy {
+            eprintln!(
+                "  Syzygy: {} ({} files, up to {} pieces)",
+                config.tablebase.syzygy.as_deref().unwrap_or_default(),
+                syzygy.files(),
+                syzygy.max_pieces()
+            );
+        }
         eprintln!("----------------------------------------");
 
         config
     }
 
+    /// Evaluation weights for a `dimension`-D board of `side`.
+    pub fn eval_params(&self, dimension: usize, side: usize) -> EvalParams {
+        self.eval.set.resolve(dimension, side)
+    }
+
+    /// How to play from the configured book, if it was built for a
+    /// `dimension`-D board of `side`.
+    pub fn book(&self, dimension: usize, side: usize) -> Option<BookProbe> {
+        let book = self.book.loaded.clone()?;
+        ((book.dimension(), book.side()) == (dimension, side)).then(|| BookProbe {
+            book,
+            depth: self.book.depth,
+            variety: self.book.variety,
+        })
+    }
+
+    /// The configured tablebases for a `dimension`-D board of `side`, if
+    /// there are any.
+    pub fn tablebases(&self, dimension: usize, side: usize) -> Option<Arc<Tablebases>> {
+        self.tablebase
+            .loaded
+            .iter()
+            .find(|t| (t.dimension(), t.side()) == (dimension, side))
+            .cloned()
+    }
+
+    /// The configured Syzygy tables, which only cover 2D 8×8 boards.
+    pub fn syzygy(&self, dimension: usize, side: usize) -> Option<Arc<SyzygyTables>> {
+        self.tablebase
+            .loaded_syzygy
+            .clone()
+            .filter(|_| (dimension, side) == (2, 8))
+    }
+
+    /// The configured network, if it was trained for a `dimension`-D board
+    /// of `side`.
+    pub fn network(&self, dimension: usize, side: usize) -> Option<Arc<Network>> {
+        self.eval
+            .loaded_network
+            .clone()
+            .filter(|n| n.check_board(dimension, side).is_ok())
+    }
+
+    fn load_eval_params(&mut self) {
+        self.eval
+            .set
+            .validate()
+            .unwrap_or_else(|e| panic!("Invalid [eval] in Config.toml: {e}"));
+        if let Some(file) = &self.eval.file {
+            let mut set = EvalParamSet::load(file).unwrap_or_else(|e| panic!("{e}"));
+            set.merge(std::mem::take(&mut self.eval.set));
+            self.eval.set = set;
+        }
+        if let Some(path) = &self.eval.network {
+            let network = Network::load(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            self.eval.loaded_network = Some(Arc::new(network));
+        }
+    }
+
+    fn load_book(&mut self) {
+        if let Some(path) = &self.book.file {
+            let book = OpeningBook::load(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            self.book.loaded = Some(Arc::new(book));
+        }
+    }
+
+    fn load_tablebases(&mut self) {
+        if let Some(path) = &self.tablebase.path {
+            let sets = Tablebases::load_dir(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            self.tablebase.loaded = sets.into_iter().map(Arc::new).collect();
+        }
+        if let Some(path) = &self.tablebase.syzygy {
+            let tables = SyzygyTables::open(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+            self.tablebase.loaded_syzygy = Some(Arc::new(tables));
+        }
+    }
+
     fn merge_env(&mut self) {
         if let Ok(val) = std::env::var("HYPERCHESS_MINIMAX_DEPTH")
             && let Ok(parsed) = val.parse()
@@ -125,6 +378,29 @@ impl AppConfig {
         {
             self.distributed.grpc_port = parsed;
         }
+        if let Ok(val) = std::env::var("HYPERCHESS_EVAL_PARAMS") {
+            self.eval.file = Some(val);
+        }
+        if let Ok(val) = std::env::var("HYPERCHESS_EVAL_NETWORK") {
+            self.eval.network = Some(val);
+        }
+        if let Ok(val) = std::env::var("HYPERCHESS_BOOK") {
+            self.book.file = Some(val);
+        }
+        if let Ok(val) = std::env::var("HYPERCHESS_TABLEBASE") {
+            self.tablebase.path = Some(val);
+        }
+        if let Ok(val) = std::env::var("HYPERCHESS_SYZYGY") {
+            self.tablebase.syzygy = Some(val);
+        }
+        if let Ok(val) = std::env::var("HYPERCHESS_ENGINE_COMMAND") {
+            self.engine.command = val;
+        }
+        if let Ok(val) = std::env::var("HYPERCHESS_ENGINE_MOVE_TIME_MS")
+            && let Ok(parsed) = val.parse()
+        {
+            self.engine.move_time_ms = parsed;
+        }
     }
 }
 
@@ -221,6 +497,35 @@ mod tests {
         assert_eq!(config.minimax.depth, 4);
     }
 
+    #[test]
+    fn test_inline_eval_params() {
+        let config: AppConfig = toml::from_str(
+            r#"
+            [minimax]
+            depth = 4
+            [compute]
+            minutes = 1
+            concurrency = 1
+            memory = 16
+            [api]
+            port = 3123
+
+            [eval.params]
+            tempo_bonus = 5
+            [[eval.overrides]]
+            dimension = 3
+            side = 4
+            [eval.overrides.params]
+            tempo_bonus = 7
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.eval.file, None);
+        assert_eq!(config.eval_params(2, 8).tempo_bonus, 5);
+        assert_eq!(config.eval_params(3, 4).tempo_bonus, 7);
+    }
+
     #[test]
     fn test_load_prints_config() {
         let _config = AppConfig::load();
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use crate::domain::board::bitboard::BitBoard;
 use crate::domain::board::board_representation::BoardRepresentation;
 use crate::domain::board::geometry::BoardGeometry;
+use crate::domain::board::listener::PieceListener;
 use crate::domain::board::pieces::PieceMap;
 use crate::domain::board::position::PositionState;
 use crate::domain::coordinate::Coordinate;
@@ -24,6 +25,9 @@ pub struct GenericBoard<R: BoardRepresentation = BitBoard> {
     pub zobrist: Arc<ZobristKeys>,
     pub pieces: PieceMap<R>,
     pub state: PositionState,
+    /// Told of every piece moved, for incrementally updated evaluations.
+    /// Not serialized.
+    pub listener: Option<Box<dyn PieceListener>>,
 }
 
 /// Wire format for serializing GenericBoard across the network.
@@ -63,6 +67,7 @@ impl<'de, R: BoardRepresentation + serde::de::DeserializeOwned> serde::Deseriali
             zobrist,
             pieces: wire.pieces,
             state: wire.state,
+            listener: None,
         })
     }
 }
@@ -82,6 +87,7 @@ impl<R: BoardRepresentation> GenericBoard<R> {
             zobrist,
             pieces,
             state,
+            listener: None,
         }
     }
 
@@ -142,21 +148,19 @@ impl<R: BoardRepresentation> GenericBoard<R> {
     // ── Hash helpers ────────────────────────────────────────────────
 
     fn hash_xor_piece(&mut self, index: usize, piece: Piece) {
-        let offset = match (piece.owner, piece.piece_type) {
-            (Player::White, PieceType::Pawn) => 0,
-            (Player::White, PieceType::Knight) => 1,
-            (Player::White, PieceType::Bishop) => 2,
-            (Player::White, PieceType::Rook) => 3,
-            (Player::White, PieceType::Queen) => 4,
-            (Player::White, PieceType::King) => 5,
-            (Player::Black, PieceType::Pawn) => 6,
-            (Player::Black, PieceType::Knight) => 7,
-            (Player::Black, PieceType::Bishop) => 8,
-            (Player::Black, PieceType::Rook) => 9,
-            (Player::Black, PieceType::Queen) => 10,
-            (Player::Black, PieceType::King) => 11,
-        };
-        self.state.hash ^= self.zobrist.piece_keys[offset * self.geo.total_cells + index];
+        self.state.hash ^= self.zobrist.piece_key(index, piece);
+    }
+
+    fn report_added(&mut self, index: usize, piece: Piece) {
+        if let Some(listener) = &mut self.listener {
+            listener.piece_added(index, piece);
+        }
+    }
+
+    fn report_removed(&mut self, index: usize, piece: Piece) {
+        if let Some(listener) = &mut self.listener {
+            listener.piece_removed(index, piece);
+        }
     }
 
     // ── Piece queries ───────────────────────────────────────────────
@@ -331,6 +335,9 @@ impl<R: BoardRepresentation> GenericBoard<R> {
         let saved_ep = self.state.en_passant_target;
         let saved_castling = self.state.castling_rights;
         let mut captured = None;
+        if let Some(listener) = &mut self.listener {
+            listener.begin_move();
+        }
 
         self.state.history.push(self.state.hash);
 
@@ -347,10 +354,12 @@ impl<R: BoardRepresentation> GenericBoard<R> {
         }
 
         self.hash_xor_piece(from_idx, moving_piece);
+        self.report_removed(from_idx, moving_piece);
 
         if let Some(target_p) = self.pieces.get_piece_at_index(to_idx) {
             captured = Some((to_idx, target_p));
             self.hash_xor_piece(to_idx, target_p);
+            self.report_removed(to_idx, target_p);
         }
 
         if moving_piece.piece_type == PieceType::Pawn
@@ -360,6 +369,7 @@ impl<R: BoardRepresentation> GenericBoard<R> {
             if let Some(victim_p) = self.pieces.get_piece_at_index(victim) {
                 captured = Some((victim, victim_p));
                 self.hash_xor_piece(victim, victim_p);
+                self.report_removed(victim, victim_p);
             }
             self.pieces.remove_piece_at_index(victim);
         }
@@ -487,11 +497,14 @@ impl<R: BoardRepresentation> GenericBoard<R> {
         self.pieces.place_piece_at_index(to_idx, piece_to_place);
 
         self.hash_xor_piece(to_idx, piece_to_place);
+        self.report_added(to_idx, piece_to_place);
 
         if let Some((r_from, r_to, r_piece)) = castling_rook_move {
             self.hash_xor_piece(r_from, r_piece);
+            self.report_removed(r_from, r_piece);
             self.pieces.remove_piece_at_index(r_from);
             self.hash_xor_piece(r_to, r_piece);
+            self.report_added(r_to, r_piece);
             self.pieces.place_piece_at_index(r_to, r_piece);
         }
 
@@ -510,6 +523,9 @@ impl<R: BoardRepresentation> GenericBoard<R> {
         if let Some(h) = self.state.history.pop() {
             self.state.hash = h;
         }
+        if let Some(listener) = &mut self.listener {
+            listener.undo_move();
+        }
 
         self.state.en_passant_target = info.en_passant_target;
         self.state.castling_rights = info.castling_rights;
@@ -608,8 +624,12 @@ impl<R: BoardRepresentation> GenericBoard<R> {
 
     pub fn set_piece(&mut self, coord: &Coordinate, piece: Piece) -> Result<(), String> {
         let index = self.coords_to_index(&coord.values).ok_or("Invalid coord")?;
+        if let Some(old) = self.pieces.get_piece_at_index(index) {
+            self.report_removed(index, old);
+        }
         self.pieces.remove_piece_at_index(index);
         self.pieces.place_piece_at_index(index, piece);
+        self.report_added(index, piece);
         self.state.hash = self
             .zobrist
             .get_hash(&self.pieces, &self.state, self.geo.total_cells);
@@ -618,6 +638,9 @@ impl<R: BoardRepresentation> GenericBoard<R> {
 
     pub fn clear_cell(&mut self, coord: &Coordinate) {
         if let Some(index) = self.coords_to_index(&coord.values) {
+            if let Some(old) = self.pieces.get_piece_at_index(index) {
+                self.report_removed(index, old);
+            }
             self.pieces.remove_piece_at_index(index);
         }
     }
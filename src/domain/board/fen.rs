@@ -38,7 +38,7 @@ impl fmt::Display for FenError {
 
 impl std::error::Error for FenError {}
 
-fn char_to_piece(c: char) -> Result<Piece, FenError> {
+pub(crate) fn char_to_piece(c: char) -> Result<Piece, FenError> {
     let owner = if c.is_ascii_uppercase() {
         Player::White
     } else {
@@ -56,7 +56,22 @@ fn char_to_piece(c: char) -> Result<Piece, FenError> {
     Ok(Piece { piece_type, owner })
 }
 
-fn parse_castling(s: &str) -> Result<u8, FenError> {
+pub(crate) fn piece_to_char(piece: Piece) -> char {
+    let c = match piece.piece_type {
+        PieceType::Pawn => 'p',
+        PieceType::Knight => 'n',
+        PieceType::Bishop => 'b',
+        PieceType::Rook => 'r',
+        PieceType::Queen => 'q',
+        PieceType::King => 'k',
+    };
+    match piece.owner {
+        Player::White => c.to_ascii_uppercase(),
+        Player::Black => c,
+    }
+}
+
+pub(crate) fn parse_castling(s: &str) -> Result<u8, FenError> {
     if s == "-" {
         return Ok(0);
     }
@@ -182,6 +197,68 @@ impl Board {
 
         Ok(board)
     }
+
+    /// Write the position as a FEN string with `side_to_move` to play.
+    ///
+    /// Only valid for standard chess positions (dimension=2, side=8).
+    pub fn to_fen(&self, side_to_move: Player) -> String {
+        let mut ranks = Vec::with_capacity(8);
+        for rank in (0..8).rev() {
+            let mut row = String::new();
+            let mut empty = 0;
+            for file in 0..8 {
+                match self.pieces.get_piece_at_index(rank + file * 8) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            row.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        row.push(piece_to_char(piece));
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                row.push_str(&empty.to_string());
+            }
+            ranks.push(row);
+        }
+
+        let side = match side_to_move {
+            Player::White => "w",
+            Player::Black => "b",
+        };
+        let en_passant = match self.state.en_passant_target {
+            Some((target, _)) => {
+                let (rank, file) = (target % 8, target / 8);
+                format!("{}{}", (b'a' + file as u8) as char, rank + 1)
+            }
+            None => "-".to_string(),
+        };
+
+        format!(
+            "{} {} {} {} {} {}",
+            ranks.join("/"),
+            side,
+            format_castling(self.state.castling_rights),
+            en_passant,
+            self.state.halfmove_clock,
+            self.state.fullmove_number
+        )
+    }
+}
+
+pub(crate) fn format_castling(rights: u8) -> String {
+    let mut s = String::new();
+    for (bit, c) in [(0x1, 'K'), (0x2, 'Q'), (0x4, 'k'), (0x8, 'q')] {
+        if rights & bit != 0 {
+            s.push(c);
+        }
+    }
+    if s.is_empty() {
+        s.push('-');
+    }
+    s
 }
 
 #[cfg(test)]
@@ -259,6 +336,22 @@ mod tests {
         assert_eq!(piece.owner, Player::Black);
     }
 
+    #[test]
+    fn test_to_fen_round_trip() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w Kq - 3 12",
+        ] {
+            let side = if fen.contains(" w ") {
+                Player::White
+            } else {
+                Player::Black
+            };
+            assert_eq!(Board::from_fen(fen).unwrap().to_fen(side), fen);
+        }
+    }
+
     #[test]
     fn test_invalid_fen_field_count() {
         assert!(Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w").is_err());
@@ -0,0 +1,311 @@
+use std::fmt;
+
+use crate::domain::board::Board;
+use crate::domain::board::fen::{char_to_piece, format_castling, parse_castling, piece_to_char};
+use crate::domain::board::notation::{format_square, parse_square};
+use crate::domain::board::pieces::PieceMap;
+use crate::domain::models::Player;
+
+/// HFEN: FEN generalised to N-dimensional boards.
+///
+/// ```text
+/// <dim>x<side> <placement> <side-to-move> <castling> <en-passant> <halfmove> <fullmove>
+/// ```
+///
+/// The placement is a sequence of 2D (rank × file) slices, one per combination
+/// of the higher axes in index order, separated by `|`. Each slice is written
+/// exactly like a FEN placement: ranks from the top down separated by `/`,
+/// files left to right, empty runs as decimal counts (which may exceed 9 on
+/// wide boards). For `2x8` the placement is identical to FEN.
+///
+/// En passant is `-` or `<target>:<victim>` in coordinate notation, since in
+/// N-D the victim square cannot be derived from the target alone.
+#[derive(Debug)]
+pub enum HfenError {
+    InvalidFieldCount,
+    InvalidGeometry(String),
+    InvalidSliceCount {
+        expected: usize,
+        got: usize,
+    },
+    InvalidRankCount {
+        slice: usize,
+        expected: usize,
+        got: usize,
+    },
+    InvalidPiece(char),
+    RankOverflow {
+        slice: usize,
+        rank: usize,
+    },
+    InvalidSideToMove(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+}
+
+impl fmt::Display for HfenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFieldCount => write!(f, "HFEN must have exactly 7 space-separated fields"),
+            Self::InvalidGeometry(s) => write!(f, "Invalid geometry field: '{s}'"),
+            Self::InvalidSliceCount { expected, got } => {
+                write!(f, "Expected {expected} slices, got {got}")
+            }
+            Self::InvalidRankCount {
+                slice,
+                expected,
+                got,
+            } => write!(f, "Slice {slice}: expected {expected} ranks, got {got}"),
+            Self::InvalidPiece(c) => write!(f, "Invalid piece character: '{c}'"),
+            Self::RankOverflow { slice, rank } => {
+                write!(f, "Slice {slice}: rank {rank} overflows")
+            }
+            Self::InvalidSideToMove(s) => write!(f, "Invalid side to move: '{s}'"),
+            Self::InvalidCastling(s) => write!(f, "Invalid castling field: '{s}'"),
+            Self::InvalidEnPassant(s) => write!(f, "Invalid en passant field: '{s}'"),
+            Self::InvalidHalfmoveClock(s) => write!(f, "Invalid halfmove clock: '{s}'"),
+            Self::InvalidFullmoveNumber(s) => write!(f, "Invalid fullmove number: '{s}'"),
+        }
+    }
+}
+
+impl std::error::Error for HfenError {}
+
+impl Board {
+    /// Write the position as an HFEN string with `side_to_move` to play.
+    pub fn to_hfen(&self, side_to_move: Player) -> String {
+        let side = self.geo.side;
+        let slice_cells = side * side;
+        let num_slices = self.geo.total_cells / slice_cells;
+
+        let mut slices = Vec::with_capacity(num_slices);
+        for slice in 0..num_slices {
+            let base = slice * slice_cells;
+            let mut ranks = Vec::with_capacity(side);
+            for rank in (0..side).rev() {
+                let mut row = String::new();
+                let mut empty = 0;
+                for file in 0..side {
+                    match self.pieces.get_piece_at_index(base + rank + file * side) {
+                        Some(piece) => {
+                            if empty > 0 {
+                                row.push_str(&empty.to_string());
+                                empty = 0;
+                            }
+                            row.push(piece_to_char(piece));
+                        }
+                        None => empty += 1,
+                    }
+                }
+                if empty > 0 {
+                    row.push_str(&empty.to_string());
+                }
+                ranks.push(row);
+            }
+            slices.push(ranks.join("/"));
+        }
+
+        let stm = match side_to_move {
+            Player::White => "w",
+            Player::Black => "b",
+        };
+        let en_passant = match self.state.en_passant_target {
+            Some((target, victim)) => format!(
+                "{}:{}",
+                format_square(&self.index_to_coords(target)),
+                format_square(&self.index_to_coords(victim))
+            ),
+            None => "-".to_string(),
+        };
+
+        format!(
+            "{}x{} {} {} {} {} {} {}",
+            self.geo.dimension,
+            side,
+            slices.join("|"),
+            stm,
+            format_castling(self.state.castling_rights),
+            en_passant,
+            self.state.halfmove_clock,
+            self.state.fullmove_number
+        )
+    }
+
+    /// Parse an HFEN string, returning the board and the side to move.
+    pub fn from_hfen(hfen: &str) -> Result<(Board, Player), HfenError> {
+        let fields: Vec<&str> = hfen.split_whitespace().collect();
+        if fields.len() != 7 {
+            return Err(HfenError::InvalidFieldCount);
+        }
+
+        let geometry_err = || HfenError::InvalidGeometry(fields[0].to_string());
+        let (dim_str, side_str) = fields[0].split_once('x').ok_or_else(geometry_err)?;
+        let dimension: usize = dim_str.parse().map_err(|_| geometry_err())?;
+        let side: usize = side_str.parse().map_err(|_| geometry_err())?;
+        if dimension < 2 || !(2..=26).contains(&side) {
+            return Err(geometry_err());
+        }
+
+        let mut board = Board::new_empty(dimension, side);
+        let slice_cells = side * side;
+        let num_slices = board.geo.total_cells / slice_cells;
+
+        // Field 2: placement
+        let slices: Vec<&str> = fields[1].split('|').collect();
+        if slices.len() != num_slices {
+            return Err(HfenError::InvalidSliceCount {
+                expected: num_slices,
+                got: slices.len(),
+            });
+        }
+        for (slice, slice_str) in slices.iter().enumerate() {
+            let ranks: Vec<&str> = slice_str.split('/').collect();
+            if ranks.len() != side {
+                return Err(HfenError::InvalidRankCount {
+                    slice,
+                    expected: side,
+                    got: ranks.len(),
+                });
+            }
+            for (row, rank_str) in ranks.iter().enumerate() {
+                let rank = side - 1 - row;
+                let mut file = 0usize;
+                let mut chars = rank_str.chars().peekable();
+                while let Some(c) = chars.next() {
+                    if let Some(digit) = c.to_digit(10) {
+                        let mut skip = digit as usize;
+                        while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+                            skip = skip * 10 + d as usize;
+                            chars.next();
+                        }
+                        file += skip;
+                    } else {
+                        if file >= side {
+                            return Err(HfenError::RankOverflow { slice, rank });
+                        }
+                        let piece = char_to_piece(c).map_err(|_| HfenError::InvalidPiece(c))?;
+                        board
+                            .pieces
+                            .place_piece_at_index(slice * slice_cells + rank + file * side, piece);
+                        file += 1;
+                    }
+                }
+                if file > side {
+                    return Err(HfenError::RankOverflow { slice, rank });
+                }
+            }
+        }
+
+        // Field 3: side to move
+        let side_to_move = match fields[2] {
+            "w" => Player::White,
+            "b" => Player::Black,
+            other => return Err(HfenError::InvalidSideToMove(other.to_string())),
+        };
+
+        // Field 4: castling rights
+        board.state.castling_rights = parse_castling(fields[3])
+            .map_err(|_| HfenError::InvalidCastling(fields[3].to_string()))?;
+
+        // Field 5: en passant (target:victim)
+        if fields[4] != "-" {
+            let ep_err = || HfenError::InvalidEnPassant(fields[4].to_string());
+            let (target, victim) = fields[4].split_once(':').ok_or_else(ep_err)?;
+            let target = parse_square(target, dimension, side).map_err(|_| ep_err())?;
+            let victim = parse_square(victim, dimension, side).map_err(|_| ep_err())?;
+            let target_idx = board.coords_to_index(&target.values).ok_or_else(ep_err)?;
+            let victim_idx = board.coords_to_index(&victim.values).ok_or_else(ep_err)?;
+            board.state.en_passant_target = Some((target_idx, victim_idx));
+        }
+
+        // Field 6: halfmove clock
+        board.state.halfmove_clock = fields[5]
+            .parse::<u16>()
+            .map_err(|_| HfenError::InvalidHalfmoveClock(fields[5].to_string()))?;
+
+        // Field 7: fullmove number
+        board.state.fullmove_number = fields[6]
+            .parse::<u16>()
+            .map_err(|_| HfenError::InvalidFullmoveNumber(fields[6].to_string()))?;
+
+        board.update_hash(side_to_move);
+
+        // Taper against the geometry's starting material rather than the
+        // current material, so a position transferred mid-game evaluates the
+        // same as it did on the board it came from.
+        let mut reference = board.clone();
+        reference.pieces = PieceMap::new_empty(dimension, side);
+        reference.setup_standard_chess();
+        board.state.start_phase = reference.state.start_phase.max(board.compute_phase());
+
+        Ok((board, side_to_move))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::rules::Rules;
+
+    #[test]
+    fn test_2d_placement_matches_fen() {
+        let board = Board::new(2, 8);
+        let hfen = board.to_hfen(Player::White);
+        assert_eq!(
+            hfen,
+            "2x8 rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    fn test_round_trip_3d_with_en_passant() {
+        let mut board = Board::new(3, 4);
+        let moves = Rules::generate_legal_moves(&mut board, Player::White);
+        let mv = moves
+            .iter()
+            .find(|m| {
+                let from = board.coords_to_index(&m.from.values).unwrap();
+                board.pieces.pawns.get_bit(from)
+            })
+            .unwrap()
+            .clone();
+        board.apply_move(&mv).unwrap();
+
+        let hfen = board.to_hfen(Player::Black);
+        let (parsed, stm) = Board::from_hfen(&hfen).unwrap();
+        assert_eq!(stm, Player::Black);
+        assert_eq!(parsed.state.hash, board.state.hash);
+        assert_eq!(
+            parsed.state.en_passant_target,
+            board.state.en_passant_target
+        );
+        assert_eq!(parsed.state.start_phase, board.state.start_phase);
+        assert_eq!(parsed.to_hfen(Player::Black), hfen);
+    }
+
+    #[test]
+    fn test_multi_digit_empty_runs() {
+        let mut board = Board::new_empty(2, 12);
+        board.pieces.place_piece_at_index(
+            0,
+            crate::domain::models::Piece {
+                piece_type: crate::domain::models::PieceType::King,
+                owner: Player::White,
+            },
+        );
+        let hfen = board.to_hfen(Player::White);
+        assert!(hfen.contains("K11"));
+        let (parsed, _) = Board::from_hfen(&hfen).unwrap();
+        assert_eq!(parsed.to_hfen(Player::White), hfen);
+    }
+
+    #[test]
+    fn test_invalid_slice_count() {
+        assert!(matches!(
+            Board::from_hfen("3x4 4/4/4/4 w - - 0 1"),
+            Err(HfenError::InvalidSliceCount { .. })
+        ));
+    }
+}
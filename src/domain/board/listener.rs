@@ -0,0 +1,27 @@
+use std::any::Any;
+use std::fmt;
+
+use crate::domain::models::Piece;
+
+/// Follows the pieces of a board through `apply_move` and `unmake_move`, so
+/// an evaluation can be kept up to date incrementally instead of being
+/// recomputed from every cell.
+///
+/// For each move the board calls [`begin_move`](Self::begin_move), then
+/// reports every piece lifted and placed; `unmake_move` calls
+/// [`undo_move`](Self::undo_move) to return to the state before. Null moves
+/// change no pieces and are not reported.
+pub trait PieceListener: fmt::Debug + Send + Sync {
+    fn begin_move(&mut self);
+    fn undo_move(&mut self);
+    fn piece_added(&mut self, index: usize, piece: Piece);
+    fn piece_removed(&mut self, index: usize, piece: Piece);
+    fn box_clone(&self) -> Box<dyn PieceListener>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl Clone for Box<dyn PieceListener> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
@@ -5,6 +5,9 @@ pub mod cache;
 pub mod entity;
 pub mod fen;
 pub mod geometry;
+pub mod hfen;
+pub mod listener;
+pub mod notation;
 pub mod pieces;
 pub mod position;
 pub mod san;
@@ -19,6 +22,7 @@ pub use entity::Board;
 pub use entity::GenericBoard;
 pub use entity::UnmakeInfo;
 pub use geometry::{BoardGeometry, Geometry};
+pub use listener::PieceListener;
 pub use pieces::{PieceMap, Pieces};
 pub use position::PositionState;
 pub use u64_board::BitBoard64;
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::domain::board::Board;
+use crate::domain::coordinate::Coordinate;
+use crate::domain::models::{Move, PieceType, Player};
+use crate::domain::rules::Rules;
+
+/// Coordinate (long algebraic) notation for N-dimensional boards.
+///
+/// A square lists its axes from the highest down to axis 0, alternating
+/// number/letter so that axis 1 (files) is a letter and axis 0 (ranks) a
+/// number: `e4` in 2D, `1e4` in 3D, `A1e4` in 4D. Axis 1 is written in lower
+/// case and the other letter axes in upper case; parsing is case-insensitive.
+///
+/// Moves are `<from><to>[promotion]`. In 2D this is exactly UCI (`e2e4`,
+/// `e7e8q`). Squares with an odd number of axes cannot be concatenated
+/// unambiguously, so for every other dimension the two squares are joined
+/// with `-` (`1e2-1e4`). The parser accepts the separator in any dimension.
+#[derive(Debug)]
+pub enum NotationError {
+    InvalidSquare(String),
+    InvalidMove(String),
+    IllegalMove(String),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSquare(s) => write!(f, "Invalid square: '{s}'"),
+            Self::InvalidMove(s) => write!(f, "Invalid move format: '{s}'"),
+            Self::IllegalMove(s) => write!(f, "No legal move matches: '{s}'"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/// Format a square given its coordinates (axis 0 first).
+pub fn format_square(coords: &[u8]) -> String {
+    let mut out = String::new();
+    for d in (0..coords.len()).rev() {
+        let v = coords[d];
+        if d % 2 != 0 {
+            let base = if d == 1 { b'a' } else { b'A' };
+            out.push((base + v) as char);
+        } else {
+            out.push_str(&(v as usize + 1).to_string());
+        }
+    }
+    out
+}
+
+/// Parse one square from the start of `input`, returning the coordinate and
+/// the unconsumed remainder.
+fn parse_square_prefix(input: &str, dim: usize, side: usize) -> Result<(Coordinate, &str), String> {
+    let mut remaining = input;
+    let mut coords: Vec<u8> = vec![0; dim];
+
+    for d in (0..dim).rev() {
+        if remaining.is_empty() {
+            return Err(format!(
+                "Insufficient parts for {}-dimensional coordinate",
+                dim
+            ));
+        }
+
+        if d % 2 != 0 {
+            let c = remaining.chars().next().unwrap();
+            if !c.is_ascii_alphabetic() {
+                return Err(format!(
+                    "Expected Letter for Dimension {}, found number/symbol",
+                    d + 1
+                ));
+            }
+            remaining = &remaining[1..];
+
+            let val = (c.to_ascii_uppercase() as u8).saturating_sub(b'A') as usize;
+            if val >= side {
+                return Err(format!("Coordinate letter '{}' out of bounds", c));
+            }
+            coords[d] = val as u8;
+        } else {
+            let end_idx = remaining
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(remaining.len());
+
+            if end_idx == 0 {
+                return Err(format!(
+                    "Expected Number for Dimension {}, found letter",
+                    d + 1
+                ));
+            }
+
+            let number_part = &remaining[..end_idx];
+            remaining = &remaining[end_idx..];
+
+            let val: usize = number_part.parse().map_err(|_| "Invalid number")?;
+            if val == 0 || val > side {
+                return Err(format!(
+                    "Coordinate number '{}' out of bounds (1-{})",
+                    val, side
+                ));
+            }
+            coords[d] = (val - 1) as u8;
+        }
+    }
+
+    Ok((Coordinate::new(coords), remaining))
+}
+
+/// Parse a complete square such as `e4`, `1e4` or `A1e4`.
+pub fn parse_square(input: &str, dim: usize, side: usize) -> Result<Coordinate, String> {
+    let (coord, rest) = parse_square_prefix(input.trim(), dim, side)?;
+    if !rest.is_empty() {
+        return Err(format!("Unexpected trailing characters '{}'", rest));
+    }
+    Ok(coord)
+}
+
+fn promotion_char(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::Queen => 'q',
+        PieceType::Rook => 'r',
+        PieceType::Bishop => 'b',
+        PieceType::Knight => 'n',
+        PieceType::Pawn => 'p',
+        PieceType::King => 'k',
+    }
+}
+
+/// Format a move in coordinate notation (UCI in 2D).
+pub fn format_move(mv: &Move) -> String {
+    let mut out = format_square(&mv.from.values);
+    if mv.from.values.len() != 2 {
+        out.push('-');
+    }
+    out.push_str(&format_square(&mv.to.values));
+    if let Some(p) = mv.promotion {
+        out.push(promotion_char(p));
+    }
+    out
+}
+
+/// Parse a move without consulting the position. The result may still be
+/// illegal; use [`parse_move`] to resolve it against the legal move list.
+pub fn parse_move_unchecked(input: &str, dim: usize, side: usize) -> Result<Move, NotationError> {
+    let s = input.trim();
+    let invalid = || NotationError::InvalidMove(s.to_string());
+
+    let (from, rest) = parse_square_prefix(s, dim, side).map_err(|_| invalid())?;
+    let rest = rest.strip_prefix('-').unwrap_or(rest);
+    let (to, rest) = parse_square_prefix(rest, dim, side).map_err(|_| invalid())?;
+
+    let promotion = match rest.to_ascii_lowercase().as_str() {
+        "" => None,
+        "q" => Some(PieceType::Queen),
+        "r" => Some(PieceType::Rook),
+        "b" => Some(PieceType::Bishop),
+        "n" => Some(PieceType::Knight),
+        _ => return Err(invalid()),
+    };
+
+    Ok(Move {
+        from,
+        to,
+        promotion,
+    })
+}
+
+/// Parse a coordinate-notation move and return the matching legal move.
+pub fn parse_move(board: &mut Board, player: Player, input: &str) -> Result<Move, NotationError> {
+    let mv = parse_move_unchecked(input, board.dimension(), board.side())?;
+    let legal_moves = Rules::generate_legal_moves(board, player);
+    legal_moves
+        .into_iter()
+        .find(|m| *m == mv)
+        .ok_or_else(|| NotationError::IllegalMove(input.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_round_trip() {
+        assert_eq!(format_square(&[3, 4]), "e4");
+        assert_eq!(format_square(&[3, 4, 0]), "1e4");
+        assert_eq!(format_square(&[3, 4, 0, 0]), "A1e4");
+        for coords in [[0u8, 0, 0, 0], [7, 7, 7, 7], [3, 4, 1, 2]] {
+            let s = format_square(&coords);
+            assert_eq!(parse_square(&s, 4, 8).unwrap().values.as_slice(), &coords);
+        }
+    }
+
+    #[test]
+    fn test_uci_move_2d() {
+        let mut board = Board::new(2, 8);
+        let mv = parse_move(&mut board, Player::White, "e2e4").unwrap();
+        assert_eq!(mv.from.values.as_slice(), &[1, 4]);
+        assert_eq!(mv.to.values.as_slice(), &[3, 4]);
+        assert_eq!(format_move(&mv), "e2e4");
+    }
+
+    #[test]
+    fn test_separator_in_3d() {
+        let mut board = Board::new(3, 4);
+        let legal = Rules::generate_legal_moves(&mut board, Player::White);
+        for mv in legal.iter() {
+            let s = format_move(mv);
+            assert!(s.contains('-'));
+            assert_eq!(&parse_move(&mut board, Player::White, &s).unwrap(), mv);
+        }
+    }
+
+    #[test]
+    fn test_promotion_suffix() {
+        let mv = parse_move_unchecked("a7a8q", 2, 8).unwrap();
+        assert_eq!(mv.promotion, Some(PieceType::Queen));
+        assert_eq!(format_move(&mv), "a7a8q");
+        assert!(parse_move_unchecked("a7a8x", 2, 8).is_err());
+    }
+
+    #[test]
+    fn test_illegal_move_rejected() {
+        let mut board = Board::new(2, 8);
+        assert!(matches!(
+            parse_move(&mut board, Player::White, "e2e5"),
+            Err(NotationError::IllegalMove(_))
+        ));
+    }
+}
@@ -21,6 +21,14 @@ impl Rules {
         attacks::is_square_attacked(board, square, by_player)
     }
 
+    /// Whether `player`'s king is attacked. A board without that king is
+    /// never in check.
+    pub fn is_in_check(board: &Board, player: Player) -> bool {
+        board
+            .get_king_coordinate(player)
+            .is_some_and(|king| attacks::is_square_attacked(board, &king, player.opponent()))
+    }
+
     pub fn scan_ray_for_threat<R: BoardRepresentation>(
         board: &GenericBoard<R>,
         origin_vals: &[u8],
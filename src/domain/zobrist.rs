@@ -1,9 +1,10 @@
 use crate::domain::board::board_representation::BoardRepresentation;
 use crate::domain::board::pieces::PieceMap;
 use crate::domain::board::position::PositionState;
-use crate::domain::models::Player;
+use crate::domain::models::{Piece, PieceType, Player};
 use rand::Rng;
 use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
 #[derive(Debug, Clone)]
 pub struct ZobristKeys {
@@ -17,8 +18,11 @@ impl ZobristKeys {
     /// Create deterministic Zobrist keys seeded from total_cells.
     /// All nodes in a distributed cluster will generate identical keys
     /// for the same board dimensions, ensuring hash compatibility.
+    ///
+    /// The generator is ChaCha8, whose output is fixed, rather than `StdRng`,
+    /// which may change between rand releases: HCBK books store these hashes.
     pub fn new(total_cells: usize) -> Self {
-        let mut rng = rand::rngs::StdRng::seed_from_u64(total_cells as u64);
+        let mut rng = ChaCha8Rng::seed_from_u64(total_cells as u64);
         let size = 12 * total_cells;
         let mut piece_keys = Vec::with_capacity(size);
         for _ in 0..size {
@@ -43,6 +47,25 @@ impl ZobristKeys {
         }
     }
 
+    /// Key of `piece` standing on cell `index`.
+    pub fn piece_key(&self, index: usize, piece: Piece) -> u64 {
+        let offset = match (piece.owner, piece.piece_type) {
+            (Player::White, PieceType::Pawn) => 0,
+            (Player::White, PieceType::Knight) => 1,
+            (Player::White, PieceType::Bishop) => 2,
+            (Player::White, PieceType::Rook) => 3,
+            (Player::White, PieceType::Queen) => 4,
+            (Player::White, PieceType::King) => 5,
+            (Player::Black, PieceType::Pawn) => 6,
+            (Player::Black, PieceType::Knight) => 7,
+            (Player::Black, PieceType::Bishop) => 8,
+            (Player::Black, PieceType::Rook) => 9,
+            (Player::Black, PieceType::Queen) => 10,
+            (Player::Black, PieceType::King) => 11,
+        };
+        self.piece_keys[offset * (self.piece_keys.len() / 12) + index]
+    }
+
     /// Compute hash from decomposed pieces and state (assumes White to move).
     pub fn get_hash<R: BoardRepresentation>(
         &self,
@@ -72,8 +95,9 @@ impl ZobristKeys {
             hash ^= self.en_passant_keys[ep_target];
         }
 
+        // As in `apply_move`, having no rights contributes no key.
         let rights = state.castling_rights as usize;
-        if rights < self.castling_keys.len() {
+        if rights > 0 && rights < self.castling_keys.len() {
             hash ^= self.castling_keys[rights];
         }
 
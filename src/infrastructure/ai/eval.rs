@@ -1,27 +1,16 @@
+use std::fmt;
+
 use smallvec::SmallVec;
 
 use crate::domain::board::Board;
 use crate::domain::models::{PieceType, Player};
 use crate::domain::rules::Rules;
+use crate::infrastructure::ai::eval_params::EvalParams;
+use crate::infrastructure::ai::nnue::NnueAccumulator;
 
-// ── Material values ──────────────────────────────────────────────────
-
-const PAWN_MG: i32 = 100;
-const PAWN_EG: i32 = 150;
-
-const KNIGHT_MG: i32 = 320;
-const KNIGHT_EG: i32 = 300;
-
-const BISHOP_MG: i32 = 330;
-const BISHOP_EG: i32 = 330;
+// Weights live in `EvalParams`; what stays here is structural.
 
-const ROOK_MG: i32 = 500;
-const ROOK_EG: i32 = 500;
-
-const QUEEN_MG: i32 = 900;
-const QUEEN_EG: i32 = 900;
-
-// ── Phase weights ────────────────────────────────────────────────────
+// ── Phase weights (must match `Board::compute_phase`) ─────────────────
 
 const PHASE_PAWN: i32 = 0;
 const PHASE_KNIGHT: i32 = 1;
@@ -29,40 +18,6 @@ const PHASE_BISHOP: i32 = 1;
 const PHASE_ROOK: i32 = 2;
 const PHASE_QUEEN: i32 = 4;
 
-// ── Mobility ─────────────────────────────────────────────────────────
-
-const MOBILITY_KNIGHT_MG: i32 = 4;
-const MOBILITY_KNIGHT_EG: i32 = 4;
-
-const MOBILITY_BISHOP_MG: i32 = 5;
-const MOBILITY_BISHOP_EG: i32 = 5;
-
-const MOBILITY_ROOK_MG: i32 = 2;
-const MOBILITY_ROOK_EG: i32 = 4;
-
-const MOBILITY_QUEEN_MG: i32 = 1;
-const MOBILITY_QUEEN_EG: i32 = 2;
-
-// ── Piece-square table (distance from center) ────────────────────────
-
-const PST_PAWN_DIST_PENALTY_MG: i32 = 2;
-const PST_PAWN_DIST_PENALTY_EG: i32 = 5;
-
-const PST_KNIGHT_DIST_PENALTY_MG: i32 = 4;
-const PST_KNIGHT_DIST_PENALTY_EG: i32 = 4;
-
-const PST_BISHOP_DIST_PENALTY_MG: i32 = 1;
-const PST_BISHOP_DIST_PENALTY_EG: i32 = 1;
-
-const PST_ROOK_DIST_PENALTY_MG: i32 = 0;
-const PST_ROOK_DIST_PENALTY_EG: i32 = 0;
-
-const PST_QUEEN_DIST_PENALTY_MG: i32 = 1;
-const PST_QUEEN_DIST_PENALTY_EG: i32 = 2;
-
-const PST_KING_DIST_BONUS_MG: i32 = 5;
-const PST_KING_DIST_PENALTY_EG: i32 = 10;
-
 // ── King safety (N-dimensional, geometry-normalized) ─────────────────
 //
 // Four signals, all dimension-independent with ratios bounded in [0,1]:
@@ -80,53 +35,8 @@ const PST_KING_DIST_PENALTY_EG: i32 = 10;
 //   4. KING ZONE CONTACT — enemy pieces in the Chebyshev-1 neighborhood,
 //      weighted by piece type (queen=6, rook=3, bishop/knight=2, pawn=1).
 
-const PAWN_SHELTER_TOTAL_MG: i32 = 160;
-const PAWN_SHELTER_TOTAL_EG: i32 = 0;
-
-const OPEN_LINE_PENALTY_MG: i32 = 80;
-const OPEN_LINE_PENALTY_EG: i32 = 10;
-const ATTACKED_LINE_PENALTY_MG: i32 = 120;
-const ATTACKED_LINE_PENALTY_EG: i32 = 15;
 const MAX_PAWN_SCAN_DEPTH: usize = 3;
 
-const TROPISM_QUEEN_WEIGHT: i32 = 4;
-const TROPISM_ROOK_WEIGHT: i32 = 2;
-const TROPISM_TOTAL_MG: i32 = 50;
-const TROPISM_TOTAL_EG: i32 = 10;
-
-const KING_ZONE_CONTACT_MG: i32 = 70;
-const KING_ZONE_CONTACT_EG: i32 = 10;
-
-// ── Pawn structure ───────────────────────────────────────────────────
-
-const PASSED_PAWN_BONUS_MG: i32 = 20;
-const PASSED_PAWN_BONUS_EG: i32 = 40;
-const PASSED_PAWN_ADVANCE_MG: i32 = 3;
-const PASSED_PAWN_ADVANCE_EG: i32 = 6;
-
-const ISOLATED_PAWN_PENALTY_MG: i32 = 10;
-const ISOLATED_PAWN_PENALTY_EG: i32 = 15;
-
-const DOUBLED_PAWN_PENALTY_MG: i32 = 10;
-const DOUBLED_PAWN_PENALTY_EG: i32 = 15;
-
-const CONNECTED_PAWN_BONUS_MG: i32 = 5;
-const CONNECTED_PAWN_BONUS_EG: i32 = 8;
-
-// ── Piece bonuses ────────────────────────────────────────────────────
-
-const BISHOP_PAIR_BONUS_MG: i32 = 30;
-const BISHOP_PAIR_BONUS_EG: i32 = 50;
-
-const ROOK_OPEN_FILE_BONUS_MG: i32 = 15;
-const ROOK_OPEN_FILE_BONUS_EG: i32 = 10;
-
-const ROOK_SEMI_OPEN_FILE_BONUS_MG: i32 = 8;
-const ROOK_SEMI_OPEN_FILE_BONUS_EG: i32 = 5;
-
-const CASTLING_RIGHTS_BONUS_MG: i32 = 10;
-const CASTLING_RIGHTS_BONUS_EG: i32 = 0;
-
 // ── Trade bonus (simplification awareness) ─────────────────────────
 //
 // When one side has more non-pawn material, piece trades amplify
@@ -137,60 +47,289 @@ const CASTLING_RIGHTS_BONUS_EG: i32 = 0;
 // Fully dimension-agnostic: uses only piece counts and stored
 // start_phase, not board topology.
 
-const TRADE_BONUS_MG: i32 = 0; // negligible in middlegame
-const TRADE_BONUS_EG: i32 = 48; // ~½ pawn at maximum simplification
+// ── Evaluation trace ─────────────────────────────────────────────────
+
+/// The named terms that make up the evaluation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EvalTerm {
+    Material,
+    Pst,
+    Mobility,
+    PawnShelter,
+    OpenLines,
+    Tropism,
+    KingZone,
+    PassedPawns,
+    IsolatedPawns,
+    DoubledPawns,
+    ConnectedPawns,
+    BishopPair,
+    RookFiles,
+    CastlingRights,
+    Trade,
+    Outposts,
+    RookOn7th,
+    Space,
+}
+
+impl EvalTerm {
+    pub const ALL: [EvalTerm; 18] = [
+        EvalTerm::Material,
+        EvalTerm::Pst,
+        EvalTerm::Mobility,
+        EvalTerm::PawnShelter,
+        EvalTerm::OpenLines,
+        EvalTerm::Tropism,
+        EvalTerm::KingZone,
+        EvalTerm::PassedPawns,
+        EvalTerm::IsolatedPawns,
+        EvalTerm::DoubledPawns,
+        EvalTerm::ConnectedPawns,
+        EvalTerm::BishopPair,
+        EvalTerm::RookFiles,
+        EvalTerm::CastlingRights,
+        EvalTerm::Trade,
+        EvalTerm::Outposts,
+        EvalTerm::RookOn7th,
+        EvalTerm::Space,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EvalTerm::Material => "material",
+            EvalTerm::Pst => "pst",
+            EvalTerm::Mobility => "mobility",
+            EvalTerm::PawnShelter => "pawn_shelter",
+            EvalTerm::OpenLines => "open_lines",
+            EvalTerm::Tropism => "tropism",
+            EvalTerm::KingZone => "king_zone",
+            EvalTerm::PassedPawns => "passed_pawns",
+            EvalTerm::IsolatedPawns => "isolated_pawns",
+            EvalTerm::DoubledPawns => "doubled_pawns",
+            EvalTerm::ConnectedPawns => "connected_pawns",
+            EvalTerm::BishopPair => "bishop_pair",
+            EvalTerm::RookFiles => "rook_files",
+            EvalTerm::CastlingRights => "castling_rights",
+            EvalTerm::Trade => "trade",
+            EvalTerm::Outposts => "outposts",
+            EvalTerm::RookOn7th => "rook_on_7th",
+            EvalTerm::Space => "space",
+        }
+    }
+}
+
+/// Midgame and endgame centipawns, from one side's point of view.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TermScore {
+    pub mg: i32,
+    pub eg: i32,
+}
+
+/// One term's contribution for each side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TermTrace {
+    pub term: EvalTerm,
+    pub white: TermScore,
+    pub black: TermScore,
+}
+
+impl TermTrace {
+    /// White's contribution minus Black's.
+    pub fn net(&self) -> TermScore {
+        TermScore {
+            mg: self.white.mg - self.black.mg,
+            eg: self.white.eg - self.black.eg,
+        }
+    }
+}
 
-// ── Tempo bonus (#19) ────────────────────────────────────────────────
-const TEMPO_BONUS: i32 = 15;
+/// [`Evaluator::evaluate`] broken down term by term.
+///
+/// `mg` and `eg` are the net totals over all terms; `tapered` blends them
+/// by `phase` out of `start_phase`. Tempo and rule-50 damping are applied
+/// after tapering, giving `value`, which always equals `evaluate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvalTrace {
+    pub terms: Vec<TermTrace>,
+    pub mg: i32,
+    pub eg: i32,
+    pub phase: i32,
+    pub start_phase: i32,
+    pub tapered: i32,
+    pub tempo: i32,
+    pub rule50: i32,
+    pub value: i32,
+}
+
+impl fmt::Display for EvalTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<16} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}",
+            "term", "white mg", "white eg", "black mg", "black eg", "net mg", "net eg"
+        )?;
+        for t in &self.terms {
+            let net = t.net();
+            writeln!(
+                f,
+                "{:<16} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}",
+                t.term.name(),
+                t.white.mg,
+                t.white.eg,
+                t.black.mg,
+                t.black.eg,
+                net.mg,
+                net.eg
+            )?;
+        }
+        writeln!(f, "{:<52} {:>8} {:>8}", "total", self.mg, self.eg)?;
+        writeln!(f, "phase            {}/{}", self.phase, self.start_phase)?;
+        writeln!(f, "tapered          {:+}", self.tapered)?;
+        writeln!(f, "tempo            {:+}", self.tempo)?;
+        writeln!(f, "rule50           {:+}", self.rule50)?;
+        write!(f, "eval             {:+} (White's view)", self.value)
+    }
+}
+
+/// Receives each term as it is computed: summed for search, itemised for
+/// a trace. `mg`/`eg` are from `player`'s point of view.
+trait EvalSink {
+    fn add(&mut self, term: EvalTerm, player: Player, mg: i32, eg: i32);
+}
+
+/// White-minus-Black totals, all the search needs.
+#[derive(Default)]
+struct Totals {
+    mg: i32,
+    eg: i32,
+}
 
-// ── Rule50 / shuffle damping (#20) ──────────────────────────────────
-// Dampens eval toward draw as 50-move rule approaches.
-const RULE50_DIVISOR: i32 = 199;
+impl EvalSink for Totals {
+    #[inline(always)]
+    fn add(&mut self, _term: EvalTerm, player: Player, mg: i32, eg: i32) {
+        match player {
+            Player::White => {
+                self.mg += mg;
+                self.eg += eg;
+            }
+            Player::Black => {
+                self.mg -= mg;
+                self.eg -= eg;
+            }
+        }
+    }
+}
 
-// ── Outpost bonus (#21) ─────────────────────────────────────────────
-// Knight/bishop on squares that can't be attacked by enemy pawns.
-const OUTPOST_KNIGHT_MG: i32 = 20;
-const OUTPOST_KNIGHT_EG: i32 = 15;
-const OUTPOST_BISHOP_MG: i32 = 10;
-const OUTPOST_BISHOP_EG: i32 = 8;
+/// Per-term, per-side sums, indexed like [`EvalTerm::ALL`].
+struct Breakdown([[TermScore; 2]; EvalTerm::ALL.len()]);
 
-// ── Rook on 7th rank (#22) ──────────────────────────────────────────
-const ROOK_ON_7TH_MG: i32 = 20;
-const ROOK_ON_7TH_EG: i32 = 30;
+impl EvalSink for Breakdown {
+    fn add(&mut self, term: EvalTerm, player: Player, mg: i32, eg: i32) {
+        let side = match player {
+            Player::White => 0,
+            Player::Black => 1,
+        };
+        let score = &mut self.0[term as usize][side];
+        score.mg += mg;
+        score.eg += eg;
+    }
+}
 
-// ── Space advantage (#23) ───────────────────────────────────────────
-// Bonus for controlling squares beyond the pawn line in own half.
-const SPACE_BONUS_MG: i32 = 2;
-const SPACE_BONUS_EG: i32 = 0;
+/// What happens to the net midgame/endgame totals after gathering.
+struct Taper {
+    phase: i32,
+    start_phase: i32,
+    tapered: i32,
+    rule50: i32,
+    value: i32,
+}
 
 pub struct Evaluator;
 
 impl Evaluator {
-    pub fn evaluate(board: &Board) -> i32 {
-        let (mg_score, eg_score, phase) = Self::gather_scores(board);
+    pub fn evaluate(board: &Board, params: &EvalParams) -> i32 {
+        let mut totals = Totals::default();
+        let phase = Self::gather_scores(board, params, &mut totals);
+        Self::taper(board, params, totals.mg, totals.eg, phase).value
+    }
+
+    /// The search's evaluation, from White's view: the network whose
+    /// accumulators `board` carries (see [`Network::attach`]), damped towards
+    /// a draw as the fifty-move count grows, or [`Self::evaluate`] if it
+    /// carries none.
+    ///
+    /// [`Network::attach`]: crate::infrastructure::ai::nnue::Network::attach
+    pub fn static_eval(board: &Board, params: &EvalParams) -> i32 {
+        match NnueAccumulator::of(board) {
+            Some(nnue) => {
+                let v = nnue.evaluate();
+                let halfmove_clock = board.state.halfmove_clock as i32;
+                v - v * halfmove_clock / params.rule50_divisor.max(1)
+            }
+            None => Self::evaluate(board, params),
+        }
+    }
 
+    /// Like [`Evaluator::evaluate`], with every term's midgame and endgame
+    /// contribution for each side.
+    pub fn evaluate_trace(board: &Board, params: &EvalParams) -> EvalTrace {
+        let mut breakdown = Breakdown([[TermScore::default(); 2]; EvalTerm::ALL.len()]);
+        let phase = Self::gather_scores(board, params, &mut breakdown);
+
+        let terms: Vec<TermTrace> = EvalTerm::ALL
+            .iter()
+            .map(|&term| {
+                let [white, black] = breakdown.0[term as usize];
+                TermTrace { term, white, black }
+            })
+            .collect();
+        let mg = terms.iter().map(|t| t.net().mg).sum();
+        let eg = terms.iter().map(|t| t.net().eg).sum();
+        let taper = Self::taper(board, params, mg, eg, phase);
+
+        EvalTrace {
+            terms,
+            mg,
+            eg,
+            phase: taper.phase,
+            start_phase: taper.start_phase,
+            tapered: taper.tapered,
+            tempo: params.tempo_bonus,
+            rule50: taper.rule50,
+            value: taper.value,
+        }
+    }
+
+    fn taper(board: &Board, p: &EvalParams, mg_score: i32, eg_score: i32, phase: i32) -> Taper {
         let start_phase = board.state.start_phase.max(1);
         let phase = phase.min(start_phase);
 
-        let mut v = (mg_score * phase + eg_score * (start_phase - phase)) / start_phase;
+        let tapered = (mg_score * phase + eg_score * (start_phase - phase)) / start_phase;
 
         // Tempo bonus (#19): side to move gets a small advantage
         // (board always evaluates from White's perspective, caller negates for Black)
         // We add tempo unconditionally; the search handles negation.
-        v += TEMPO_BONUS;
+        let v = tapered + p.tempo_bonus;
 
         // Rule50 / shuffle damping (#20): dampen eval toward draw
-        let rule50 = board.state.halfmove_clock as i32;
-        if rule50 > 0 {
-            v -= v * rule50 / RULE50_DIVISOR;
-        }
+        let halfmove_clock = board.state.halfmove_clock as i32;
+        let rule50 = if halfmove_clock > 0 {
+            -(v * halfmove_clock / p.rule50_divisor.max(1))
+        } else {
+            0
+        };
 
-        v
+        Taper {
+            phase,
+            start_phase,
+            tapered,
+            rule50,
+            value: v + rule50,
+        }
     }
 
-    fn gather_scores(board: &Board) -> (i32, i32, i32) {
-        let mut mg_score = 0;
-        let mut eg_score = 0;
+    /// Feed every term into `sink`; returns the game phase.
+    fn gather_scores(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) -> i32 {
         let mut phase = 0;
 
         // Track non-pawn material per side (for trade bonus)
@@ -198,81 +337,62 @@ impl Evaluator {
         let mut black_npm = 0i32;
 
         // Per-piece evaluation (material + PST + mobility)
-        for (idx, piece_type) in Self::iter_pieces(board, Player::White) {
-            let (mg, eg, p) = Self::evaluate_piece(board, idx, piece_type, Player::White);
-            mg_score += mg;
-            eg_score += eg;
-            phase += p;
-            if piece_type != PieceType::Pawn && piece_type != PieceType::King {
-                white_npm += Self::piece_value_mg(piece_type);
-            }
-        }
-
-        for (idx, piece_type) in Self::iter_pieces(board, Player::Black) {
-            let (mg, eg, p) = Self::evaluate_piece(board, idx, piece_type, Player::Black);
-            mg_score -= mg;
-            eg_score -= eg;
-            phase += p;
-            if piece_type != PieceType::Pawn && piece_type != PieceType::King {
-                black_npm += Self::piece_value_mg(piece_type);
+        for player in [Player::White, Player::Black] {
+            for (idx, piece_type) in Self::iter_pieces(board, player) {
+                phase += Self::evaluate_piece(board, p, idx, piece_type, player, sink);
+                if piece_type != PieceType::Pawn && piece_type != PieceType::King {
+                    match player {
+                        Player::White => white_npm += Self::piece_value_mg(p, piece_type),
+                        Player::Black => black_npm += Self::piece_value_mg(p, piece_type),
+                    }
+                }
             }
         }
 
         // Positional evaluation (N-dimensional)
-        let (ks_mg, ks_eg) = Self::evaluate_king_safety(board);
-        mg_score += ks_mg;
-        eg_score += ks_eg;
+        Self::evaluate_king_safety(board, p, sink);
 
         // Cache pawn indices once (used by pawn structure + outposts)
         let white_pawns = Self::get_pawn_indices(board, Player::White);
         let black_pawns = Self::get_pawn_indices(board, Player::Black);
 
-        let (ps_mg, ps_eg) = Self::evaluate_pawn_structure(board, &white_pawns, &black_pawns);
-        mg_score += ps_mg;
-        eg_score += ps_eg;
-
-        let (bp_mg, bp_eg) = Self::evaluate_bishop_pair(board);
-        mg_score += bp_mg;
-        eg_score += bp_eg;
-
-        let (rf_mg, rf_eg) = Self::evaluate_rook_files(board);
-        mg_score += rf_mg;
-        eg_score += rf_eg;
-
-        let (cr_mg, cr_eg) = Self::evaluate_castling_rights(board);
-        mg_score += cr_mg;
-        eg_score += cr_eg;
+        Self::evaluate_pawn_structure(board, p, &white_pawns, &black_pawns, sink);
+        Self::evaluate_bishop_pair(board, p, sink);
+        Self::evaluate_rook_files(board, p, sink);
+        Self::evaluate_castling_rights(board, p, sink);
 
         // ── Trade bonus ──
+        // Credited to the side with more non-pawn material.
         let start_phase = board.state.start_phase.max(1);
         let npm_diff = white_npm - black_npm;
         let simplification = start_phase - phase.min(start_phase);
-        let npm_norm = npm_diff / ROOK_MG.max(1);
-        mg_score += npm_norm * TRADE_BONUS_MG * simplification / start_phase;
-        eg_score += npm_norm * TRADE_BONUS_EG * simplification / start_phase;
+        let npm_norm = npm_diff / p.rook_mg.max(1);
+        let (ahead, lead) = if npm_norm >= 0 {
+            (Player::White, npm_norm)
+        } else {
+            (Player::Black, -npm_norm)
+        };
+        sink.add(
+            EvalTerm::Trade,
+            ahead,
+            lead * p.trade_bonus_mg * simplification / start_phase,
+            lead * p.trade_bonus_eg * simplification / start_phase,
+        );
 
         // Outposts (#21), rook on 7th (#22), space (#23)
-        let (out_mg, out_eg) = Self::evaluate_outposts(board, &white_pawns, &black_pawns);
-        mg_score += out_mg;
-        eg_score += out_eg;
-
-        let (r7_mg, r7_eg) = Self::evaluate_rook_7th(board);
-        mg_score += r7_mg;
-        eg_score += r7_eg;
-
-        let (sp_mg, sp_eg) = Self::evaluate_space(board);
-        mg_score += sp_mg;
-        eg_score += sp_eg;
+        Self::evaluate_outposts(board, p, &white_pawns, &black_pawns, sink);
+        Self::evaluate_rook_7th(board, p, sink);
+        Self::evaluate_space(board, p, sink);
 
-        (mg_score, eg_score, phase)
+        phase
     }
 
-    fn piece_value_mg(piece_type: PieceType) -> i32 {
+    fn piece_value_mg(p: &EvalParams, piece_type: PieceType) -> i32 {
         match piece_type {
-            PieceType::Knight => KNIGHT_MG,
-            PieceType::Bishop => BISHOP_MG,
-            PieceType::Rook => ROOK_MG,
-            PieceType::Queen => QUEEN_MG,
+            PieceType::Knight => p.knight_mg,
+            PieceType::Bishop => p.bishop_mg,
+            PieceType::Rook => p.rook_mg,
+            PieceType::Queen => p.queen_mg,
             _ => 0,
         }
     }
@@ -304,84 +424,85 @@ impl Evaluator {
         })
     }
 
+    /// Material, PST and mobility of one piece; returns its phase weight.
     fn evaluate_piece(
         board: &Board,
+        p: &EvalParams,
         index: usize,
         piece_type: PieceType,
         player: Player,
-    ) -> (i32, i32, i32) {
-        let mut mg = 0;
-        let mut eg = 0;
-        let mut phase = 0;
-
-        let (mat_mg, mat_eg, ph) = match piece_type {
-            PieceType::Pawn => (PAWN_MG, PAWN_EG, PHASE_PAWN),
-            PieceType::Knight => (KNIGHT_MG, KNIGHT_EG, PHASE_KNIGHT),
-            PieceType::Bishop => (BISHOP_MG, BISHOP_EG, PHASE_BISHOP),
-            PieceType::Rook => (ROOK_MG, ROOK_EG, PHASE_ROOK),
-            PieceType::Queen => (QUEEN_MG, QUEEN_EG, PHASE_QUEEN),
+        sink: &mut impl EvalSink,
+    ) -> i32 {
+        let (mat_mg, mat_eg, phase) = match piece_type {
+            PieceType::Pawn => (p.pawn_mg, p.pawn_eg, PHASE_PAWN),
+            PieceType::Knight => (p.knight_mg, p.knight_eg, PHASE_KNIGHT),
+            PieceType::Bishop => (p.bishop_mg, p.bishop_eg, PHASE_BISHOP),
+            PieceType::Rook => (p.rook_mg, p.rook_eg, PHASE_ROOK),
+            PieceType::Queen => (p.queen_mg, p.queen_eg, PHASE_QUEEN),
             PieceType::King => (0, 0, 0),
         };
-        mg += mat_mg;
-        eg += mat_eg;
-        phase += ph;
+        sink.add(EvalTerm::Material, player, mat_mg, mat_eg);
 
         let dist_int = board.geo.cache.center_dist[index];
 
         let (pst_mg, pst_eg) = match piece_type {
             PieceType::Pawn => (
-                -dist_int * PST_PAWN_DIST_PENALTY_MG,
-                -dist_int * PST_PAWN_DIST_PENALTY_EG,
+                -dist_int * p.pst_pawn_dist_penalty_mg,
+                -dist_int * p.pst_pawn_dist_penalty_eg,
             ),
             PieceType::Knight => (
-                -dist_int * PST_KNIGHT_DIST_PENALTY_MG,
-                -dist_int * PST_KNIGHT_DIST_PENALTY_EG,
+                -dist_int * p.pst_knight_dist_penalty_mg,
+                -dist_int * p.pst_knight_dist_penalty_eg,
             ),
             PieceType::Bishop => (
-                -dist_int * PST_BISHOP_DIST_PENALTY_MG,
-                -dist_int * PST_BISHOP_DIST_PENALTY_EG,
+                -dist_int * p.pst_bishop_dist_penalty_mg,
+                -dist_int * p.pst_bishop_dist_penalty_eg,
             ),
             PieceType::Rook => (
-                -dist_int * PST_ROOK_DIST_PENALTY_MG,
-                -dist_int * PST_ROOK_DIST_PENALTY_EG,
+                -dist_int * p.pst_rook_dist_penalty_mg,
+                -dist_int * p.pst_rook_dist_penalty_eg,
             ),
             PieceType::Queen => (
-                -dist_int * PST_QUEEN_DIST_PENALTY_MG,
-                -dist_int * PST_QUEEN_DIST_PENALTY_EG,
+                -dist_int * p.pst_queen_dist_penalty_mg,
+                -dist_int * p.pst_queen_dist_penalty_eg,
             ),
             PieceType::King => (
-                dist_int * PST_KING_DIST_BONUS_MG,
-                -dist_int * PST_KING_DIST_PENALTY_EG,
+                dist_int * p.pst_king_dist_bonus_mg,
+                -dist_int * p.pst_king_dist_penalty_eg,
             ),
         };
-        mg += pst_mg;
-        eg += pst_eg;
+        sink.add(EvalTerm::Pst, player, pst_mg, pst_eg);
 
         if piece_type != PieceType::Pawn && piece_type != PieceType::King {
             let mobility = Rules::count_piece_mobility_for(board, index, piece_type, player);
             let (mob_mg, mob_eg) = match piece_type {
-                PieceType::Knight => (mobility * MOBILITY_KNIGHT_MG, mobility * MOBILITY_KNIGHT_EG),
-                PieceType::Bishop => (mobility * MOBILITY_BISHOP_MG, mobility * MOBILITY_BISHOP_EG),
-                PieceType::Rook => (mobility * MOBILITY_ROOK_MG, mobility * MOBILITY_ROOK_EG),
-                PieceType::Queen => (mobility * MOBILITY_QUEEN_MG, mobility * MOBILITY_QUEEN_EG),
+                PieceType::Knight => (
+                    mobility * p.mobility_knight_mg,
+                    mobility * p.mobility_knight_eg,
+                ),
+                PieceType::Bishop => (
+                    mobility * p.mobility_bishop_mg,
+                    mobility * p.mobility_bishop_eg,
+                ),
+                PieceType::Rook => (mobility * p.mobility_rook_mg, mobility * p.mobility_rook_eg),
+                PieceType::Queen => (
+                    mobility * p.mobility_queen_mg,
+                    mobility * p.mobility_queen_eg,
+                ),
                 _ => (0, 0),
             };
-            mg += mob_mg;
-            eg += mob_eg;
+            sink.add(EvalTerm::Mobility, player, mob_mg, mob_eg);
         }
 
-        (mg, eg, phase)
+        phase
     }
 
     // ── King Safety (N-dimensional) ───────────────────────────────────
 
-    fn evaluate_king_safety(board: &Board) -> (i32, i32) {
-        let mut mg = 0i32;
-        let mut eg = 0i32;
-
+    fn evaluate_king_safety(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) {
         let zone_size = board.geo.cache.king_offsets.len() as i32;
         if zone_size == 0 {
-            return (0, 0);
+            return;
         }
 
         // Forward zone sizes (pre-computed once, same for all positions).
@@ -407,7 +528,6 @@ impl Evaluator {
         let max_dist = (board.side() as i32) - 1;
 
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let king_coord = match board.get_king_coordinate(player) {
                 Some(k) => k,
                 None => continue,
@@ -446,8 +566,12 @@ impl Evaluator {
                 }
             }
             if forward_size > 0 {
-                mg += sign * (shelter_count * PAWN_SHELTER_TOTAL_MG) / forward_size;
-                eg += sign * (shelter_count * PAWN_SHELTER_TOTAL_EG) / forward_size;
+                sink.add(
+                    EvalTerm::PawnShelter,
+                    player,
+                    (shelter_count * p.pawn_shelter_total_mg) / forward_size,
+                    (shelter_count * p.pawn_shelter_total_eg) / forward_size,
+                );
             }
 
             // ── Signal 2: Open lines toward king ──
@@ -459,7 +583,12 @@ impl Evaluator {
 
             for dir_info in &board.geo.cache.rook_directions {
                 let (open, attacked) = Self::ray_shield_and_threat(
-                    board, king_idx, dir_info, my_occ, enemy_occ, max_scan,
+                    board,
+                    king_idx,
+                    dir_info,
+                    my_occ,
+                    enemy_occ,
+                    max_scan,
                     |b, i| b.pieces.rooks.get_bit(i) || b.pieces.queens.get_bit(i),
                 );
                 open_count += open as i32;
@@ -468,7 +597,12 @@ impl Evaluator {
 
             for dir_info in &board.geo.cache.bishop_directions {
                 let (open, attacked) = Self::ray_shield_and_threat(
-                    board, king_idx, dir_info, my_occ, enemy_occ, max_scan,
+                    board,
+                    king_idx,
+                    dir_info,
+                    my_occ,
+                    enemy_occ,
+                    max_scan,
                     |b, i| b.pieces.bishops.get_bit(i) || b.pieces.queens.get_bit(i),
                 );
                 open_count += open as i32;
@@ -476,10 +610,14 @@ impl Evaluator {
             }
 
             if total_dirs > 0 {
-                mg -= sign * (open_count * OPEN_LINE_PENALTY_MG) / total_dirs;
-                eg -= sign * (open_count * OPEN_LINE_PENALTY_EG) / total_dirs;
-                mg -= sign * (attacked_count * ATTACKED_LINE_PENALTY_MG) / total_dirs;
-                eg -= sign * (attacked_count * ATTACKED_LINE_PENALTY_EG) / total_dirs;
+                sink.add(
+                    EvalTerm::OpenLines,
+                    player,
+                    -(open_count * p.open_line_penalty_mg) / total_dirs
+                        - (attacked_count * p.attacked_line_penalty_mg) / total_dirs,
+                    -(open_count * p.open_line_penalty_eg) / total_dirs
+                        - (attacked_count * p.attacked_line_penalty_eg) / total_dirs,
+                );
             }
 
             // ── Signal 3: Heavy piece tropism (Q/R Chebyshev distance) ──
@@ -501,17 +639,21 @@ impl Evaluator {
                     .unwrap_or(0);
                 let proximity = (max_dist - chebyshev).max(0);
                 let weight = if is_queen {
-                    TROPISM_QUEEN_WEIGHT
+                    p.tropism_queen_weight
                 } else {
-                    TROPISM_ROOK_WEIGHT
+                    p.tropism_rook_weight
                 };
                 tropism_score += proximity * weight;
             }
 
-            let normalizer = max_dist * (TROPISM_QUEEN_WEIGHT + 2 * TROPISM_ROOK_WEIGHT);
+            let normalizer = max_dist * (p.tropism_queen_weight + 2 * p.tropism_rook_weight);
             if normalizer > 0 {
-                mg -= sign * (tropism_score * TROPISM_TOTAL_MG) / normalizer;
-                eg -= sign * (tropism_score * TROPISM_TOTAL_EG) / normalizer;
+                sink.add(
+                    EvalTerm::Tropism,
+                    player,
+                    -(tropism_score * p.tropism_total_mg) / normalizer,
+                    -(tropism_score * p.tropism_total_eg) / normalizer,
+                );
             }
 
             // ── Signal 4: King zone enemy contact (weighted by piece type) ──
@@ -537,12 +679,14 @@ impl Evaluator {
             }
             let max_contact = zone_size * 6;
             if max_contact > 0 {
-                mg -= sign * (contact_score * KING_ZONE_CONTACT_MG) / max_contact;
-                eg -= sign * (contact_score * KING_ZONE_CONTACT_EG) / max_contact;
+                sink.add(
+                    EvalTerm::KingZone,
+                    player,
+                    -(contact_score * p.king_zone_contact_mg) / max_contact,
+                    -(contact_score * p.king_zone_contact_eg) / max_contact,
+                );
             }
         }
-
-        (mg, eg)
     }
 
     /// Combined pawn-shield + threat scan in a single ray walk.
@@ -596,15 +740,14 @@ impl Evaluator {
 
     fn evaluate_pawn_structure(
         board: &Board,
+        p: &EvalParams,
         white_pawns: &SmallVec<[usize; 16]>,
         black_pawns: &SmallVec<[usize; 16]>,
-    ) -> (i32, i32) {
-        let mut mg = 0;
-        let mut eg = 0;
+        sink: &mut impl EvalSink,
+    ) {
         let dim = board.dimension();
 
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let (my_pawns, enemy_pawns) = match player {
                 Player::White => (white_pawns, black_pawns),
                 Player::Black => (black_pawns, white_pawns),
@@ -619,31 +762,45 @@ impl Evaluator {
                         Player::White => coords[0] as i32,
                         Player::Black => (board.side() as i32 - 1) - coords[0] as i32,
                     };
-                    mg += sign * (PASSED_PAWN_BONUS_MG + advancement * PASSED_PAWN_ADVANCE_MG);
-                    eg += sign * (PASSED_PAWN_BONUS_EG + advancement * PASSED_PAWN_ADVANCE_EG);
+                    sink.add(
+                        EvalTerm::PassedPawns,
+                        player,
+                        p.passed_pawn_bonus_mg + advancement * p.passed_pawn_advance_mg,
+                        p.passed_pawn_bonus_eg + advancement * p.passed_pawn_advance_eg,
+                    );
                 }
 
                 // Isolated pawn: no friendly pawn on adjacent file columns
                 if Self::is_isolated_pawn(board, coords, my_pawns, dim) {
-                    mg -= sign * ISOLATED_PAWN_PENALTY_MG;
-                    eg -= sign * ISOLATED_PAWN_PENALTY_EG;
+                    sink.add(
+                        EvalTerm::IsolatedPawns,
+                        player,
+                        -p.isolated_pawn_penalty_mg,
+                        -p.isolated_pawn_penalty_eg,
+                    );
                 }
 
                 // Doubled pawn: another friendly pawn on same file column
                 if Self::is_doubled_pawn(board, coords, my_pawns, pawn_idx, dim) {
-                    mg -= sign * DOUBLED_PAWN_PENALTY_MG;
-                    eg -= sign * DOUBLED_PAWN_PENALTY_EG;
+                    sink.add(
+                        EvalTerm::DoubledPawns,
+                        player,
+                        -p.doubled_pawn_penalty_mg,
+                        -p.doubled_pawn_penalty_eg,
+                    );
                 }
 
                 // Connected pawn: protected by a friendly pawn
                 if Self::is_connected_pawn(board, pawn_idx, player) {
-                    mg += sign * CONNECTED_PAWN_BONUS_MG;
-                    eg += sign * CONNECTED_PAWN_BONUS_EG;
+                    sink.add(
+                        EvalTerm::ConnectedPawns,
+                        player,
+                        p.connected_pawn_bonus_mg,
+                        p.connected_pawn_bonus_eg,
+                    );
                 }
             }
         }
-
-        (mg, eg)
     }
 
     fn get_pawn_indices(board: &Board, player: Player) -> SmallVec<[usize; 16]> {
@@ -766,12 +923,8 @@ impl Evaluator {
 
     // ── Bishop pair ──────────────────────────────────────────────────
 
-    fn evaluate_bishop_pair(board: &Board) -> (i32, i32) {
-        let mut mg = 0;
-        let mut eg = 0;
-
+    fn evaluate_bishop_pair(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) {
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let occ = match player {
                 Player::White => &board.pieces.white_occupancy,
                 Player::Black => &board.pieces.black_occupancy,
@@ -784,22 +937,20 @@ impl Evaluator {
                 }
             }
             if bishop_count >= 2 {
-                mg += sign * BISHOP_PAIR_BONUS_MG;
-                eg += sign * BISHOP_PAIR_BONUS_EG;
+                sink.add(
+                    EvalTerm::BishopPair,
+                    player,
+                    p.bishop_pair_bonus_mg,
+                    p.bishop_pair_bonus_eg,
+                );
             }
         }
-
-        (mg, eg)
     }
 
     // ── Rook on open/semi-open file ──────────────────────────────────
 
-    fn evaluate_rook_files(board: &Board) -> (i32, i32) {
-        let mut mg = 0;
-        let mut eg = 0;
-
+    fn evaluate_rook_files(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) {
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let occ = match player {
                 Player::White => &board.pieces.white_occupancy,
                 Player::Black => &board.pieces.black_occupancy,
@@ -814,16 +965,22 @@ impl Evaluator {
                 let has_enemy = Self::file_column_has_pawn(board, idx, player.opponent());
 
                 if !has_friendly && !has_enemy {
-                    mg += sign * ROOK_OPEN_FILE_BONUS_MG;
-                    eg += sign * ROOK_OPEN_FILE_BONUS_EG;
+                    sink.add(
+                        EvalTerm::RookFiles,
+                        player,
+                        p.rook_open_file_bonus_mg,
+                        p.rook_open_file_bonus_eg,
+                    );
                 } else if !has_friendly {
-                    mg += sign * ROOK_SEMI_OPEN_FILE_BONUS_MG;
-                    eg += sign * ROOK_SEMI_OPEN_FILE_BONUS_EG;
+                    sink.add(
+                        EvalTerm::RookFiles,
+                        player,
+                        p.rook_semi_open_file_bonus_mg,
+                        p.rook_semi_open_file_bonus_eg,
+                    );
                 }
             }
         }
-
-        (mg, eg)
     }
 
     /// Check if any pawn of `player` exists on the same file column
@@ -846,14 +1003,18 @@ impl Evaluator {
 
     // ── Castling rights bonus ────────────────────────────────────────
 
-    fn evaluate_castling_rights(board: &Board) -> (i32, i32) {
+    fn evaluate_castling_rights(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) {
         let white_rights = (board.state.castling_rights & 0x3).count_ones() as i32;
         let black_rights = ((board.state.castling_rights >> 2) & 0x3).count_ones() as i32;
 
-        let mg = (white_rights - black_rights) * CASTLING_RIGHTS_BONUS_MG;
-        let eg = (white_rights - black_rights) * CASTLING_RIGHTS_BONUS_EG;
-
-        (mg, eg)
+        for (player, rights) in [(Player::White, white_rights), (Player::Black, black_rights)] {
+            sink.add(
+                EvalTerm::CastlingRights,
+                player,
+                rights * p.castling_rights_bonus_mg,
+                rights * p.castling_rights_bonus_eg,
+            );
+        }
     }
 
     // ── Outpost detection (#21) ──────────────────────────────────────
@@ -863,15 +1024,14 @@ impl Evaluator {
 
     fn evaluate_outposts(
         board: &Board,
+        p: &EvalParams,
         white_pawns: &SmallVec<[usize; 16]>,
         black_pawns: &SmallVec<[usize; 16]>,
-    ) -> (i32, i32) {
-        let mut mg = 0;
-        let mut eg = 0;
+        sink: &mut impl EvalSink,
+    ) {
         let dim = board.dimension();
 
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let occ = match player {
                 Player::White => &board.pieces.white_occupancy,
                 Player::Black => &board.pieces.black_occupancy,
@@ -922,31 +1082,25 @@ impl Evaluator {
                         Player::Black => (rank as usize) < board.side() / 2,
                     };
                     if in_enemy_half {
-                        if is_knight {
-                            mg += sign * OUTPOST_KNIGHT_MG;
-                            eg += sign * OUTPOST_KNIGHT_EG;
+                        let (mg, eg) = if is_knight {
+                            (p.outpost_knight_mg, p.outpost_knight_eg)
                         } else {
-                            mg += sign * OUTPOST_BISHOP_MG;
-                            eg += sign * OUTPOST_BISHOP_EG;
-                        }
+                            (p.outpost_bishop_mg, p.outpost_bishop_eg)
+                        };
+                        sink.add(EvalTerm::Outposts, player, mg, eg);
                     }
                 }
             }
         }
-
-        (mg, eg)
     }
 
     // ── Rook on 7th rank (#22) ───────────────────────────────────────
     // Bonus for rook on the penultimate rank (rank N-2 for white, rank 1 for black).
 
-    fn evaluate_rook_7th(board: &Board) -> (i32, i32) {
-        let mut mg = 0;
-        let mut eg = 0;
+    fn evaluate_rook_7th(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) {
         let last_rank = board.side() - 1;
 
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let occ = match player {
                 Player::White => &board.pieces.white_occupancy,
                 Player::Black => &board.pieces.black_occupancy,
@@ -962,13 +1116,15 @@ impl Evaluator {
                 }
                 let rank = board.geo.cache.index_to_coords[idx][0] as usize;
                 if rank == seventh {
-                    mg += sign * ROOK_ON_7TH_MG;
-                    eg += sign * ROOK_ON_7TH_EG;
+                    sink.add(
+                        EvalTerm::RookOn7th,
+                        player,
+                        p.rook_on_7th_mg,
+                        p.rook_on_7th_eg,
+                    );
                 }
             }
         }
-
-        (mg, eg)
     }
 
     // ── Space advantage (#23) ────────────────────────────────────────
@@ -976,20 +1132,17 @@ impl Evaluator {
     // own pawns (safe, controlled space). N-dimensional: uses
     // center distance + rank-based filtering.
 
-    fn evaluate_space(board: &Board) -> (i32, i32) {
+    fn evaluate_space(board: &Board, p: &EvalParams, sink: &mut impl EvalSink) {
         // Skip for high-dimensional boards (>3D): scanning all cells is too
         // expensive for a marginal 2cp/square bonus.
         if board.total_cells() > 512 {
-            return (0, 0);
+            return;
         }
 
-        let mut mg = 0;
-        let mut eg = 0;
         let side = board.side();
         let half = side / 2;
 
         for player in [Player::White, Player::Black] {
-            let sign = if player == Player::White { 1 } else { -1 };
             let mut space_count = 0i32;
 
             let my_occ = match player {
@@ -1019,11 +1172,13 @@ impl Evaluator {
                 }
             }
 
-            mg += sign * space_count * SPACE_BONUS_MG;
-            eg += sign * space_count * SPACE_BONUS_EG;
+            sink.add(
+                EvalTerm::Space,
+                player,
+                space_count * p.space_bonus_mg,
+                space_count * p.space_bonus_eg,
+            );
         }
-
-        (mg, eg)
     }
 }
 
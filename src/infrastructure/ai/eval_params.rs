@@ -0,0 +1,326 @@
+//! Weights of the hand-crafted evaluation, loadable from TOML or JSON.
+//!
+//! A parameter file sets any subset of the weights for every board, then
+//! overrides them per dimension or per `(dimension, side)`:
+//!
+//! ```toml
+//! [params]
+//! tempo_bonus = 10
+//!
+//! [[overrides]]
+//! dimension = 3
+//! [overrides.params]
+//! king_zone_contact_mg = 40
+//!
+//! [[overrides]]
+//! dimension = 4
+//! side = 4
+//! [overrides.params]
+//! space_bonus_mg = 0
+//! ```
+//!
+//! Weights not mentioned keep their built-in values, which were tuned for 2D.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! eval_params {
+    ($($(#[$meta:meta])* $name:ident = $default:expr,)*) => {
+        /// Every tunable weight of [`Evaluator`](super::eval::Evaluator), in
+        /// centipawns unless noted.
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(default, deny_unknown_fields)]
+        pub struct EvalParams {
+            $($(#[$meta])* pub $name: i32,)*
+        }
+
+        impl Default for EvalParams {
+            fn default() -> Self {
+                Self {
+                    $($name: $default,)*
+                }
+            }
+        }
+
+        impl EvalParams {
+            /// Every parameter name, in declaration order.
+            pub const NAMES: &'static [&'static str] = &[$(stringify!($name),)*];
+
+            pub fn get(&self, name: &str) -> Option<i32> {
+                match name {
+                    $(stringify!($name) => Some(self.$name),)*
+                    _ => None,
+                }
+            }
+
+            pub fn get_mut(&mut self, name: &str) -> Option<&mut i32> {
+                match name {
+                    $(stringify!($name) => Some(&mut self.$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+eval_params! {
+    // ── Material ──
+    pawn_mg = 100,
+    pawn_eg = 150,
+    knight_mg = 320,
+    knight_eg = 300,
+    bishop_mg = 330,
+    bishop_eg = 330,
+    rook_mg = 500,
+    rook_eg = 500,
+    queen_mg = 900,
+    queen_eg = 900,
+
+    // ── Mobility, per reachable square ──
+    mobility_knight_mg = 4,
+    mobility_knight_eg = 4,
+    mobility_bishop_mg = 5,
+    mobility_bishop_eg = 5,
+    mobility_rook_mg = 2,
+    mobility_rook_eg = 4,
+    mobility_queen_mg = 1,
+    mobility_queen_eg = 2,
+
+    // ── Piece-square table, per step of distance from the center ──
+    pst_pawn_dist_penalty_mg = 2,
+    pst_pawn_dist_penalty_eg = 5,
+    pst_knight_dist_penalty_mg = 4,
+    pst_knight_dist_penalty_eg = 4,
+    pst_bishop_dist_penalty_mg = 1,
+    pst_bishop_dist_penalty_eg = 1,
+    pst_rook_dist_penalty_mg = 0,
+    pst_rook_dist_penalty_eg = 0,
+    pst_queen_dist_penalty_mg = 1,
+    pst_queen_dist_penalty_eg = 2,
+    pst_king_dist_bonus_mg = 5,
+    pst_king_dist_penalty_eg = 10,
+
+    // ── King safety: totals spread over the normalised signals ──
+    pawn_shelter_total_mg = 160,
+    pawn_shelter_total_eg = 0,
+    open_line_penalty_mg = 80,
+    open_line_penalty_eg = 10,
+    attacked_line_penalty_mg = 120,
+    attacked_line_penalty_eg = 15,
+    tropism_queen_weight = 4,
+    tropism_rook_weight = 2,
+    tropism_total_mg = 50,
+    tropism_total_eg = 10,
+    king_zone_contact_mg = 70,
+    king_zone_contact_eg = 10,
+
+    // ── Pawn structure ──
+    passed_pawn_bonus_mg = 20,
+    passed_pawn_bonus_eg = 40,
+    passed_pawn_advance_mg = 3,
+    passed_pawn_advance_eg = 6,
+    isolated_pawn_penalty_mg = 10,
+    isolated_pawn_penalty_eg = 15,
+    doubled_pawn_penalty_mg = 10,
+    doubled_pawn_penalty_eg = 15,
+    connected_pawn_bonus_mg = 5,
+    connected_pawn_bonus_eg = 8,
+
+    // ── Piece bonuses ──
+    bishop_pair_bonus_mg = 30,
+    bishop_pair_bonus_eg = 50,
+    rook_open_file_bonus_mg = 15,
+    rook_open_file_bonus_eg = 10,
+    rook_semi_open_file_bonus_mg = 8,
+    rook_semi_open_file_bonus_eg = 5,
+    castling_rights_bonus_mg = 10,
+    castling_rights_bonus_eg = 0,
+    outpost_knight_mg = 20,
+    outpost_knight_eg = 15,
+    outpost_bishop_mg = 10,
+    outpost_bishop_eg = 8,
+    rook_on_7th_mg = 20,
+    rook_on_7th_eg = 30,
+    space_bonus_mg = 2,
+    space_bonus_eg = 0,
+
+    // ── Trade bonus, per rook of non-pawn material ahead at full simplification ──
+    trade_bonus_mg = 0,
+    trade_bonus_eg = 48,
+
+    // ── Applied after tapering ──
+    tempo_bonus = 15,
+    /// Halfmove clock at which the evaluation would be damped to zero.
+    rule50_divisor = 199,
+}
+
+/// Overrides for one dimension, or one `(dimension, side)` when `side` is set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvalOverride {
+    pub dimension: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<usize>,
+    pub params: BTreeMap<String, i32>,
+}
+
+/// A parameter file: weights for every board, then per-board overrides.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvalParamSet {
+    pub params: BTreeMap<String, i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<EvalOverride>,
+}
+
+#[derive(Debug)]
+pub enum EvalParamsError {
+    Io(String, std::io::Error),
+    Parse(String, String),
+    UnknownParam(String),
+}
+
+impl fmt::Display for EvalParamsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalParamsError::Io(path, e) => write!(f, "{path}: {e}"),
+            EvalParamsError::Parse(path, e) => write!(f, "cannot parse {path}: {e}"),
+            EvalParamsError::UnknownParam(name) => {
+                write!(f, "unknown evaluation parameter '{name}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EvalParamsError {}
+
+impl EvalParamSet {
+    /// Read a parameter file: JSON if the name ends in `.json`, TOML
+    /// otherwise.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EvalParamsError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let contents =
+            fs::read_to_string(path).map_err(|e| EvalParamsError::Io(name.clone(), e))?;
+        let set: Self = if is_json(path) {
+            serde_json::from_str(&contents)
+                .map_err(|e| EvalParamsError::Parse(name, e.to_string()))?
+        } else {
+            toml::from_str(&contents).map_err(|e| EvalParamsError::Parse(name, e.to_string()))?
+        };
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Write the set in the format `load` expects for `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EvalParamsError> {
+        let path = path.as_ref();
+        let contents = if is_json(path) {
+            serde_json::to_string_pretty(self).expect("parameter sets always serialize")
+        } else {
+            toml::to_string(self).expect("parameter sets always serialize")
+        };
+        fs::write(path, contents).map_err(|e| EvalParamsError::Io(path.display().to_string(), e))
+    }
+
+    /// Every name in the set is a parameter of [`EvalParams`].
+    pub fn validate(&self) -> Result<(), EvalParamsError> {
+        let names = self
+            .params
+            .keys()
+            .chain(self.overrides.iter().flat_map(|o| o.params.keys()));
+        for name in names {
+            if EvalParams::default().get(name).is_none() {
+                return Err(EvalParamsError::UnknownParam(name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// `other` on top of `self`: its weights win and its overrides apply
+    /// after ours.
+    pub fn merge(&mut self, other: EvalParamSet) {
+        self.params.extend(other.params);
+        self.overrides.extend(other.overrides);
+    }
+
+    /// The weights for a board: built-in defaults, then `params`, then the
+    /// overrides for `dimension`, then those for `(dimension, side)`.
+    pub fn resolve(&self, dimension: usize, side: usize) -> EvalParams {
+        let mut resolved = EvalParams::default();
+        apply(&mut resolved, &self.params);
+        let matching = |side_match: fn(Option<usize>, usize) -> bool| {
+            self.overrides
+                .iter()
+                .filter(move |o| o.dimension == dimension && side_match(o.side, side))
+        };
+        for o in matching(|s, _| s.is_none()) {
+            apply(&mut resolved, &o.params);
+        }
+        for o in matching(|s, side| s == Some(side)) {
+            apply(&mut resolved, &o.params);
+        }
+        resolved
+    }
+}
+
+fn apply(params: &mut EvalParams, values: &BTreeMap<String, i32>) {
+    for (name, &value) in values {
+        if let Some(slot) = params.get_mut(name) {
+            *slot = value;
+        }
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_layers_overrides() {
+        let set: EvalParamSet = toml::from_str(
+            r#"
+            [params]
+            tempo_bonus = 10
+            pawn_mg = 90
+
+            [[overrides]]
+            dimension = 3
+            side = 6
+            [overrides.params]
+            pawn_mg = 70
+
+            [[overrides]]
+            dimension = 3
+            [overrides.params]
+            pawn_mg = 80
+            "#,
+        )
+        .unwrap();
+        set.validate().unwrap();
+
+        let two = set.resolve(2, 8);
+        assert_eq!((two.tempo_bonus, two.pawn_mg), (10, 90));
+        assert_eq!(set.resolve(3, 8).pawn_mg, 80);
+        assert_eq!(set.resolve(3, 6).pawn_mg, 70);
+        assert_eq!(set.resolve(3, 6).knight_mg, EvalParams::default().knight_mg);
+    }
+
+    #[test]
+    fn test_unknown_names_are_rejected() {
+        let mut set = EvalParamSet::default();
+        set.params.insert("pawn_value".into(), 100);
+        assert!(matches!(
+            set.validate(),
+            Err(EvalParamsError::UnknownParam(name)) if name == "pawn_value"
+        ));
+    }
+}
@@ -0,0 +1,226 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::domain::board::Board;
+use crate::domain::models::Player;
+use crate::infrastructure::ai::limits::SearchLimits;
+use crate::infrastructure::ai::minimax::MinimaxBot;
+use crate::infrastructure::ai::observer::{SearchEvent, SearchInfo, SearchObserver};
+use crate::infrastructure::ai::result::SearchResult;
+
+/// A search running on a background thread.
+///
+/// Clones refer to the same search, so one clone can be kept to stop it while
+/// another is awaited. The result can be taken from every clone.
+#[derive(Clone)]
+pub struct SearchHandle {
+    shared: Arc<Shared>,
+    stop_flag: Arc<AtomicBool>,
+    ponder: Arc<Ponder>,
+}
+
+/// Whether a search is pondering, shared between a bot and its clones.
+#[derive(Default)]
+pub(crate) struct Ponder {
+    active: AtomicBool,
+    hit_at: Mutex<Option<Instant>>,
+}
+
+impl Ponder {
+    pub(crate) fn start(&self, active: bool) {
+        *self.hit_at.lock().unwrap() = None;
+        self.active.store(active, Ordering::Relaxed);
+    }
+
+    fn hit(&self) {
+        *self.hit_at.lock().unwrap() = Some(Instant::now());
+        self.active.store(false, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Time counted against the search's limits: none while pondering, and
+    /// since the ponderhit if there was one.
+    pub(crate) fn elapsed(&self, start_time: Instant) -> Option<Duration> {
+        if self.is_active() {
+            return None;
+        }
+        let since = self.hit_at.lock().unwrap().unwrap_or(start_time);
+        Some(since.elapsed())
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    finished: Condvar,
+    /// Set by `stop`, unlike the bot's stop flag which time limits also raise.
+    cancelled: AtomicBool,
+}
+
+#[derive(Default)]
+struct State {
+    best: Option<SearchInfo>,
+    /// `Some` once the search thread is done; the inner `None` means there
+    /// was no legal move to search, or that the search panicked.
+    result: Option<Option<SearchResult>>,
+    wakers: Vec<Waker>,
+}
+
+/// Records the best line of each iteration, then forwards the event.
+struct TrackBest {
+    shared: Arc<Shared>,
+    inner: Option<Arc<dyn SearchObserver>>,
+}
+
+impl SearchObserver for TrackBest {
+    fn on_event(&self, event: &SearchEvent) {
+        if let SearchEvent::Iteration(info) = event
+            && info.multipv == 1
+        {
+            self.shared.state.lock().unwrap().best = Some(info.clone());
+        }
+        if let Some(inner) = &self.inner {
+            inner.on_event(event);
+        }
+    }
+}
+
+/// Hands the search thread's result to the handle when dropped, so that
+/// waiters are released with `None` if the search panics.
+struct Publish {
+    shared: Arc<Shared>,
+    result: Option<SearchResult>,
+}
+
+impl Drop for Publish {
+    fn drop(&mut self) {
+        let mut state = self
+            .shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        state.result = Some(self.result.take());
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+        self.shared.finished.notify_all();
+    }
+}
+
+impl MinimaxBot {
+    /// Start searching on a background thread and return at once.
+    ///
+    /// The search runs on a clone of this bot, sharing its transposition
+    /// table, counters and stop flag, so run only one search per bot at a
+    /// time. The bot's observer still receives events.
+    pub fn start_search(
+        &self,
+        board: &Board,
+        player: Player,
+        limits: SearchLimits,
+    ) -> SearchHandle {
+        let mut bot = self.clone();
+        // Reset here rather than in the search thread so that a `stop` or
+        // `ponderhit` arriving before the thread gets going is not lost.
+        bot.reset_controls(&limits);
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::default()),
+            finished: Condvar::new(),
+            cancelled: AtomicBool::new(false),
+        });
+        bot.set_observer(Some(Arc::new(TrackBest {
+            shared: shared.clone(),
+            inner: self.observer(),
+        })));
+
+        let board = board.clone();
+        let thread_shared = shared.clone();
+        thread::spawn(move || {
+            let mut publish = Publish {
+                shared: thread_shared,
+                result: None,
+            };
+            publish.result = bot.search_started(&board, player, &limits);
+        });
+
+        SearchHandle {
+            shared,
+            stop_flag: self.stop_flag(),
+            ponder: self.ponder(),
+        }
+    }
+}
+
+impl SearchHandle {
+    /// Ask the search to finish as soon as possible. The result is still
+    /// delivered, built from the last completed iteration.
+    pub fn stop(&self) {
+        self.shared.cancelled.store(true, Ordering::Relaxed);
+        self.ponder.start(false);
+        self.stop_flag.store(true, Ordering::Relaxed);
+    }
+
+    /// The opponent played the move this search was pondering on: keep
+    /// searching, now against the time limits, which start counting here.
+    pub fn ponderhit(&self) {
+        self.ponder.hit();
+    }
+
+    /// Whether the search is still pondering, i.e. was started with
+    /// `SearchLimits::ponder` and has seen neither `ponderhit` nor `stop`.
+    /// A ponder search may finish early (say on a depth limit), but its move
+    /// should only be played after the ponderhit.
+    pub fn is_pondering(&self) -> bool {
+        self.ponder.is_active()
+    }
+
+    /// Whether `stop` was called on this search or one of its clones.
+    pub fn was_stopped(&self) -> bool {
+        self.shared.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.shared.state.lock().unwrap().result.is_some()
+    }
+
+    /// Best line of the deepest iteration completed so far.
+    pub fn best_so_far(&self) -> Option<SearchInfo> {
+        self.shared.state.lock().unwrap().best.clone()
+    }
+
+    /// Block until the search ends. `None` if there was no legal move or
+    /// the search panicked.
+    pub fn wait(&self) -> Option<SearchResult> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.result.is_none() {
+            state = self.shared.finished.wait(state).unwrap();
+        }
+        state.result.clone().flatten()
+    }
+}
+
+/// Await the result from async code without blocking the executor.
+impl Future for SearchHandle {
+    type Output = Option<SearchResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.state.lock().unwrap();
+        match &state.result {
+            Some(result) => Poll::Ready(result.clone()),
+            None => {
+                if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    state.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
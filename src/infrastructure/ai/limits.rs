@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use crate::domain::models::Move;
+
+/// Remaining time on the side to move's clock.
+#[derive(Clone, Copy, Debug)]
+pub struct Clock {
+    pub remaining: Duration,
+    pub increment: Duration,
+    /// Moves until the next time control; `None` for sudden death.
+    pub moves_to_go: Option<u32>,
+}
+
+impl Clock {
+    /// Moves assumed left in the game when there is no `moves_to_go`.
+    const DEFAULT_MOVES_TO_GO: u32 = 30;
+
+    /// Time to spend on this move: a share of the remaining time plus most of
+    /// the increment, never more than a third of the clock.
+    pub fn allocate(&self) -> Duration {
+        let moves_to_go = self.moves_to_go.unwrap_or(Self::DEFAULT_MOVES_TO_GO).max(1);
+        let budget = self.remaining / moves_to_go + self.increment * 3 / 4;
+        budget.min(self.remaining / 3).max(Duration::from_millis(1))
+    }
+}
+
+/// What a search should do and when it should stop. Unset fields fall back
+/// to the bot's configured depth and time limit.
+#[derive(Clone, Debug, Default)]
+pub struct SearchLimits {
+    pub depth: Option<usize>,
+    /// Exact time budget. Takes precedence over `clock`.
+    pub movetime: Option<Duration>,
+    pub nodes: Option<u64>,
+    /// Stop once a mate in at most this many moves is found.
+    pub mate: Option<u32>,
+    /// Ignore depth and time; run until the stop flag is raised.
+    pub infinite: bool,
+    /// Only consider these root moves. Empty means all legal moves.
+    pub search_moves: Vec<Move>,
+    /// Budget the move from the game clock, stopping early when the best
+    /// move is stable.
+    pub clock: Option<Clock>,
+    /// Number of root lines to score exactly (MultiPV); 0 is treated as 1.
+    pub multi_pv: usize,
+    /// Search on the opponent's time: time limits are suspended until
+    /// [`SearchHandle::ponderhit`](super::SearchHandle::ponderhit), and
+    /// counted from then on.
+    pub ponder: bool,
+}
+
+impl SearchLimits {
+    pub fn depth(depth: usize) -> Self {
+        Self {
+            depth: Some(depth),
+            ..Self::default()
+        }
+    }
+
+    pub fn movetime(movetime: Duration) -> Self {
+        Self {
+            movetime: Some(movetime),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_allocation() {
+        let clock = Clock {
+            remaining: Duration::from_secs(60),
+            increment: Duration::from_secs(1),
+            moves_to_go: None,
+        };
+        assert_eq!(clock.allocate(), Duration::from_millis(2750));
+
+        let last_move = Clock {
+            remaining: Duration::from_secs(3),
+            increment: Duration::ZERO,
+            moves_to_go: Some(1),
+        };
+        assert_eq!(last_move.allocate(), Duration::from_secs(1));
+    }
+}
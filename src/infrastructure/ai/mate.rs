@@ -0,0 +1,338 @@
+use std::fmt;
+use std::mem::size_of;
+
+use crate::config::AppConfig;
+use crate::domain::board::{Board, UnmakeInfo, notation};
+use crate::domain::models::{Move, Player};
+use crate::domain::rules::Rules;
+
+/// Proof or disproof number of a solved node.
+const INFINITY: u32 = u32::MAX;
+
+/// Initial proof number of an attacking move that does not give check.
+/// Checks are tried first; mates mostly run through them.
+const QUIET_PROOF: u32 = 3;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// The attacker is to move and needs one move that mates.
+    Or,
+    /// The defender is to move and every reply must lose.
+    And,
+}
+
+struct Node {
+    mv: Option<Move>,
+    parent: u32,
+    /// Children are contiguous in the arena; `None` until expanded.
+    children: Option<(u32, u32)>,
+    kind: Kind,
+    /// Plies left for the side to move before the mate must be delivered.
+    plies: u32,
+    proof: u32,
+    disproof: u32,
+}
+
+/// A forced mate: an attacking move and the answer to every defence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolutionNode {
+    pub attack: Move,
+    /// Every legal reply with the continuation that mates after it; empty
+    /// when `attack` mates at once.
+    pub defences: Vec<(Move, SolutionNode)>,
+}
+
+impl SolutionNode {
+    /// Moves to mate against the best defence.
+    pub fn moves(&self) -> u32 {
+        1 + self
+            .defences
+            .iter()
+            .map(|(_, next)| next.moves())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The line where the defender resists longest.
+    pub fn main_line(&self) -> Vec<Move> {
+        let mut line = vec![self.attack.clone()];
+        if let Some((defence, next)) = self.defences.iter().max_by_key(|(_, next)| next.moves()) {
+            line.push(defence.clone());
+            line.extend(next.main_line());
+        }
+        line
+    }
+
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let mate = if self.defences.is_empty() { "#" } else { "" };
+        writeln!(
+            f,
+            "{:indent$}{}{mate}",
+            "",
+            notation::format_move(&self.attack)
+        )?;
+        for (defence, next) in &self.defences {
+            writeln!(f, "{:indent$}  {}", "", notation::format_move(defence))?;
+            next.write_indented(f, indent + 4)?;
+        }
+        Ok(())
+    }
+}
+
+/// The solution tree, attacking moves on their own lines with the defences
+/// indented below them.
+impl fmt::Display for SolutionNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+/// Outcome of [`MateSolver::solve`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MateResult {
+    /// A shortest forced mate, in `solution.moves()` moves.
+    Mate(SolutionNode),
+    /// Proven: no mate within the requested number of moves.
+    NoMate,
+    /// The node budget ran out first.
+    Unknown,
+}
+
+/// Proof-number search for forced mates.
+///
+/// Unlike the alpha-beta search, nothing is pruned heuristically: a
+/// `Mate` is a proof over every defence, and `NoMate` a proof that none
+/// exists within the bound. Mate lengths are tried from 1 upwards, so the
+/// solution found is a shortest one.
+pub struct MateSolver {
+    max_nodes: usize,
+    nodes: u64,
+}
+
+impl MateSolver {
+    /// A solver whose search tree fits in `compute.memory`.
+    pub fn new(config: &AppConfig) -> Self {
+        Self::with_node_limit(config.compute.memory * 1024 * 1024 / size_of::<Node>())
+    }
+
+    pub fn with_node_limit(max_nodes: usize) -> Self {
+        Self {
+            max_nodes: max_nodes.max(1),
+            nodes: 0,
+        }
+    }
+
+    /// Tree nodes created by the last `solve`, over all mate lengths tried.
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Find a mate in at most `moves` moves for `attacker`, who is to move.
+    pub fn solve(&mut self, board: &Board, attacker: Player, moves: u32) -> MateResult {
+        self.nodes = 0;
+        for n in 1..=moves {
+            match self.prove(board, attacker, n) {
+                MateResult::NoMate => continue,
+                found => return found,
+            }
+        }
+        MateResult::NoMate
+    }
+
+    /// Proof-number search for a mate in at most `moves`.
+    fn prove(&mut self, board: &Board, attacker: Player, moves: u32) -> MateResult {
+        let mut tree = Tree {
+            nodes: vec![Node {
+                mv: None,
+                parent: 0,
+                children: None,
+                kind: Kind::Or,
+                plies: 2 * moves - 1,
+                proof: 1,
+                disproof: 1,
+            }],
+            board: board.clone(),
+            attacker,
+        };
+
+        while tree.nodes[0].proof != 0 && tree.nodes[0].disproof != 0 {
+            if tree.nodes.len() >= self.max_nodes {
+                self.nodes += tree.nodes.len() as u64;
+                return MateResult::Unknown;
+            }
+            let (leaf, undo) = tree.most_proving();
+            tree.expand(leaf);
+            tree.unwind(undo);
+            tree.update_ancestors(leaf);
+        }
+
+        self.nodes += tree.nodes.len() as u64;
+        if tree.nodes[0].proof == 0 {
+            MateResult::Mate(tree.solution(0))
+        } else {
+            MateResult::NoMate
+        }
+    }
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+    /// Kept at the root position between iterations.
+    board: Board,
+    attacker: Player,
+}
+
+impl Tree {
+    fn children(&self, node: usize) -> std::ops::Range<usize> {
+        match self.nodes[node].children {
+            Some((first, count)) => first as usize..(first + count) as usize,
+            None => 0..0,
+        }
+    }
+
+    fn to_move(&self, node: usize) -> Player {
+        match self.nodes[node].kind {
+            Kind::Or => self.attacker,
+            Kind::And => self.attacker.opponent(),
+        }
+    }
+
+    /// Descend to the most-proving leaf, playing the moves on the board.
+    /// Returns the leaf and the moves to take back.
+    fn most_proving(&mut self) -> (usize, Vec<(Move, UnmakeInfo)>) {
+        let mut node = 0;
+        let mut undo = Vec::new();
+        while self.nodes[node].children.is_some() {
+            let kind = self.nodes[node].kind;
+            node = self
+                .children(node)
+                .min_by_key(|&c| match kind {
+                    Kind::Or => self.nodes[c].proof,
+                    Kind::And => self.nodes[c].disproof,
+                })
+                .expect("expanded node without children");
+            let mv = self.nodes[node].mv.clone().unwrap();
+            let info = self.board.apply_move(&mv).expect("tree move is legal");
+            undo.push((mv, info));
+        }
+        (node, undo)
+    }
+
+    fn unwind(&mut self, undo: Vec<(Move, UnmakeInfo)>) {
+        for (mv, info) in undo.into_iter().rev() {
+            self.board.unmake_move(&mv, info);
+        }
+    }
+
+    /// Generate the children of a leaf, or settle it if the game is over or
+    /// the ply budget is spent.
+    fn expand(&mut self, leaf: usize) {
+        let player = self.to_move(leaf);
+        let moves = Rules::generate_legal_moves(&mut self.board, player);
+        let (kind, plies) = (self.nodes[leaf].kind, self.nodes[leaf].plies);
+
+        let mated =
+            moves.is_empty() && kind == Kind::And && Rules::is_in_check(&self.board, player);
+        if mated {
+            self.settle(leaf, true);
+            return;
+        }
+        // Stalemate, the attacker out of moves, or no plies left to mate in.
+        if moves.is_empty() || plies == 0 {
+            self.settle(leaf, false);
+            return;
+        }
+
+        let first = self.nodes.len() as u32;
+        let child_kind = match kind {
+            Kind::Or => Kind::And,
+            Kind::And => Kind::Or,
+        };
+        for mv in moves {
+            let proof = match kind {
+                Kind::Or => {
+                    let info = self.board.apply_move(&mv).expect("legal move");
+                    let check = Rules::is_in_check(&self.board, player.opponent());
+                    self.board.unmake_move(&mv, info);
+                    if check { 1 } else { QUIET_PROOF }
+                }
+                Kind::And => 1,
+            };
+            self.nodes.push(Node {
+                mv: Some(mv),
+                parent: leaf as u32,
+                children: None,
+                kind: child_kind,
+                plies: plies - 1,
+                proof,
+                disproof: 1,
+            });
+        }
+        self.nodes[leaf].children = Some((first, self.nodes.len() as u32 - first));
+        self.recompute(leaf);
+    }
+
+    fn settle(&mut self, node: usize, proven: bool) {
+        let node = &mut self.nodes[node];
+        (node.proof, node.disproof) = if proven { (0, INFINITY) } else { (INFINITY, 0) };
+    }
+
+    /// Proof and disproof numbers of an expanded node from its children.
+    fn recompute(&mut self, node: usize) {
+        let children = self.children(node);
+        let proofs = self.nodes[children.clone()].iter().map(|n| n.proof);
+        let disproofs = self.nodes[children].iter().map(|n| n.disproof);
+        let (proof, disproof) = match self.nodes[node].kind {
+            Kind::Or => (
+                proofs.min().unwrap(),
+                disproofs.fold(0, u32::saturating_add),
+            ),
+            Kind::And => (
+                proofs.fold(0, u32::saturating_add),
+                disproofs.min().unwrap(),
+            ),
+        };
+        self.nodes[node].proof = proof;
+        self.nodes[node].disproof = disproof;
+    }
+
+    fn update_ancestors(&mut self, mut node: usize) {
+        while node != 0 {
+            node = self.nodes[node].parent as usize;
+            self.recompute(node);
+        }
+    }
+
+    /// The proof below a proven OR node.
+    fn solution(&self, node: usize) -> SolutionNode {
+        // Of several mating moves, prefer the quickest.
+        let attack = self
+            .children(node)
+            .filter(|&c| self.nodes[c].proof == 0)
+            .min_by_key(|&c| self.proof_depth(c))
+            .expect("proven node without a proven child");
+        SolutionNode {
+            attack: self.nodes[attack].mv.clone().unwrap(),
+            defences: self
+                .children(attack)
+                .map(|reply| (self.nodes[reply].mv.clone().unwrap(), self.solution(reply)))
+                .collect(),
+        }
+    }
+
+    /// Plies to mate below a proven AND node against the best defence.
+    fn proof_depth(&self, node: usize) -> u32 {
+        self.children(node)
+            .map(|reply| {
+                let attack = self
+                    .children(reply)
+                    .filter(|&c| self.nodes[c].proof == 0)
+                    .map(|c| self.proof_depth(c))
+                    .min()
+                    .unwrap_or(0);
+                2 + attack
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
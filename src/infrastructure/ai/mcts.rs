@@ -0,0 +1,541 @@
+use std::mem::size_of;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::config::AppConfig;
+use crate::domain::board::Board;
+use crate::domain::models::{Move, PieceType, Player};
+use crate::domain::rules::Rules;
+use crate::domain::services::PlayerStrategy;
+use crate::infrastructure::ai::eval::Evaluator;
+use crate::infrastructure::ai::eval_params::{EvalParamSet, EvalParams};
+use crate::infrastructure::ai::limits::SearchLimits;
+use crate::infrastructure::ai::observer::{SearchEvent, SearchInfo, SearchObserver};
+use crate::infrastructure::ai::result::SearchResult;
+use crate::infrastructure::ai::search_core::{VAL_QUEEN, get_piece_value, q_search};
+
+/// Centipawn score mapped to a value of tanh(1) ≈ 0.76.
+const VALUE_SCALE: f32 = 400.0;
+
+/// Unvisited children start this far below their parent's value, so the
+/// search widens only when the explored moves disappoint.
+const FPU_REDUCTION: f32 = 0.2;
+
+/// Leaves selected per thread before evaluating a batch in parallel.
+const LEAVES_PER_THREAD: usize = 4;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, PartialEq)]
+enum NodeState {
+    Unexpanded,
+    Expanded {
+        first_child: u32,
+        count: u32,
+    },
+    /// Game over: the exact value for the player who moved into the node.
+    Terminal(f32),
+}
+
+struct Node {
+    mv: Option<Move>,
+    prior: f32,
+    visits: u32,
+    /// Sum of backed-up values, from the perspective of the player who made
+    /// `mv`, in [-1, 1] per visit.
+    value: f32,
+    /// Selections of this node still waiting for their evaluation. Counted
+    /// as losses so parallel selections spread over different leaves.
+    virtual_loss: u32,
+    state: NodeState,
+}
+
+impl Node {
+    fn new(mv: Option<Move>, prior: f32) -> Self {
+        Self {
+            mv,
+            prior,
+            visits: 0,
+            value: 0.0,
+            virtual_loss: 0,
+            state: NodeState::Unexpanded,
+        }
+    }
+
+    /// Mean value with pending selections counted as losses.
+    fn q(&self) -> Option<f32> {
+        let n = self.visits + self.virtual_loss;
+        (n > 0).then(|| (self.value - self.virtual_loss as f32) / n as f32)
+    }
+}
+
+/// A leaf picked for evaluation: the path from the root and the position at
+/// its end.
+struct Leaf {
+    path: Vec<u32>,
+    board: Board,
+    player: Player,
+}
+
+/// Result of evaluating a leaf, computed in parallel and applied to the tree
+/// afterwards.
+struct Expansion {
+    /// Legal moves with their priors; empty when the game is over.
+    moves: Vec<(Move, f32)>,
+    /// Value for the player to move at the leaf.
+    value: f32,
+}
+
+/// Monte Carlo Tree Search with PUCT selection.
+///
+/// An alternative to [`MinimaxBot`](super::MinimaxBot) for boards whose
+/// branching factor defeats alpha-beta (4D and up). Leaves are valued by a
+/// quiescence search (or the static evaluation) instead of random playouts,
+/// and move priors come from a cheap capture/promotion heuristic. Batches of
+/// leaves are selected with virtual loss and evaluated in parallel on rayon.
+///
+/// The tree is rebuilt for every search and capped by `compute.memory`.
+#[derive(Clone)]
+pub struct MctsBot {
+    time_limit: Duration,
+    num_threads: usize,
+    exploration: f32,
+    quiescence: bool,
+    max_nodes: usize,
+    stop_flag: Arc<AtomicBool>,
+    observer: Option<Arc<dyn SearchObserver>>,
+    /// Resolved against each searched board's geometry.
+    eval_params: EvalParamSet,
+}
+
+impl MctsBot {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            time_limit: Duration::from_secs(config.compute.minutes * 60),
+            num_threads: config.compute.concurrency.max(1),
+            exploration: config.mcts.exploration,
+            quiescence: config.mcts.quiescence,
+            max_nodes: config.compute.memory * 1024 * 1024 / size_of::<Node>(),
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            observer: None,
+            eval_params: config.eval.set.clone(),
+        }
+    }
+
+    /// Receive progress and the final line.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn SearchObserver>>) {
+        self.observer = observer;
+    }
+
+    /// Raise to end the current search early; it still returns its best move.
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop_flag.clone()
+    }
+
+    /// Search `board` for `player`. Returns `None` if there is no legal move.
+    ///
+    /// Honours `movetime`, `clock`, `nodes` (counted in playouts), `infinite`
+    /// and `search_moves`; `depth` has no meaning here and is ignored.
+    pub fn search(
+        &mut self,
+        board: &Board,
+        player: Player,
+        limits: &SearchLimits,
+    ) -> Option<SearchResult> {
+        let start_time = Instant::now();
+        self.stop_flag.store(false, Ordering::Relaxed);
+        let time = if limits.infinite {
+            None
+        } else {
+            limits
+                .movetime
+                .or_else(|| limits.clock.map(|clock| clock.allocate()))
+                .or((limits.nodes.is_none()).then_some(self.time_limit))
+        };
+
+        let mut search = Search {
+            tree: vec![Node::new(None, 1.0)],
+            exploration: self.exploration,
+            seldepth: 0,
+            terminal_visits: 0,
+        };
+        let q_nodes = Arc::new(AtomicUsize::new(0));
+        let params = self.eval_params.resolve(board.dimension(), board.side());
+
+        // The root is expanded up front so `search_moves` can prune it. It
+        // is never scored as a draw by repetition or the fifty-move rule:
+        // the game has not ended, so there is a move to find.
+        let root = evaluate_position(
+            &mut board.clone(),
+            player,
+            false,
+            &params,
+            &q_nodes,
+            &self.stop_flag,
+        );
+        if root.moves.is_empty() {
+            return None;
+        }
+        let moves = root
+            .moves
+            .into_iter()
+            .filter(|(mv, _)| limits.search_moves.is_empty() || limits.search_moves.contains(mv))
+            .collect::<Vec<_>>();
+        if moves.is_empty() {
+            return None;
+        }
+        search.expand(0, moves);
+
+        let batch = self.num_threads * LEAVES_PER_THREAD;
+        let deadline = time.map(|t| start_time + t);
+        let mut playouts = 0u64;
+        let mut last_progress = start_time;
+        loop {
+            let out_of_time = deadline.is_some_and(|d| Instant::now() >= d);
+            let out_of_nodes = limits.nodes.is_some_and(|n| playouts >= n);
+            let out_of_memory = search.tree.len() >= self.max_nodes;
+            if out_of_time
+                || out_of_nodes
+                || out_of_memory
+                || self.stop_flag.load(Ordering::Relaxed)
+            {
+                break;
+            }
+
+            let wanted = limits
+                .nodes
+                .map_or(batch, |n| batch.min((n - playouts) as usize));
+            let terminal_visits = search.terminal_visits;
+            let leaves: Vec<Leaf> = (0..wanted)
+                .filter_map(|_| search.select(board, player))
+                .collect();
+            playouts += search.terminal_visits - terminal_visits;
+            let quiescence = self.quiescence;
+            let stop_flag = &self.stop_flag;
+            // A batch can take long on big boards, so the deadline is also
+            // checked per leaf; raising the stop flag cuts short the
+            // quiescence searches still running.
+            let expansions: Vec<(Leaf, Expansion, bool)> = leaves
+                .into_par_iter()
+                .map(|mut leaf| {
+                    if deadline.is_some_and(|d| Instant::now() >= d) {
+                        stop_flag.store(true, Ordering::Relaxed);
+                    }
+                    let expansion = evaluate(
+                        &mut leaf.board,
+                        leaf.player,
+                        quiescence,
+                        &params,
+                        &q_nodes,
+                        stop_flag,
+                    );
+                    let stopped = stop_flag.load(Ordering::Relaxed);
+                    (leaf, expansion, stopped)
+                })
+                .collect();
+
+            // A quiescence search cut short by the stop flag returns garbage.
+            // One that ended with the flag still down was not cut short.
+            for (leaf, expansion, discard) in expansions {
+                search.backup(&leaf.path, expansion, discard);
+                playouts += u64::from(!discard);
+            }
+
+            if let Some(observer) = &self.observer
+                && last_progress.elapsed() >= PROGRESS_INTERVAL
+            {
+                last_progress = Instant::now();
+                let time = start_time.elapsed();
+                observer.on_event(&SearchEvent::Progress {
+                    nodes: playouts,
+                    nps: nps(playouts, time),
+                    time,
+                });
+            }
+        }
+
+        let result = search.result(playouts, start_time.elapsed());
+        if let Some(observer) = &self.observer {
+            observer.on_event(&SearchEvent::Iteration(result.lines[0].clone()));
+        }
+        Some(result)
+    }
+}
+
+impl PlayerStrategy for MctsBot {
+    fn get_move(&mut self, board: &Board, player: Player) -> Option<Move> {
+        self.search(board, player, &SearchLimits::default())
+            .map(|result| result.best_move)
+    }
+}
+
+struct Search {
+    /// Arena of nodes; the children of a node are contiguous.
+    tree: Vec<Node>,
+    exploration: f32,
+    seldepth: usize,
+    /// Selections that ended in a finished game and needed no evaluation.
+    terminal_visits: u64,
+}
+
+impl Search {
+    fn expand(&mut self, node: usize, moves: Vec<(Move, f32)>) {
+        let first_child = self.tree.len() as u32;
+        let count = moves.len() as u32;
+        self.tree.extend(
+            moves
+                .into_iter()
+                .map(|(mv, prior)| Node::new(Some(mv), prior)),
+        );
+        self.tree[node].state = NodeState::Expanded { first_child, count };
+    }
+
+    fn children(&self, node: usize) -> std::ops::Range<usize> {
+        match self.tree[node].state {
+            NodeState::Expanded { first_child, count } => {
+                first_child as usize..(first_child + count) as usize
+            }
+            _ => 0..0,
+        }
+    }
+
+    /// Walk down by PUCT from the root, adding virtual loss along the way.
+    /// `None` when the walk ends in a finished game, which is backed up at
+    /// once.
+    fn select(&mut self, root_board: &Board, root_player: Player) -> Option<Leaf> {
+        let mut board = root_board.clone();
+        let mut player = root_player;
+        let mut path = vec![0u32];
+        let mut node = 0usize;
+        loop {
+            self.tree[node].virtual_loss += 1;
+            match self.tree[node].state {
+                NodeState::Unexpanded => break,
+                NodeState::Terminal(value) => {
+                    self.seldepth = self.seldepth.max(path.len() - 1);
+                    self.terminal_visits += 1;
+                    self.update(&path, value);
+                    return None;
+                }
+                NodeState::Expanded { .. } => {
+                    node = self.best_child(node);
+                    let mv = self.tree[node].mv.as_ref().expect("child without a move");
+                    board.apply_move(mv).expect("tree move is legal");
+                    player = player.opponent();
+                    path.push(node as u32);
+                }
+            }
+        }
+        self.seldepth = self.seldepth.max(path.len() - 1);
+        Some(Leaf {
+            path,
+            board,
+            player,
+        })
+    }
+
+    fn best_child(&self, node: usize) -> usize {
+        let parent = &self.tree[node];
+        let sqrt_n = ((parent.visits + parent.virtual_loss).max(1) as f32).sqrt();
+        // The parent's value is for the opponent of the player choosing here.
+        let fpu = -parent.q().unwrap_or(0.0) - FPU_REDUCTION;
+
+        let score = |child: &Node| {
+            let n = child.visits + child.virtual_loss;
+            let u = self.exploration * child.prior * sqrt_n / (1 + n) as f32;
+            child.q().unwrap_or(fpu) + u
+        };
+        self.children(node)
+            .max_by(|&a, &b| score(&self.tree[a]).total_cmp(&score(&self.tree[b])))
+            .expect("expanded node without children")
+    }
+
+    /// Attach the evaluation of a leaf and back its value up the path.
+    /// With `discard`, only the virtual loss is taken back.
+    fn backup(&mut self, path: &[u32], expansion: Expansion, discard: bool) {
+        if discard {
+            for &node in path {
+                self.tree[node as usize].virtual_loss -= 1;
+            }
+            return;
+        }
+
+        let leaf = *path.last().unwrap() as usize;
+        let value = -expansion.value;
+        // Two selections in one batch may reach the same leaf.
+        if self.tree[leaf].state == NodeState::Unexpanded {
+            if expansion.moves.is_empty() {
+                self.tree[leaf].state = NodeState::Terminal(value);
+            } else {
+                self.expand(leaf, expansion.moves);
+            }
+        }
+        self.update(path, value);
+    }
+
+    /// Add `value`, for the player who moved into the leaf, to every node on
+    /// the path, flipping sides at each step.
+    fn update(&mut self, path: &[u32], mut value: f32) {
+        for &node in path.iter().rev() {
+            let node = &mut self.tree[node as usize];
+            node.virtual_loss -= 1;
+            node.visits += 1;
+            node.value += value;
+            value = -value;
+        }
+    }
+
+    fn most_visited(&self, node: usize) -> Option<usize> {
+        self.children(node).max_by_key(|&c| self.tree[c].visits)
+    }
+
+    fn result(&self, playouts: u64, time: Duration) -> SearchResult {
+        let mut pv = Vec::new();
+        let mut node = 0;
+        while let Some(child) = self.most_visited(node) {
+            if self.tree[child].visits == 0 {
+                break;
+            }
+            pv.push(self.tree[child].mv.clone().unwrap());
+            node = child;
+        }
+        // Even an unvisited root move beats no move at all.
+        if pv.is_empty() {
+            let first = self.children(0).start;
+            pv.push(self.tree[first].mv.clone().unwrap());
+        }
+
+        let best = self.most_visited(0).unwrap();
+        let q = self.tree[best].q().unwrap_or(0.0);
+        let score = (q.clamp(-0.999, 0.999).atanh() * VALUE_SCALE) as i32;
+        let info = SearchInfo {
+            depth: pv.len(),
+            seldepth: self.seldepth,
+            multipv: 1,
+            score,
+            pv: pv.clone(),
+            nodes: playouts,
+            nps: nps(playouts, time),
+            hashfull: 0,
+            time,
+        };
+        SearchResult {
+            best_move: pv[0].clone(),
+            score,
+            depth: pv.len(),
+            seldepth: self.seldepth,
+            nodes: playouts,
+            pv,
+            mate: None,
+            time,
+            lines: vec![info],
+            book: false,
+        }
+    }
+}
+
+/// Legal moves with priors, and a value for `player`, who is to move. A
+/// leaf repeating an earlier position or reaching the fifty-move limit is
+/// a draw.
+fn evaluate(
+    board: &mut Board,
+    player: Player,
+    quiescence: bool,
+    params: &EvalParams,
+    nodes: &Arc<AtomicUsize>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Expansion {
+    if board.is_repetition() || board.state.halfmove_clock >= 100 {
+        return Expansion {
+            moves: Vec::new(),
+            value: 0.0,
+        };
+    }
+    evaluate_position(board, player, quiescence, params, nodes, stop_flag)
+}
+
+/// [`evaluate`] without the repetition and fifty-move draws.
+fn evaluate_position(
+    board: &mut Board,
+    player: Player,
+    quiescence: bool,
+    params: &EvalParams,
+    nodes: &Arc<AtomicUsize>,
+    stop_flag: &Arc<AtomicBool>,
+) -> Expansion {
+    let moves = Rules::generate_legal_moves(board, player);
+    if moves.is_empty() {
+        let value = if Rules::is_in_check(board, player) {
+            -1.0
+        } else {
+            0.0
+        };
+        return Expansion {
+            moves: Vec::new(),
+            value,
+        };
+    }
+
+    let score = if quiescence {
+        q_search(
+            board,
+            -i32::MAX,
+            i32::MAX,
+            player,
+            0,
+            params,
+            nodes,
+            stop_flag,
+            None,
+        )
+    } else {
+        let score = Evaluator::evaluate(board, params);
+        if player == Player::Black {
+            -score
+        } else {
+            score
+        }
+    };
+
+    Expansion {
+        moves: priors(board, moves.into_iter().collect()),
+        value: (score as f32 / VALUE_SCALE).tanh(),
+    }
+}
+
+/// Softmax over a capture and promotion heuristic: winning captures and
+/// queen promotions first, quiet moves evenly.
+fn priors(board: &Board, moves: Vec<Move>) -> Vec<(Move, f32)> {
+    let logit = |mv: &Move| {
+        let value_at = |coords: &[u8]| {
+            board
+                .coords_to_index(coords)
+                .map_or(0, |idx| get_piece_value(board, idx))
+        };
+        let victim = value_at(&mv.to.values);
+        let mut logit = 0.0;
+        if victim > 0 {
+            let attacker = value_at(&mv.from.values).min(VAL_QUEEN);
+            logit += 1.0 + (victim - attacker / 10) as f32 / 300.0;
+        }
+        if mv.promotion == Some(PieceType::Queen) {
+            logit += 2.0;
+        }
+        logit
+    };
+
+    let weights: Vec<f32> = moves.iter().map(|mv| logit(mv).exp()).collect();
+    let total: f32 = weights.iter().sum();
+    moves
+        .into_iter()
+        .zip(weights)
+        .map(|(mv, w)| (mv, w / total))
+        .collect()
+}
+
+fn nps(nodes: u64, time: Duration) -> u64 {
+    (nodes as f64 / time.as_secs_f64().max(0.001)) as u64
+}
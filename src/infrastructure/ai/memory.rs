@@ -0,0 +1,217 @@
+use std::fmt;
+use std::mem::size_of;
+
+use smallvec::SmallVec;
+
+use crate::domain::board::BitBoardLarge;
+use crate::domain::rules::Rules;
+use crate::infrastructure::ai::minimax::search_tables_bytes;
+use crate::infrastructure::ai::transposition::LockFreeTT;
+
+const MB: usize = 1024 * 1024;
+
+/// The transposition table never shrinks below this.
+const MIN_TT_MB: usize = 1;
+
+/// Sizes of the hashed per-thread history tables, as powers of two.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableSizes {
+    /// from × to style tables: history, continuation and low-ply history.
+    pub pair_bits: u32,
+    /// Pawn-structure × destination history.
+    pub pawn_bits: u32,
+}
+
+/// Table sizes tried in order until the search fits its budget. The first
+/// keeps the from × to tables of 2D and 3D side-8 boards exactly indexed,
+/// and pawn history only on 2D: 3D side-8 needs 22 bits and is hashed.
+const TABLE_STEPS: [TableSizes; 4] = [
+    TableSizes {
+        pair_bits: 18,
+        pawn_bits: 20,
+    },
+    TableSizes {
+        pair_bits: 16,
+        pawn_bits: 18,
+    },
+    TableSizes {
+        pair_bits: 14,
+        pawn_bits: 16,
+    },
+    TableSizes {
+        pair_bits: 12,
+        pawn_bits: 14,
+    },
+];
+
+/// Projected memory use of a search, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    /// Board geometry cache: coordinates, step-validity masks, leaper targets.
+    pub geometry: usize,
+    pub zobrist: usize,
+    pub transposition_table: usize,
+    /// History tables of all search threads together.
+    pub search_tables: usize,
+}
+
+impl MemoryEstimate {
+    pub fn total(&self) -> usize {
+        self.geometry + self.zobrist + self.transposition_table + self.search_tables
+    }
+}
+
+impl fmt::Display for MemoryEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mb = |bytes: usize| bytes as f64 / MB as f64;
+        writeln!(f, "  geometry:            {:>10.1} MB", mb(self.geometry))?;
+        writeln!(f, "  zobrist keys:        {:>10.1} MB", mb(self.zobrist))?;
+        writeln!(
+            f,
+            "  transposition table: {:>10.1} MB",
+            mb(self.transposition_table)
+        )?;
+        writeln!(
+            f,
+            "  search tables:       {:>10.1} MB",
+            mb(self.search_tables)
+        )?;
+        write!(f, "  total:               {:>10.1} MB", mb(self.total()))
+    }
+}
+
+/// How a search divides its memory budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryPlan {
+    pub estimate: MemoryEstimate,
+    /// Size to create the transposition table with.
+    pub tt_mb: usize,
+    pub tables: TableSizes,
+}
+
+/// The search cannot run within the budget even with the smallest tables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryError {
+    pub dimension: usize,
+    pub side: usize,
+    pub threads: usize,
+    pub required_mb: usize,
+    pub budget_mb: usize,
+}
+
+impl fmt::Display for MemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a {}D side-{} board with {} thread(s) needs at least {} MB, \
+             but compute.memory is {} MB",
+            self.dimension, self.side, self.threads, self.required_mb, self.budget_mb
+        )
+    }
+}
+
+impl std::error::Error for MemoryError {}
+
+fn geometry_bytes(dimension: usize, side: usize) -> usize {
+    let total_cells = side.pow(dimension as u32);
+    let directions = Rules::get_rook_directions_calc(dimension).len()
+        + Rules::get_bishop_directions_calc(dimension).len();
+    // Masks spill to the heap beyond the inline capacity of 8 words.
+    let words = total_cells.div_ceil(64);
+    let mask = size_of::<BitBoardLarge>() + if words > 8 { words * 8 } else { 0 };
+
+    total_cells * size_of::<SmallVec<[u8; 8]>>()
+        + directions * side * mask
+        + 4 * total_cells * size_of::<SmallVec<[usize; 16]>>()
+        + total_cells * size_of::<i32>()
+}
+
+fn zobrist_bytes(total_cells: usize) -> usize {
+    (13 * total_cells + 16 + 1) * size_of::<u64>()
+}
+
+/// Memory a search on a `dimension`-D board of `side` would use with a
+/// transposition table of `tt_mb` and full-size history tables.
+pub fn estimate(dimension: usize, side: usize, threads: usize, tt_mb: usize) -> MemoryEstimate {
+    let total_cells = side.pow(dimension as u32);
+    MemoryEstimate {
+        geometry: geometry_bytes(dimension, side),
+        zobrist: zobrist_bytes(total_cells),
+        transposition_table: LockFreeTT::bytes_for(tt_mb),
+        search_tables: threads.max(1) * search_tables_bytes(total_cells, TABLE_STEPS[0]),
+    }
+}
+
+/// Fit a search into `budget_mb` in total.
+///
+/// The geometry and Zobrist keys are fixed costs. History tables shrink
+/// (hashing more moves together) until the transposition table gets at least
+/// half of the budget, and the table takes whatever is left.
+pub fn plan(
+    dimension: usize,
+    side: usize,
+    threads: usize,
+    budget_mb: usize,
+) -> Result<MemoryPlan, MemoryError> {
+    let threads = threads.max(1);
+    let total_cells = side.pow(dimension as u32);
+    let fixed = geometry_bytes(dimension, side) + zobrist_bytes(total_cells);
+    let budget = budget_mb * MB;
+
+    let tables_for = |sizes: TableSizes| threads * search_tables_bytes(total_cells, sizes);
+    let left_for_tt = |sizes: TableSizes| budget.checked_sub(fixed + tables_for(sizes));
+
+    let smallest = TABLE_STEPS[TABLE_STEPS.len() - 1];
+    let tables = TABLE_STEPS
+        .into_iter()
+        .find(|&sizes| left_for_tt(sizes).is_some_and(|left| left >= budget / 2))
+        .unwrap_or(smallest);
+
+    let tt_mb = left_for_tt(tables).unwrap_or(0) / MB;
+    if tt_mb < MIN_TT_MB {
+        let required = fixed + tables_for(smallest) + MIN_TT_MB * MB;
+        return Err(MemoryError {
+            dimension,
+            side,
+            threads,
+            required_mb: required.div_ceil(MB),
+            budget_mb,
+        });
+    }
+
+    Ok(MemoryPlan {
+        estimate: MemoryEstimate {
+            geometry: geometry_bytes(dimension, side),
+            zobrist: zobrist_bytes(total_cells),
+            transposition_table: LockFreeTT::bytes_for(tt_mb),
+            search_tables: tables_for(tables),
+        },
+        tt_mb,
+        tables,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_fits_budget() {
+        for (dimension, side, threads, budget_mb) in [(2, 8, 4, 16), (3, 8, 2, 64), (4, 8, 2, 32)] {
+            let plan = plan(dimension, side, threads, budget_mb).unwrap();
+            assert!(plan.estimate.total() <= budget_mb * MB);
+        }
+    }
+
+    #[test]
+    fn test_plan_shrinks_tables_then_refuses() {
+        let full = plan(4, 8, 1, 1024).unwrap();
+        assert_eq!(full.tables, TABLE_STEPS[0]);
+
+        let tight = plan(4, 8, 4, 48).unwrap();
+        assert_ne!(tight.tables, TABLE_STEPS[0]);
+
+        let err = plan(5, 8, 64, 16).unwrap_err();
+        assert!(err.required_mb > 16);
+    }
+}
@@ -1,22 +1,41 @@
 use super::eval::Evaluator;
+use super::eval_params::EvalParams;
+use super::nnue::Network;
 use crate::config::AppConfig;
 use crate::domain::board::{Board, UnmakeInfo};
 use crate::domain::models::{Move, PieceType, Player};
 use crate::domain::rules::{MoveList, Rules};
 use crate::domain::services::PlayerStrategy;
-use crate::infrastructure::ai::transposition::{Flag, LockFreeTT, PackedMove};
+use crate::infrastructure::ai::handle::Ponder;
+use crate::infrastructure::ai::limits::SearchLimits;
+use crate::infrastructure::ai::memory::{self, MemoryError, TableSizes};
+use crate::infrastructure::ai::observer::{SearchEvent, SearchInfo, SearchObserver};
+use crate::infrastructure::ai::personality::Personality;
+use crate::infrastructure::ai::result::{
+    MATE_THRESHOLD, SearchResult, mate_in, mated_score, mating_score,
+};
+use crate::infrastructure::ai::skill::Skill;
+use crate::infrastructure::ai::transposition::{
+    Flag, LockFreeTT, PackedMove, score_from_tt, score_to_tt,
+};
+use crate::infrastructure::book::BookProbe;
+use crate::infrastructure::syzygy::{SyzygyTables, Wdl, wdl_score};
+use crate::infrastructure::tablebase::{Tablebases, TbValue};
 use rayon::prelude::*;
-use std::sync::Arc;
+use std::mem::size_of;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
 use super::search_core::{VAL_BISHOP, VAL_KNIGHT, VAL_QUEEN, VAL_ROOK};
 use super::see::SEE;
 
-const CHECKMATE_SCORE: i32 = 30000;
 const TIMEOUT_CHECK_INTERVAL: usize = 2048;
 
+/// Iteration cap for searches bounded only by time, nodes or a stop signal.
+const MAX_SEARCH_DEPTH: usize = 64;
+
 /// History heuristic cap (Stockfish uses 7183; we use the same scale).
 const MAX_HISTORY: i32 = 7183;
 
@@ -81,6 +100,58 @@ fn shift_ancestors(
     child
 }
 
+/// History scores over pairs of cell-like indices (from × to, previous
+/// destination × to, pawn hash bucket × to). Pairs beyond `2^max_bits` are
+/// hashed into that many slots, keeping history memory bounded on 4D/5D
+/// boards; see [`TableSizes`].
+#[derive(Clone)]
+struct PairTable {
+    values: Vec<i32>,
+    width: usize,
+    /// Bits of the hashed index, or `None` when pairs are indexed exactly.
+    hash_bits: Option<u32>,
+}
+
+impl PairTable {
+    fn new(rows: usize, width: usize, max_bits: u32) -> Self {
+        let hash_bits = (rows * width > 1 << max_bits).then_some(max_bits);
+        Self {
+            values: vec![0; Self::len(rows, width, max_bits)],
+            width,
+            hash_bits,
+        }
+    }
+
+    fn len(rows: usize, width: usize, max_bits: u32) -> usize {
+        (rows * width).min(1 << max_bits)
+    }
+
+    #[inline]
+    fn index(&self, row: usize, col: usize) -> usize {
+        let exact = row * self.width + col;
+        match self.hash_bits {
+            // Fibonacci hashing spreads neighbouring pairs over the table.
+            Some(bits) => {
+                ((exact as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (64 - bits)) as usize
+            }
+            None => exact,
+        }
+    }
+
+    #[inline]
+    fn get(&self, row: usize, col: usize) -> i32 {
+        self.values.get(self.index(row, col)).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    fn update(&mut self, row: usize, col: usize, bonus: i32) {
+        let idx = self.index(row, col);
+        if let Some(entry) = self.values.get_mut(idx) {
+            update_history(entry, bonus);
+        }
+    }
+}
+
 /// Correction history (#18): tracks static eval error by pawn structure hash.
 /// Stores a weighted running average of (search_score - static_eval).
 const CORRECTION_TABLE_SIZE: usize = 16384;
@@ -115,6 +186,130 @@ impl CorrectionHistory {
     }
 }
 
+/// Per-thread move-ordering tables. Each search thread takes a set through
+/// `take_tables` and returns it when done, so the tables carry over between
+/// the moves of a game (see `prepare`). `new_game` drops them, and a board
+/// of another size gets new ones.
+struct SearchTables {
+    killers: Vec<[Option<Move>; 2]>,
+    history: Vec<PairTable>,
+    countermoves: Vec<Vec<Option<Move>>>,
+    cont_history: Vec<PairTable>,
+    /// Capture history (#10): [player][to_square * 6 + captured_piece_type]
+    capture_history: Vec<Vec<i32>>,
+    correction_history: CorrectionHistory,
+    pawn_history: Vec<PairTable>,
+    low_ply_history: Vec<PairTable>,
+    /// Triangular PV scratch space: `pv_table[d]` is the best line found
+    /// from stack frame `d` of the current `minimax` call.
+    pv_table: Vec<Vec<Move>>,
+}
+
+impl SearchTables {
+    fn new(total_cells: usize, max_depth: usize, sizes: TableSizes) -> Self {
+        let cap_hist_size = total_cells * 6;
+        let moves = PairTable::new(total_cells, total_cells, sizes.pair_bits);
+        let pawns = PairTable::new(PAWN_HIST_SIZE, total_cells, sizes.pawn_bits);
+        Self {
+            killers: (0..=max_depth).map(|_| [None, None]).collect(),
+            history: vec![moves.clone(); 2],
+            countermoves: vec![vec![None; total_cells], vec![None; total_cells]],
+            cont_history: vec![moves.clone(); CONT_HIST_PLIES * 2],
+            capture_history: vec![vec![0i32; cap_hist_size], vec![0i32; cap_hist_size]],
+            correction_history: CorrectionHistory::new(),
+            pawn_history: vec![pawns; 2],
+            low_ply_history: vec![moves; LOW_PLY_MAX * 2],
+            pv_table: Vec::new(),
+        }
+    }
+
+    /// Get tables kept from the previous move ready for the next search.
+    /// Killers, low-ply history and PVs belong to the old root and are
+    /// cleared; the other histories are halved so fresh results dominate.
+    fn prepare(&mut self, max_depth: usize) {
+        self.killers = (0..=max_depth).map(|_| [None, None]).collect();
+        self.pv_table.clear();
+        for table in &mut self.low_ply_history {
+            table.values.fill(0);
+        }
+        let aged = self
+            .history
+            .iter_mut()
+            .chain(&mut self.cont_history)
+            .chain(&mut self.pawn_history)
+            .map(|table| &mut table.values)
+            .chain(&mut self.capture_history);
+        for table in aged {
+            for value in table.iter_mut() {
+                *value /= 2;
+            }
+        }
+    }
+}
+
+/// Memory taken by one thread's `SearchTables`, for the memory planner.
+pub(crate) fn search_tables_bytes(total_cells: usize, sizes: TableSizes) -> usize {
+    let pairs = PairTable::len(total_cells, total_cells, sizes.pair_bits);
+    let pawns = PairTable::len(PAWN_HIST_SIZE, total_cells, sizes.pawn_bits);
+    let history = (2 + CONT_HIST_PLIES * 2 + LOW_PLY_MAX * 2) * pairs + 2 * pawns;
+    let capture = 2 * total_cells * 6;
+
+    (history + capture) * size_of::<i32>()
+        + 2 * total_cells * size_of::<Option<Move>>()
+        + CORRECTION_TABLE_SIZE * size_of::<(i32, i32)>()
+        + (MAX_SEARCH_DEPTH + 1) * size_of::<[Option<Move>; 2]>()
+}
+
+/// A root move with its score and the line that produced it. `pv[0]` is the
+/// root move; fail-low moves carry only that.
+#[derive(Clone)]
+struct RootLine {
+    score: i32,
+    pv: Vec<Move>,
+}
+
+/// Root moves searched in one iteration, best first.
+struct RootIteration {
+    scored: Vec<RootLine>,
+    /// A move reached `beta`; the remaining moves were not searched.
+    failed_high: bool,
+}
+
+impl RootIteration {
+    fn best_score(&self) -> i32 {
+        self.scored.first().map_or(-i32::MAX, |s| s.score)
+    }
+}
+
+/// What one Lazy SMP thread found.
+struct ThreadOutcome {
+    best_move: Move,
+    score: i32,
+    /// Top `multi_pv` root moves of the last completed iteration.
+    lines: Vec<RootLine>,
+    /// Last completed iteration, 0 if none finished.
+    depth: usize,
+}
+
+/// Expand a TT move into a `Move`. The result is only pseudo-valid: the
+/// entry may belong to a different position.
+fn unpack_tt_move(board: &Board, tm: PackedMove) -> Move {
+    let promotion = match tm.promotion {
+        1 => Some(PieceType::Queen),
+        2 => Some(PieceType::Rook),
+        3 => Some(PieceType::Bishop),
+        4 => Some(PieceType::Knight),
+        _ => None,
+    };
+    Move {
+        from: crate::domain::coordinate::Coordinate::new(
+            board.index_to_coords(tm.from_idx as usize),
+        ),
+        to: crate::domain::coordinate::Coordinate::new(board.index_to_coords(tm.to_idx as usize)),
+        promotion,
+    }
+}
+
 /// Map a piece at a board index to a 0-5 type index for capture history.
 #[inline]
 fn piece_type_index(board: &Board, idx: usize) -> usize {
@@ -133,119 +328,778 @@ fn piece_type_index(board: &Board, idx: usize) -> usize {
     } // king
 }
 
+/// Limits of the search in progress, with defaults filled in.
+#[derive(Clone)]
+struct ResolvedLimits {
+    depth: usize,
+    time: Option<Duration>,
+    /// Stop iterating early when the best move is stable (#24, #25).
+    adaptive_time: bool,
+    nodes: Option<u64>,
+    mate: Option<u32>,
+    multi_pv: usize,
+}
+
+/// Cloning shares the transposition table, history tables, counters and
+/// stop flag; see [`MinimaxBot::start_search`].
+///
+/// The transposition table and history tables persist from one search to
+/// the next, so keep one bot per game and call [`MinimaxBot::new_game`]
+/// before reusing it for another.
+#[derive(Clone)]
 pub struct MinimaxBot {
+    /// Depth and time used when a search sets no limits of its own.
     depth: usize,
     time_limit: Duration,
     tt: Arc<LockFreeTT>,
+    /// Per-thread history tables left by the last search, reused by the next.
+    tables: Arc<Mutex<Vec<SearchTables>>>,
+    table_sizes: TableSizes,
     stop_flag: Arc<AtomicBool>,
     nodes_searched: Arc<AtomicUsize>,
+    /// Deepest ply reached in the current search (for `SearchInfo::seldepth`).
+    seldepth: Arc<AtomicUsize>,
     num_threads: usize,
     lmr_table: [[i32; LMR_TABLE_SIZE]; LMR_TABLE_SIZE],
+    observer: Option<Arc<dyn SearchObserver>>,
+    limits: ResolvedLimits,
+    ponder: Arc<Ponder>,
+    /// Weights as configured, before the personality's preset.
+    base_params: EvalParams,
+    eval_params: EvalParams,
+    /// Evaluates in place of the classical evaluation when set.
+    network: Option<Arc<Network>>,
+    book: Option<BookProbe>,
+    tablebases: Option<Arc<Tablebases>>,
+    syzygy: Option<Arc<SyzygyTables>>,
+    skill: Skill,
+    personality: Personality,
+    /// Centipawns a draw is worse than an equal position for the side to
+    /// move at the root.
+    contempt: i32,
 }
 
 impl MinimaxBot {
-    pub fn new(config: &AppConfig, _dimension: usize, _side: usize) -> Self {
-        Self {
-            depth: config.minimax.depth,
-            time_limit: Duration::from_secs(config.compute.minutes * 60),
-            tt: Arc::new(LockFreeTT::new(config.compute.memory)),
-            stop_flag: Arc::new(AtomicBool::new(false)),
-            nodes_searched: Arc::new(AtomicUsize::new(0)),
-            num_threads: config.compute.concurrency.max(1),
-            lmr_table: compute_lmr_table(),
-        }
+    /// Create a bot for a `dimension`-D board of `side`.
+    ///
+    /// # Panics
+    ///
+    /// If the search does not fit in `compute.memory`; see [`Self::try_new`].
+    pub fn new(config: &AppConfig, dimension: usize, side: usize) -> Self {
+        Self::try_new(config, dimension, side).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Create a bot whose whole search, not just the transposition table,
+    /// fits in `compute.memory`, or explain why it cannot.
+    pub fn try_new(config: &AppConfig, dimension: usize, side: usize) -> Result<Self, MemoryError> {
+        let mut bot = Self::try_new_from_params(
+            config.minimax.depth,
+            Duration::from_secs(config.compute.minutes * 60),
+            config.compute.memory,
+            config.compute.concurrency,
+            dimension,
+            side,
+        )?;
+        bot.base_params = config.eval_params(dimension, side);
+        bot.eval_params = bot.base_params.clone();
+        bot.network = config.network(dimension, side);
+        bot.book = config.book(dimension, side);
+        bot.tablebases = config.tablebases(dimension, side);
+        bot.syzygy = config.syzygy(dimension, side);
+        Ok(bot)
     }
 
     /// Create a MinimaxBot from explicit parameters (used by distributed workers).
+    /// Memory is planned for a 2D 8×8 board; prefer [`Self::try_new_from_params`].
     pub fn new_from_params(
         depth: usize,
         time_limit: Duration,
         memory_mb: usize,
         num_threads: usize,
     ) -> Self {
-        Self {
+        Self::try_new_from_params(depth, time_limit, memory_mb, num_threads, 2, 8)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// `new_from_params` for a known board geometry, failing rather than
+    /// overrunning `memory_mb`. Evaluates with the default weights.
+    pub fn try_new_from_params(
+        depth: usize,
+        time_limit: Duration,
+        memory_mb: usize,
+        num_threads: usize,
+        dimension: usize,
+        side: usize,
+    ) -> Result<Self, MemoryError> {
+        let num_threads = num_threads.max(1);
+        let plan = memory::plan(dimension, side, num_threads, memory_mb)?;
+        Ok(Self {
             depth,
             time_limit,
-            tt: Arc::new(LockFreeTT::new(memory_mb)),
+            tt: Arc::new(LockFreeTT::new(plan.tt_mb)),
+            tables: Arc::new(Mutex::new(Vec::new())),
+            table_sizes: plan.tables,
             stop_flag: Arc::new(AtomicBool::new(false)),
             nodes_searched: Arc::new(AtomicUsize::new(0)),
-            num_threads: num_threads.max(1),
+            seldepth: Arc::new(AtomicUsize::new(0)),
+            num_threads,
             lmr_table: compute_lmr_table(),
-        }
+            observer: None,
+            limits: ResolvedLimits {
+                depth: 1,
+                time: None,
+                adaptive_time: false,
+                nodes: None,
+                mate: None,
+                multi_pv: 1,
+            },
+            ponder: Arc::new(Ponder::default()),
+            base_params: EvalParams::default(),
+            eval_params: EvalParams::default(),
+            network: None,
+            book: None,
+            tablebases: None,
+            syzygy: None,
+            skill: Skill::default(),
+            personality: Personality::default(),
+            contempt: 0,
+        })
     }
 
-    /// Search only a specified subset of root moves (used by distributed workers).
-    /// Returns (best_move, score, nodes_searched, completed).
-    pub fn search_subset(
-        &mut self,
-        board: &Board,
-        player: Player,
-        root_moves: Vec<Move>,
-    ) -> (Move, i32, u64, bool) {
-        if root_moves.is_empty() {
-            // Should not happen, but return a safe default
-            return (
-                Move {
-                    from: crate::domain::coordinate::Coordinate::new(smallvec::smallvec![0]),
-                    to: crate::domain::coordinate::Coordinate::new(smallvec::smallvec![0]),
-                    promotion: None,
-                },
-                -i32::MAX,
-                0,
-                true,
-            );
+    /// Receive progress and per-iteration results. Without an observer the
+    /// search is silent.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn SearchObserver>>) {
+        self.observer = observer;
+    }
+
+    /// Evaluate with `params`, under the personality's preset, from now on.
+    /// Scores stored under the old weights are forgotten, as by
+    /// [`Self::new_game`].
+    pub fn set_eval_params(&mut self, params: EvalParams) {
+        self.eval_params = self.personality.weights(&params);
+        self.base_params = params;
+        self.new_game();
+    }
+
+    /// The weights evaluated with, the personality's preset included.
+    pub fn eval_params(&self) -> &EvalParams {
+        &self.eval_params
+    }
+
+    /// Play in `personality`'s style from now on: its preset over the
+    /// configured weights, and its contempt. Scores stored before are
+    /// forgotten, as by [`Self::new_game`].
+    pub fn set_personality(&mut self, personality: Personality) {
+        self.personality = personality;
+        self.eval_params = personality.weights(&self.base_params);
+        self.contempt = personality.contempt();
+        self.new_game();
+    }
+
+    pub fn personality(&self) -> Personality {
+        self.personality
+    }
+
+    /// Score draws `contempt` centipawns below an equal position, in place
+    /// of the personality's contempt. Scores stored before are forgotten,
+    /// as by [`Self::new_game`].
+    pub fn set_contempt(&mut self, contempt: i32) {
+        self.contempt = contempt;
+        self.new_game();
+    }
+
+    pub fn contempt(&self) -> i32 {
+        self.contempt
+    }
+
+    /// Evaluate with `network` on the board it was trained for, and with
+    /// the classical evaluation elsewhere or if `None`. Scores stored under
+    /// the old evaluation are forgotten, as by [`Self::new_game`].
+    pub fn set_network(&mut self, network: Option<Arc<Network>>) {
+        self.network = network;
+        self.new_game();
+    }
+
+    pub fn network(&self) -> Option<&Arc<Network>> {
+        self.network.as_ref()
+    }
+
+    /// Play from `book` while the game is in it, instead of searching.
+    pub fn set_book(&mut self, book: Option<BookProbe>) {
+        self.book = book;
+    }
+
+    pub fn book(&self) -> Option<&BookProbe> {
+        self.book.as_ref()
+    }
+
+    /// Play perfectly from `tablebases` once the game is in them, and score
+    /// the positions the search reaches in them exactly.
+    pub fn set_tablebases(&mut self, tablebases: Option<Arc<Tablebases>>) {
+        self.tablebases = tablebases;
+    }
+
+    pub fn tablebases(&self) -> Option<&Arc<Tablebases>> {
+        self.tablebases.as_ref()
+    }
+
+    /// Search only the moves keeping the best result once the game is in
+    /// the Syzygy tables, and score positions reached by a capture or pawn
+    /// move from them.
+    pub fn set_syzygy(&mut self, syzygy: Option<Arc<SyzygyTables>>) {
+        self.syzygy = syzygy;
+    }
+
+    pub fn syzygy(&self) -> Option<&Arc<SyzygyTables>> {
+        self.syzygy.as_ref()
+    }
+
+    /// Play at `skill` from now on; see [`Skill`].
+    pub fn set_skill(&mut self, skill: Skill) {
+        self.skill = skill;
+    }
+
+    pub fn skill(&self) -> Skill {
+        self.skill
+    }
+
+    pub(crate) fn observer(&self) -> Option<Arc<dyn SearchObserver>> {
+        self.observer.clone()
+    }
+
+    pub(crate) fn ponder(&self) -> Arc<Ponder> {
+        self.ponder.clone()
+    }
+
+    /// Forget everything learned from earlier positions: empty the
+    /// transposition table and drop the history tables.
+    pub fn new_game(&self) {
+        self.tt.clear();
+        self.tables.lock().unwrap().clear();
+    }
+
+    /// Tables for one search thread, reusing those of an earlier search.
+    fn take_tables(&self, total_cells: usize, max_depth: usize) -> SearchTables {
+        let kept = self.tables.lock().unwrap().pop();
+        match kept {
+            Some(mut tables) if tables.countermoves[0].len() == total_cells => {
+                tables.prepare(max_depth);
+                tables
+            }
+            _ => SearchTables::new(total_cells, max_depth, self.table_sizes),
         }
+    }
 
-        self.nodes_searched.store(0, Ordering::Relaxed);
+    /// Clear the stop flag and enter or leave pondering for a new search.
+    pub(crate) fn reset_controls(&self, limits: &SearchLimits) {
+        self.ponder.start(limits.ponder);
         self.stop_flag.store(false, Ordering::Relaxed);
-        let start_time = Instant::now();
+    }
+
+    /// Reset per-search counters and start the progress monitor thread.
+    /// The returned flag keeps the monitor alive; clear it when the search ends.
+    fn begin_search(&self, start_time: Instant) -> Arc<AtomicBool> {
+        self.nodes_searched.store(0, Ordering::Relaxed);
+        self.seldepth.store(0, Ordering::Relaxed);
+
+        let search_active = Arc::new(AtomicBool::new(true));
+        let Some(observer) = self.observer.clone() else {
+            return search_active;
+        };
 
         let nodes_counter = self.nodes_searched.clone();
         let stop_flag = self.stop_flag.clone();
-        let search_active = Arc::new(AtomicBool::new(true));
         let search_active_clone = search_active.clone();
-
-        // Monitoring thread
         thread::spawn(move || {
             let mut last_nodes = 0;
             let mut last_time = Instant::now();
+
             while search_active_clone.load(Ordering::Relaxed) {
                 thread::sleep(Duration::from_millis(500));
-                if stop_flag.load(Ordering::Relaxed) {
+
+                if stop_flag.load(Ordering::Relaxed) || !search_active_clone.load(Ordering::Relaxed)
+                {
                     break;
                 }
+
                 let current_nodes = nodes_counter.load(Ordering::Relaxed);
                 let now = Instant::now();
                 let duration = now.duration_since(last_time).as_secs_f64();
+
                 if duration > 0.0 {
                     let nps = (current_nodes - last_nodes) as f64 / duration;
-                    let nps_fmt = if nps > 1_000_000.0 {
-                        format!("{:.2} MN/s", nps / 1_000_000.0)
-                    } else {
-                        format!("{:.2} kN/s", nps / 1_000.0)
-                    };
-                    eprint!(
-                        "\r[worker] nodes {} nps {} time {:.1}s  ",
-                        current_nodes,
-                        nps_fmt,
-                        start_time.elapsed().as_secs_f32()
-                    );
-                    use std::io::Write;
-                    std::io::stderr().flush().unwrap_or(());
+                    observer.on_event(&SearchEvent::Progress {
+                        nodes: current_nodes as u64,
+                        nps: nps as u64,
+                        time: start_time.elapsed(),
+                    });
                 }
+
                 last_nodes = current_nodes;
                 last_time = now;
             }
         });
 
-        let results: Vec<(Move, i32)> = (0..self.num_threads)
+        search_active
+    }
+
+    /// Build one `SearchInfo` per root line of a completed iteration.
+    fn line_infos(
+        &self,
+        board: &Board,
+        player: Player,
+        depth: usize,
+        lines: &[RootLine],
+        start: Instant,
+    ) -> Vec<SearchInfo> {
+        let time = start.elapsed();
+        let nodes = self.nodes_searched.load(Ordering::Relaxed) as u64;
+        let millis = time.as_millis().max(1) as u64;
+        let seldepth = self.seldepth.load(Ordering::Relaxed).max(depth);
+        let hashfull = self.tt.hashfull();
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| SearchInfo {
+                depth,
+                seldepth,
+                multipv: i + 1,
+                score: line.score,
+                pv: self.validated_pv(board, player, &line.pv, depth),
+                nodes,
+                nps: nodes * 1000 / millis,
+                hashfull,
+                time,
+            })
+            .collect()
+    }
+
+    /// Notify the observer of a completed iteration.
+    fn report_iteration(
+        &self,
+        board: &Board,
+        player: Player,
+        depth: usize,
+        lines: &[RootLine],
+        start: Instant,
+    ) {
+        let Some(observer) = &self.observer else {
+            return;
+        };
+        for info in self.line_infos(board, player, depth, lines, start) {
+            observer.on_event(&SearchEvent::Iteration(info));
+        }
+    }
+
+    /// Replay `pv` from the root, cutting it at the first illegal move, then
+    /// extend it with TT best moves up to `min_len` moves. TT moves can come
+    /// from hash collisions, so they go through the same legality check.
+    fn validated_pv(
+        &self,
+        board: &Board,
+        player: Player,
+        pv: &[Move],
+        min_len: usize,
+    ) -> Vec<Move> {
+        let max_len = pv.len().max(min_len);
+        let mut board = board.clone();
+        let mut player = player;
+        let mut line = Vec::with_capacity(max_len);
+
+        while line.len() < max_len {
+            let candidate = match pv.get(line.len()) {
+                Some(mv) => mv.clone(),
+                None => match self.tt.get(board.state.hash) {
+                    Some((_, _, _, Some(tm))) => unpack_tt_move(&board, tm),
+                    _ => break,
+                },
+            };
+            let legal = Rules::generate_legal_moves(&mut board, player);
+            if !legal.contains(&candidate) || board.apply_move(&candidate).is_err() {
+                break;
+            }
+            line.push(candidate);
+            player = player.opponent();
+        }
+        line
+    }
+
+    /// Search every root move once at `depth` within `(alpha, beta)`.
+    ///
+    /// With `multi_pv` K > 1 the lower bound passed to each move is the K-th
+    /// best score so far rather than the best, so all of the top K moves come
+    /// back with exact scores.
+    #[allow(clippy::too_many_arguments)]
+    fn search_root(
+        &self,
+        board: &mut Board,
+        player: Player,
+        root_moves: &[Move],
+        depth: usize,
+        alpha: i32,
+        beta: i32,
+        start_time: Instant,
+        tables: &mut SearchTables,
+    ) -> RootIteration {
+        let mut scored = Vec::with_capacity(root_moves.len());
+        // Best `multi_pv` scores so far, highest first.
+        let mut top: Vec<i32> = Vec::with_capacity(self.limits.multi_pv + 1);
+        let mut alpha_inner = alpha;
+        let mut failed_high = false;
+
+        for mv in root_moves {
+            let mv_to_idx = board.coords_to_index(&mv.to.values);
+            let info = board.apply_move(mv).unwrap();
+            let score = -self.minimax(
+                board,
+                depth - 1,
+                -beta,
+                -alpha_inner,
+                player.opponent(),
+                1,
+                start_time,
+                true,
+                &mut tables.killers,
+                &mut tables.history,
+                &mut tables.countermoves,
+                &mut tables.cont_history,
+                &mut tables.capture_history,
+                &mut tables.correction_history,
+                &mut tables.pawn_history,
+                &mut tables.low_ply_history,
+                mv_to_idx,
+                &mut tables.pv_table,
+            );
+            board.unmake_move(mv, info);
+
+            if self.stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            let mut pv = vec![mv.clone()];
+            if score > alpha_inner {
+                pv.extend(tables.pv_table.first().into_iter().flatten().cloned());
+            }
+            scored.push(RootLine { score, pv });
+
+            let pos = top.partition_point(|&s| s >= score);
+            top.insert(pos, score);
+            top.truncate(self.limits.multi_pv);
+            if top.len() == self.limits.multi_pv {
+                alpha_inner = alpha_inner.max(top[self.limits.multi_pv - 1]);
+            }
+            if score >= beta {
+                failed_high = true;
+                break;
+            }
+        }
+
+        // Stable sort: on equal scores the move searched first stays best.
+        scored.sort_by_key(|s| std::cmp::Reverse(s.score));
+        RootIteration {
+            scored,
+            failed_high,
+        }
+    }
+
+    /// Search only a specified subset of root moves (used by distributed workers).
+    /// Returns (best_move, score, nodes_searched, completed).
+    pub fn search_subset(
+        &mut self,
+        board: &Board,
+        player: Player,
+        root_moves: Vec<Move>,
+    ) -> (Move, i32, u64, bool) {
+        let limits = SearchLimits {
+            depth: Some(self.depth),
+            movetime: Some(self.time_limit),
+            search_moves: root_moves,
+            ..SearchLimits::default()
+        };
+        match self.search(board, player, &limits) {
+            Some(result) => (
+                result.best_move,
+                result.score,
+                result.nodes,
+                !self.stop_flag.load(Ordering::Relaxed),
+            ),
+            // Should not happen, but return a safe default
+            None => (
+                Move {
+                    from: crate::domain::coordinate::Coordinate::new(smallvec::smallvec![0]),
+                    to: crate::domain::coordinate::Coordinate::new(smallvec::smallvec![0]),
+                    promotion: None,
+                },
+                -i32::MAX,
+                0,
+                true,
+            ),
+        }
+    }
+
+    /// Analyse a position: the top `limits.multi_pv` root moves, best first.
+    /// Empty if there are no legal moves.
+    pub fn analyze(
+        &mut self,
+        board: &Board,
+        player: Player,
+        limits: &SearchLimits,
+    ) -> Vec<SearchInfo> {
+        self.search(board, player, limits)
+            .map(|result| result.lines)
+            .unwrap_or_default()
+    }
+
+    /// A flag that aborts the running search when raised. `search` clears it
+    /// on start, so raise it only once the search is under way. Prefer
+    /// [`MinimaxBot::start_search`], whose handle has no such race.
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop_flag.clone()
+    }
+
+    /// Search the position within `limits` and return the best move with its
+    /// principal variation. `None` if no legal root move is left to search.
+    pub fn search(
+        &mut self,
+        board: &Board,
+        player: Player,
+        limits: &SearchLimits,
+    ) -> Option<SearchResult> {
+        self.reset_controls(limits);
+        self.search_started(board, player, limits)
+    }
+
+    /// `search` without resetting the stop flag and ponder state first.
+    pub(crate) fn search_started(
+        &mut self,
+        board: &Board,
+        player: Player,
+        limits: &SearchLimits,
+    ) -> Option<SearchResult> {
+        let weakened = self.skill.is_weakened() && !limits.infinite && limits.mate.is_none();
+        let capped;
+        let limits = if weakened {
+            capped = self.skill.limit(limits);
+            &capped
+        } else {
+            limits
+        };
+
+        let mut root_moves = Rules::generate_legal_moves(&mut board.clone(), player).into_vec();
+        if !limits.search_moves.is_empty() {
+            root_moves.retain(|mv| limits.search_moves.contains(mv));
+        }
+        if root_moves.is_empty() {
+            return None;
+        }
+        if let Some(result) = self.book_move(board, player, limits, &root_moves) {
+            return Some(result);
+        }
+        if let Some(result) = self.tablebase_move(board, player, limits, &root_moves) {
+            return Some(result);
+        }
+        if !limits.infinite
+            && limits.mate.is_none()
+            && let Some(syzygy) = &self.syzygy
+            && let Some(moves) = syzygy.root_moves(board, player, &root_moves)
+        {
+            root_moves = moves;
+        }
+
+        let attached;
+        let board = match &self.network {
+            Some(network) if network.check_board(board.dimension(), board.side()).is_ok() => {
+                attached = network.attach(board);
+                &attached
+            }
+            _ => board,
+        };
+
+        self.limits = self.resolve_limits(limits);
+        self.tt.new_search();
+        let start_time = Instant::now();
+        let results = self.search_threads(board, player, &root_moves, start_time);
+        let best = results.into_iter().max_by_key(|r| r.score)?;
+        // A thread stopped mid-iteration keeps the previous iteration's move,
+        // whose line may no longer be the first one.
+        let line = best
+            .lines
+            .iter()
+            .find(|l| l.pv[0] == best.best_move)
+            .map_or_else(|| vec![best.best_move.clone()], |l| l.pv.clone());
+
+        let mut result = SearchResult {
+            pv: self.validated_pv(board, player, &line, best.depth),
+            lines: self.line_infos(board, player, best.depth, &best.lines, start_time),
+            best_move: best.best_move,
+            score: best.score,
+            depth: best.depth,
+            seldepth: self.seldepth.load(Ordering::Relaxed).max(best.depth),
+            nodes: self.nodes_searched.load(Ordering::Relaxed) as u64,
+            mate: mate_in(best.score),
+            time: start_time.elapsed(),
+            book: false,
+        };
+        if weakened {
+            // Each search runs on a clone of the bot, so a generator kept on
+            // it would repeat the same choices.
+            let pick = self.skill.pick(&result.lines, &mut rand::thread_rng());
+            if let Some(line) = result
+                .lines
+                .get(pick)
+                .filter(|l| pick > 0 && !l.pv.is_empty())
+            {
+                result.best_move = line.pv[0].clone();
+                result.pv = line.pv.clone();
+                result.score = line.score;
+                result.mate = mate_in(line.score);
+            }
+        }
+        Some(result)
+    }
+
+    /// A move from the book, unless the search is analysing (`infinite` or
+    /// `mate`) rather than playing.
+    fn book_move(
+        &self,
+        board: &Board,
+        player: Player,
+        limits: &SearchLimits,
+        root_moves: &[Move],
+    ) -> Option<SearchResult> {
+        if limits.infinite || limits.mate.is_some() {
+            return None;
+        }
+        let mv = self.book.as_ref()?.pick(board, player)?;
+        if !root_moves.contains(&mv) {
+            return None;
+        }
+        Some(SearchResult {
+            best_move: mv.clone(),
+            score: 0,
+            depth: 0,
+            seldepth: 0,
+            nodes: 0,
+            pv: vec![mv],
+            mate: None,
+            time: Duration::ZERO,
+            lines: Vec::new(),
+            book: true,
+        })
+    }
+
+    /// The tablebase move keeping the best result, with its line to mate,
+    /// unless the search is analysing.
+    fn tablebase_move(
+        &self,
+        board: &Board,
+        player: Player,
+        limits: &SearchLimits,
+        root_moves: &[Move],
+    ) -> Option<SearchResult> {
+        if limits.infinite || limits.mate.is_some() {
+            return None;
+        }
+        let tablebases = self.tablebases.as_ref()?;
+        let (best_move, value) = tablebases.best_move(board, player, root_moves)?;
+
+        let mut pv = vec![best_move.clone()];
+        let mut line = board.clone();
+        let mut to_move = player;
+        while pv.len() < MAX_SEARCH_DEPTH {
+            line.apply_move(pv.last().unwrap()).ok()?;
+            to_move = to_move.opponent();
+            let moves = Rules::generate_legal_moves(&mut line, to_move).into_vec();
+            match tablebases.best_move(&line, to_move, &moves) {
+                Some((mv, v)) if v != TbValue::Draw => pv.push(mv),
+                _ => break,
+            }
+        }
+
+        let score = value.score(0);
+        Some(SearchResult {
+            best_move,
+            score,
+            depth: 0,
+            seldepth: 0,
+            nodes: 0,
+            pv,
+            mate: mate_in(score),
+            time: Duration::ZERO,
+            lines: Vec::new(),
+            book: false,
+        })
+    }
+
+    /// Fill in unset limits from the configured depth and time limit.
+    fn resolve_limits(&self, limits: &SearchLimits) -> ResolvedLimits {
+        let multi_pv = limits.multi_pv.max(1);
+        if limits.infinite {
+            return ResolvedLimits {
+                depth: MAX_SEARCH_DEPTH,
+                time: None,
+                adaptive_time: false,
+                nodes: limits.nodes,
+                mate: limits.mate,
+                multi_pv,
+            };
+        }
+
+        let time = limits
+            .movetime
+            .or_else(|| limits.clock.map(|clock| clock.allocate()));
+        let unbounded = limits.depth.is_none()
+            && time.is_none()
+            && limits.nodes.is_none()
+            && limits.mate.is_none();
+        if unbounded {
+            // Nothing requested: play with the configured depth and time.
+            return ResolvedLimits {
+                depth: self.depth,
+                time: Some(self.time_limit),
+                adaptive_time: true,
+                nodes: None,
+                mate: None,
+                multi_pv,
+            };
+        }
+
+        // A mate in N needs at most 2N - 1 plies.
+        let mate_depth = limits.mate.map(|n| 2 * n as usize);
+        ResolvedLimits {
+            depth: limits
+                .depth
+                .or(mate_depth)
+                .unwrap_or(MAX_SEARCH_DEPTH)
+                .clamp(1, MAX_SEARCH_DEPTH),
+            time,
+            adaptive_time: limits.movetime.is_none() && limits.clock.is_some(),
+            nodes: limits.nodes,
+            mate: limits.mate,
+            multi_pv,
+        }
+    }
+
+    /// Run the Lazy SMP root search over `root_moves` on every thread.
+    fn search_threads(
+        &self,
+        board: &Board,
+        player: Player,
+        root_moves: &[Move],
+        start_time: Instant,
+    ) -> Vec<ThreadOutcome> {
+        let search_active = self.begin_search(start_time);
+        let limits = &self.limits;
+
+        let results: Vec<ThreadOutcome> = (0..self.num_threads)
             .into_par_iter()
             .map(|thread_idx| {
                 let mut local_board = board.clone();
                 let mut local_best_move = None;
                 let mut local_best_score = -i32::MAX;
+                let mut local_lines = Vec::new();
+                let mut completed_depth = 0;
 
-                let mut my_moves = root_moves.clone();
+                let mut my_moves = root_moves.to_vec();
                 if thread_idx > 0 {
                     use rand::seq::SliceRandom;
                     let mut rng = rand::thread_rng();
@@ -253,130 +1107,150 @@ impl MinimaxBot {
                 }
 
                 let mut prev_score = 0;
-                let mut killers = (0..=self.depth).map(|_| [None, None]).collect::<Vec<_>>();
-                let total_cells = local_board.total_cells();
-                let hist_size = total_cells * total_cells;
-                let mut history = vec![vec![0i32; hist_size], vec![0i32; hist_size]];
-                let mut countermoves = vec![vec![None; total_cells], vec![None; total_cells]];
-                let mut cont_history = vec![vec![0i32; hist_size]; CONT_HIST_PLIES * 2];
-                // Capture history (#10): [player][to_square * 6 + captured_piece_type]
-                let cap_hist_size = total_cells * 6;
-                let mut capture_history =
-                    vec![vec![0i32; cap_hist_size], vec![0i32; cap_hist_size]];
-                let mut correction_history = CorrectionHistory::new();
-                let pawn_hist_entry_size = PAWN_HIST_SIZE * total_cells;
-                let mut pawn_history = vec![
-                    vec![0i32; pawn_hist_entry_size],
-                    vec![0i32; pawn_hist_entry_size],
-                ];
-                let mut low_ply_history = vec![vec![0i32; hist_size]; LOW_PLY_MAX * 2];
-
-                // Depth staggering (#27)
+                let mut tables = self.take_tables(local_board.total_cells(), limits.depth);
+
+                // Adaptive time management (#24, #25)
+                let mut best_move_stable_count: usize = 0;
+                let mut prev_best_move: Option<Move> = None;
+                let mut prev_iter_score: i32 = 0;
+
+                // Depth staggering (#27): helper threads start at higher depths
+                // to diversify TT population. Thread 0 starts at 1, thread 1 at 2, etc.
                 let start_depth = if thread_idx == 0 {
                     1
                 } else {
                     1 + (thread_idx % 3)
                 };
-                for d in start_depth..=self.depth {
+                for d in start_depth..=limits.depth {
                     let mut delta = 50;
-                    let (mut alpha, mut beta) = if d > 4 {
+                    // MultiPV needs exact scores below the best move, so it
+                    // always searches with a full window.
+                    let aspiration = d > 4 && limits.multi_pv == 1;
+                    let (mut alpha, mut beta) = if aspiration {
                         (prev_score - delta, prev_score + delta)
                     } else {
                         (-i32::MAX, i32::MAX)
                     };
 
                     loop {
-                        let mut best_score_this_iter = -i32::MAX;
-                        let mut best_move_this_iter = None;
-                        let mut alpha_inner = alpha;
-                        let mut failed_high = false;
-                        let mut failed_low = false;
-
-                        for mv in &my_moves {
-                            let mv_to_idx = local_board.coords_to_index(&mv.to.values);
-                            let info = local_board.apply_move(mv).unwrap();
-                            let score = -self.minimax(
-                                &mut local_board,
-                                d - 1,
-                                -beta,
-                                -alpha_inner,
-                                player.opponent(),
-                                start_time,
-                                true,
-                                &mut killers,
-                                &mut history,
-                                &mut countermoves,
-                                &mut cont_history,
-                                &mut capture_history,
-                                &mut correction_history,
-                                &mut pawn_history,
-                                &mut low_ply_history,
-                                mv_to_idx,
-                            );
-                            local_board.unmake_move(mv, info);
+                        let iteration = self.search_root(
+                            &mut local_board,
+                            player,
+                            &my_moves,
+                            d,
+                            alpha,
+                            beta,
+                            start_time,
+                            &mut tables,
+                        );
+                        let best_score_this_iter = iteration.best_score();
 
-                            if self.stop_flag.load(Ordering::Relaxed) {
-                                break;
-                            }
+                        if self.stop_flag.load(Ordering::Relaxed) {
+                            local_best_score = best_score_this_iter;
+                            break;
+                        }
 
-                            if score > best_score_this_iter {
-                                best_score_this_iter = score;
-                                best_move_this_iter = Some(mv.clone());
-                            }
-                            if score > alpha_inner {
-                                alpha_inner = score;
+                        if aspiration {
+                            if best_score_this_iter <= alpha {
+                                beta = (alpha + beta) / 2;
+                                alpha -= delta;
+                                delta += delta / 3;
+                                continue;
                             }
-                            if score >= beta {
-                                failed_high = true;
-                                break;
+                            if iteration.failed_high {
+                                beta += delta;
+                                delta += delta / 3;
+                                continue;
                             }
                         }
 
-                        if self.stop_flag.load(Ordering::Relaxed) {
-                            local_best_score = best_score_this_iter;
-                            break;
+                        local_best_score = best_score_this_iter;
+                        local_best_move = iteration.scored.first().map(|s| s.pv[0].clone());
+                        prev_score = local_best_score;
+                        if limits.multi_pv > 1 {
+                            // Try last iteration's best lines first.
+                            my_moves = iteration.scored.iter().map(|s| s.pv[0].clone()).collect();
+                        }
+                        local_lines = iteration.scored;
+                        local_lines.truncate(limits.multi_pv);
+                        completed_depth = d;
+                        if thread_idx == 0 {
+                            self.report_iteration(
+                                &local_board,
+                                player,
+                                d,
+                                &local_lines,
+                                start_time,
+                            );
+                        }
+                        break;
+                    }
+                    if self.stop_flag.load(Ordering::Relaxed) {
+                        break;
+                    }
+
+                    // Mate target reached: no need to look deeper.
+                    if let Some(target) = limits.mate
+                        && mate_in(local_best_score).is_some_and(|m| m > 0 && m <= target as i32)
+                    {
+                        break;
+                    }
+
+                    // Adaptive time: check if we should stop early (#24, #25)
+                    if let Some(base_time) = limits.time
+                        && limits.adaptive_time
+                        && d >= 5
+                        && thread_idx == 0
+                    {
+                        // Best move stability (#25)
+                        if local_best_move == prev_best_move {
+                            best_move_stable_count += 1;
+                        } else {
+                            best_move_stable_count = 0;
                         }
+                        prev_best_move = local_best_move.clone();
+
+                        // Falling eval factor (#24): if eval is dropping, use more time
+                        let eval_drop = (prev_iter_score - local_best_score).max(0);
+                        prev_iter_score = local_best_score;
+                        let falling_eval_factor = if eval_drop > 50 {
+                            1.5_f64 // eval falling significantly: 50% more time
+                        } else if eval_drop > 20 {
+                            1.2
+                        } else {
+                            1.0
+                        };
 
-                        if best_score_this_iter <= alpha {
-                            failed_low = true;
-                        }
+                        // Stability factor: stable best move → reduce time
+                        let stability_factor = match best_move_stable_count {
+                            0..=1 => 1.2, // unstable: more time
+                            2..=3 => 1.0, // normal
+                            4..=6 => 0.7, // stable: less time
+                            _ => 0.5,     // very stable: much less time
+                        };
 
-                        if d > 4 {
-                            if failed_low {
-                                beta = (alpha + beta) / 2;
-                                alpha -= delta;
-                                delta += delta / 3;
-                                continue;
-                            }
-                            if failed_high {
-                                beta += delta;
-                                delta += delta / 3;
-                                continue;
-                            }
+                        let adjusted_time =
+                            base_time.as_secs_f64() * falling_eval_factor * stability_factor;
+                        let elapsed = self.ponder.elapsed(start_time);
+                        if elapsed.is_some_and(|t| t.as_secs_f64() > adjusted_time * 0.6) {
+                            // Used 60% of adjusted time — stop iterating
+                            break;
                         }
-
-                        local_best_score = best_score_this_iter;
-                        local_best_move = best_move_this_iter;
-                        prev_score = local_best_score;
-                        break;
-                    }
-                    if self.stop_flag.load(Ordering::Relaxed) {
-                        break;
                     }
                 }
 
-                (
-                    local_best_move.unwrap_or(my_moves[0].clone()),
-                    local_best_score,
-                )
+                self.tables.lock().unwrap().push(tables);
+                ThreadOutcome {
+                    best_move: local_best_move.unwrap_or(my_moves[0].clone()),
+                    score: local_best_score,
+                    lines: local_lines,
+                    depth: completed_depth,
+                }
             })
             .collect();
 
         search_active.store(false, Ordering::Relaxed);
-        let total_nodes = self.nodes_searched.load(Ordering::Relaxed) as u64;
-        let completed = !self.stop_flag.load(Ordering::Relaxed);
-        let best = results.into_iter().max_by_key(|r| r.1).unwrap();
-        (best.0, best.1, total_nodes, completed)
+        results
     }
 
     /// Evaluate with correction history adjustment (#18).
@@ -390,7 +1264,8 @@ impl MinimaxBot {
         let pawn_hash = Self::pawn_hash(board);
         let correction = correction_history.get(pawn_hash);
         // Apply correction scaled down to avoid overshooting
-        (raw + correction / 16).clamp(-CHECKMATE_SCORE + 100, CHECKMATE_SCORE - 100)
+        // Keep static evaluations clear of the mate score range.
+        (raw + correction / 16).clamp(-MATE_THRESHOLD + 1, MATE_THRESHOLD - 1)
     }
 
     /// Update correction history based on search result vs static eval error.
@@ -402,7 +1277,7 @@ impl MinimaxBot {
         correction_history: &mut CorrectionHistory,
     ) {
         // Only update for non-mate scores and sufficient depth
-        if search_score.abs() > CHECKMATE_SCORE - 100 || depth < 2 {
+        if search_score.abs() >= MATE_THRESHOLD || depth < 2 {
             return;
         }
         let error = search_score - static_eval;
@@ -425,8 +1300,17 @@ impl MinimaxBot {
         hash
     }
 
+    /// The score of a draw for the side to move `ply` plies from the root.
+    fn draw_score(&self, ply: usize) -> i32 {
+        if ply.is_multiple_of(2) {
+            -self.contempt
+        } else {
+            self.contempt
+        }
+    }
+
     fn evaluate(&self, board: &Board, player_at_leaf: Option<Player>) -> i32 {
-        let score = Evaluator::evaluate(board);
+        let score = Evaluator::static_eval(board, &self.eval_params);
 
         if let Some(p) = player_at_leaf
             && p == Player::Black
@@ -440,12 +1324,21 @@ impl MinimaxBot {
         super::search_core::get_piece_value(board, idx)
     }
 
-    fn q_search(&self, board: &mut Board, alpha: i32, beta: i32, player: Player) -> i32 {
+    fn q_search(
+        &self,
+        board: &mut Board,
+        alpha: i32,
+        beta: i32,
+        player: Player,
+        ply: usize,
+    ) -> i32 {
         super::search_core::q_search(
             board,
             alpha,
             beta,
             player,
+            ply,
+            &self.eval_params,
             &self.nodes_searched,
             &self.stop_flag,
             Some(&self.tt),
@@ -461,15 +1354,15 @@ impl MinimaxBot {
         moves: &mut [Move],
         tt_move: Option<PackedMove>,
         killers: Option<&[Option<Move>; 2]>,
-        history: &[Vec<i32>],
+        history: &[PairTable],
         countermove: Option<&Move>,
-        cont_history: &[Vec<i32>],
+        cont_history: &[PairTable],
         capture_history: &[Vec<i32>],
         ancestors: &[Option<usize>; CONT_HIST_PLIES],
         player: Player,
-        pawn_history: &[Vec<i32>],
+        pawn_history: &[PairTable],
         pawn_hash: u64,
-        low_ply_history: &[Vec<i32>],
+        low_ply_history: &[PairTable],
         ply: usize,
     ) {
         moves.sort_by_cached_key(|mv| {
@@ -544,37 +1437,26 @@ impl MinimaxBot {
             }
 
             // Quiet move score: history + multi-ply continuation history + pawn + low-ply
-            let mut quiet_score: i32 = 0;
-            let total = board.total_cells();
-            let hist_idx = from_idx * total + to_idx;
-
-            if hist_idx < history[0].len() {
-                quiet_score += history[player as usize][hist_idx];
-            }
+            let mut quiet_score: i32 = history[player as usize].get(from_idx, to_idx);
 
             // Multi-ply continuation history (#11)
             for ply_back in 0..CONT_HIST_PLIES {
-                if let Some(anc_idx) = ancestors[ply_back] {
-                    let cont_idx = anc_idx * total + to_idx;
-                    let table_idx = ply_back * 2 + player as usize;
-                    if table_idx < cont_history.len() && cont_idx < cont_history[table_idx].len() {
-                        quiet_score += cont_history[table_idx][cont_idx];
-                    }
+                if let Some(anc_idx) = ancestors[ply_back]
+                    && let Some(table) = cont_history.get(ply_back * 2 + player as usize)
+                {
+                    quiet_score += table.get(anc_idx, to_idx);
                 }
             }
 
             // Pawn history (#12)
-            let ph_idx = (pawn_hash as usize & (PAWN_HIST_SIZE - 1)) * total + to_idx;
-            if ph_idx < pawn_history[player as usize].len() {
-                quiet_score += pawn_history[player as usize][ph_idx] / 2;
-            }
+            let pawn_bucket = pawn_hash as usize & (PAWN_HIST_SIZE - 1);
+            quiet_score += pawn_history[player as usize].get(pawn_bucket, to_idx) / 2;
 
             // Low-ply history (#13)
-            if ply < LOW_PLY_MAX {
-                let lp_table = ply * 2 + player as usize;
-                if lp_table < low_ply_history.len() && hist_idx < low_ply_history[lp_table].len() {
-                    quiet_score += low_ply_history[lp_table][hist_idx] / 2;
-                }
+            if ply < LOW_PLY_MAX
+                && let Some(table) = low_ply_history.get(ply * 2 + player as usize)
+            {
+                quiet_score += table.get(from_idx, to_idx) / 2;
             }
 
             -(200_000 + quiet_score)
@@ -591,23 +1473,26 @@ impl MinimaxBot {
         alpha: i32,
         beta: i32,
         player: Player,
+        ply: usize,
         start_time: Instant,
         allow_null: bool,
         killers: &mut [[Option<Move>; 2]],
-        history: &mut [Vec<i32>],
+        history: &mut [PairTable],
         countermoves: &mut [Vec<Option<Move>>],
-        cont_history: &mut [Vec<i32>],
+        cont_history: &mut [PairTable],
         capture_history: &mut [Vec<i32>],
         correction_history: &mut CorrectionHistory,
-        pawn_history: &mut [Vec<i32>],
-        low_ply_history: &mut [Vec<i32>],
+        pawn_history: &mut [PairTable],
+        low_ply_history: &mut [PairTable],
         prev_move_to_idx: Option<usize>,
+        pv_table: &mut Vec<Vec<Move>>,
     ) -> i32 {
         let mut stack: Vec<SearchFrame> = Vec::with_capacity(depth + 1);
         let mut return_value: i32 = 0;
 
         let mut initial = SearchFrame::new(depth, alpha, beta, player, allow_null);
         initial.ancestor_to_idx[0] = prev_move_to_idx;
+        initial.ply = ply;
         stack.push(initial);
 
         'outer: loop {
@@ -664,6 +1549,7 @@ impl MinimaxBot {
                             );
                             child.ancestor_to_idx =
                                 shift_ancestors(&stack[d].ancestor_to_idx, re_search_to);
+                            child.ply = stack[d].ply + 1;
                             stack.push(child);
                             continue 'outer;
                         }
@@ -687,13 +1573,14 @@ impl MinimaxBot {
                             );
                             child.ancestor_to_idx =
                                 shift_ancestors(&stack[d].ancestor_to_idx, re_search_to);
+                            child.ply = stack[d].ply + 1;
                             stack.push(child);
                             continue 'outer;
                         }
                     }
 
                     // Process the score
-                    let ply = self.depth.saturating_sub(stack[d].depth);
+                    let ply = self.limits.depth.saturating_sub(stack[d].depth);
                     self.process_move_result(
                         &mut stack[d],
                         board,
@@ -707,6 +1594,8 @@ impl MinimaxBot {
                         pawn_history,
                         low_ply_history,
                         ply,
+                        pv_table,
+                        d,
                     );
                     board.unmake_move(&mv, info);
 
@@ -763,6 +1652,7 @@ impl MinimaxBot {
                             true,
                         );
                         child.ancestor_to_idx = shift_ancestors(&stack[d].ancestor_to_idx, re_to);
+                        child.ply = stack[d].ply + 1;
                         stack.push(child);
                         continue 'outer;
                     }
@@ -771,7 +1661,7 @@ impl MinimaxBot {
                     let (mv, info) = stack[d].pending_unmake.take().unwrap();
                     let mv_clone = mv.clone();
 
-                    let ply = self.depth.saturating_sub(stack[d].depth);
+                    let ply = self.limits.depth.saturating_sub(stack[d].depth);
                     self.process_move_result(
                         &mut stack[d],
                         board,
@@ -785,6 +1675,8 @@ impl MinimaxBot {
                         pawn_history,
                         low_ply_history,
                         ply,
+                        pv_table,
+                        d,
                     );
                     board.unmake_move(&mv, info);
 
@@ -862,7 +1754,7 @@ impl MinimaxBot {
                     let score = -return_value;
                     let (mv, info) = stack[d].pending_unmake.take().unwrap();
 
-                    let ply = self.depth.saturating_sub(stack[d].depth);
+                    let ply = self.limits.depth.saturating_sub(stack[d].depth);
                     self.process_move_result(
                         &mut stack[d],
                         board,
@@ -876,6 +1768,8 @@ impl MinimaxBot {
                         pawn_history,
                         low_ply_history,
                         ply,
+                        pv_table,
+                        d,
                     );
                     board.unmake_move(&mv, info);
 
@@ -905,7 +1799,7 @@ impl MinimaxBot {
                     let score = -return_value;
                     let (mv, info) = stack[d].pending_unmake.take().unwrap();
 
-                    let ply = self.depth.saturating_sub(stack[d].depth);
+                    let ply = self.limits.depth.saturating_sub(stack[d].depth);
                     self.process_move_result(
                         &mut stack[d],
                         board,
@@ -919,6 +1813,8 @@ impl MinimaxBot {
                         pawn_history,
                         low_ply_history,
                         ply,
+                        pv_table,
+                        d,
                     );
                     board.unmake_move(&mv, info);
 
@@ -952,12 +1848,22 @@ impl MinimaxBot {
 
             // ========== INITIALIZATION ==========
             if stack[d].phase == SearchPhase::Init {
-                if self
-                    .nodes_searched
-                    .fetch_add(1, Ordering::Relaxed)
-                    .is_multiple_of(TIMEOUT_CHECK_INTERVAL)
-                    && start_time.elapsed() > self.time_limit
-                {
+                if pv_table.len() <= d {
+                    pv_table.resize_with(d + 1, Vec::new);
+                }
+                pv_table[d].clear();
+
+                // Frame d sits at ply d + 1 from the root.
+                if d + 1 > self.seldepth.load(Ordering::Relaxed) {
+                    self.seldepth.fetch_max(d + 1, Ordering::Relaxed);
+                }
+                let nodes = self.nodes_searched.fetch_add(1, Ordering::Relaxed);
+                let out_of_time = nodes.is_multiple_of(TIMEOUT_CHECK_INTERVAL)
+                    && self
+                        .limits
+                        .time
+                        .is_some_and(|t| self.ponder.elapsed(start_time).is_some_and(|e| e > t));
+                if out_of_time || self.limits.nodes.is_some_and(|n| nodes as u64 >= n) {
                     self.stop_flag.store(true, Ordering::Relaxed);
                     return_value = 0;
                     stack.pop();
@@ -975,10 +1881,21 @@ impl MinimaxBot {
                     continue;
                 }
 
+                // A repetition or the fifty-move rule ends the game drawn.
+                if board.is_repetition() || board.state.halfmove_clock >= 100 {
+                    return_value = self.draw_score(stack[d].ply);
+                    stack.pop();
+                    if stack.is_empty() {
+                        return return_value;
+                    }
+                    continue;
+                }
+
                 let hash = board.state.hash;
                 stack[d].hash = hash;
 
                 if let Some((tt_score, tt_depth, tt_flag, best_m)) = self.tt.get(hash) {
+                    let tt_score = score_from_tt(tt_score, stack[d].ply);
                     stack[d].tt_move = best_m;
                     if tt_depth as usize >= stack[d].depth {
                         match tt_flag {
@@ -1004,12 +1921,38 @@ impl MinimaxBot {
                     }
                 }
 
+                if let Some(tablebases) = &self.tablebases
+                    && let Some(value) = tablebases.probe(board, stack[d].player)
+                {
+                    return_value = match value {
+                        TbValue::Draw => self.draw_score(stack[d].ply),
+                        _ => value.score(stack[d].ply),
+                    };
+                    stack.pop();
+                    if stack.is_empty() {
+                        return return_value;
+                    }
+                    continue;
+                }
+                if let Some(syzygy) = &self.syzygy
+                    && let Some(wdl) = syzygy.probe(board, stack[d].player)
+                {
+                    return_value = match wdl {
+                        Wdl::Win | Wdl::Loss => wdl_score(wdl, stack[d].ply),
+                        _ => self.draw_score(stack[d].ply),
+                    };
+                    stack.pop();
+                    if stack.is_empty() {
+                        return return_value;
+                    }
+                    continue;
+                }
+
                 stack[d].original_alpha = stack[d].alpha;
 
                 // Mate distance pruning (#2): tighten bounds based on shortest possible mate
                 {
-                    let ply = self.depth - stack[d].depth;
-                    let mating_value = CHECKMATE_SCORE - ply as i32;
+                    let mating_value = mating_score(stack[d].ply);
                     if mating_value < stack[d].beta {
                         stack[d].beta = mating_value;
                         if stack[d].alpha >= mating_value {
@@ -1021,7 +1964,7 @@ impl MinimaxBot {
                             continue;
                         }
                     }
-                    let mated_value = -CHECKMATE_SCORE + ply as i32;
+                    let mated_value = mated_score(stack[d].ply);
                     if mated_value > stack[d].alpha {
                         stack[d].alpha = mated_value;
                         if stack[d].beta <= mated_value {
@@ -1036,8 +1979,13 @@ impl MinimaxBot {
                 }
 
                 if stack[d].depth == 0 {
-                    return_value =
-                        self.q_search(board, stack[d].alpha, stack[d].beta, stack[d].player);
+                    return_value = self.q_search(
+                        board,
+                        stack[d].alpha,
+                        stack[d].beta,
+                        stack[d].player,
+                        stack[d].ply,
+                    );
                     stack.pop();
                     if stack.is_empty() {
                         return return_value;
@@ -1064,6 +2012,7 @@ impl MinimaxBot {
                         stack[d].allow_null,
                     );
                     iid_child.ancestor_to_idx = stack[d].ancestor_to_idx;
+                    iid_child.ply = stack[d].ply;
                     stack.push(iid_child);
                     continue 'outer;
                 }
@@ -1102,8 +2051,10 @@ impl MinimaxBot {
                         SearchFrame::new(child_depth, child_alpha, child_beta, child_player, false);
                     // Null move verification (#4): set nmp_min_ply to prevent
                     // consecutive null moves near the verification boundary
-                    child.nmp_min_ply = self.depth - stack[d].depth + 3 * (stack[d].depth - r) / 4;
+                    child.nmp_min_ply =
+                        self.limits.depth - stack[d].depth + 3 * (stack[d].depth - r) / 4;
                     child.ancestor_to_idx = shift_ancestors(&stack[d].ancestor_to_idx, None);
+                    child.ply = stack[d].ply + 1;
                     stack.push(child);
                     continue 'outer;
                 }
@@ -1114,7 +2065,7 @@ impl MinimaxBot {
                 if !is_pv_here
                     && stack[d].depth >= 5
                     && !stack[d].in_check_at_entry
-                    && stack[d].beta.abs() < CHECKMATE_SCORE - 100
+                    && stack[d].beta.abs() < MATE_THRESHOLD
                 {
                     let probcut_beta = stack[d].beta + 200;
                     let pc_depth = stack[d].depth - 4;
@@ -1141,6 +2092,7 @@ impl MinimaxBot {
                                     -probcut_beta,
                                     -probcut_beta + 1,
                                     stack[d].player.opponent(),
+                                    stack[d].ply + 1,
                                 );
                                 if qval >= probcut_beta {
                                     // Verify with a shallow search
@@ -1150,6 +2102,8 @@ impl MinimaxBot {
                                         -probcut_beta,
                                         -probcut_beta + 1,
                                         stack[d].player.opponent(),
+                                        stack[d].ply + 1,
+                                        &self.eval_params,
                                         &self.nodes_searched,
                                         &self.stop_flag,
                                         Some(&self.tt),
@@ -1159,7 +2113,10 @@ impl MinimaxBot {
                                         // Store in TT at depth+1 (Stockfish does this)
                                         self.tt.store(
                                             stack[d].hash,
-                                            val - (probcut_beta - stack[d].beta),
+                                            score_to_tt(
+                                                val - (probcut_beta - stack[d].beta),
+                                                stack[d].ply,
+                                            ),
                                             (stack[d].depth + 1) as u8,
                                             Flag::LowerBound,
                                             stack[d].tt_move,
@@ -1184,7 +2141,13 @@ impl MinimaxBot {
                     && !stack[d].in_check_at_entry
                     && stack[d].static_eval + RAZOR_MARGIN[stack[d].depth] < stack[d].alpha
                 {
-                    let qval = self.q_search(board, stack[d].alpha, stack[d].beta, stack[d].player);
+                    let qval = self.q_search(
+                        board,
+                        stack[d].alpha,
+                        stack[d].beta,
+                        stack[d].player,
+                        stack[d].ply,
+                    );
                     if qval < stack[d].alpha {
                         return_value = qval;
                         stack.pop();
@@ -1197,20 +2160,7 @@ impl MinimaxBot {
 
                 // Staged move generation (#8): try TT move before generating all moves
                 if let Some(tm) = stack[d].tt_move {
-                    let from_coords = board.index_to_coords(tm.from_idx as usize);
-                    let to_coords = board.index_to_coords(tm.to_idx as usize);
-                    let promotion = match tm.promotion {
-                        1 => Some(PieceType::Queen),
-                        2 => Some(PieceType::Rook),
-                        3 => Some(PieceType::Bishop),
-                        4 => Some(PieceType::Knight),
-                        _ => None,
-                    };
-                    let tt_mv = Move {
-                        from: crate::domain::coordinate::Coordinate::new(from_coords),
-                        to: crate::domain::coordinate::Coordinate::new(to_coords),
-                        promotion,
-                    };
+                    let tt_mv = unpack_tt_move(board, tm);
                     if let Ok(info) = board.apply_move(&tt_mv) {
                         let illegal = if let Some(king_pos) =
                             board.get_king_coordinate(stack[d].player)
@@ -1253,6 +2203,7 @@ impl MinimaxBot {
                             );
                             child.ancestor_to_idx =
                                 shift_ancestors(&stack[d].ancestor_to_idx, move_to_idx);
+                            child.ply = stack[d].ply + 1;
                             stack.push(child);
                             continue 'outer;
                         } else {
@@ -1273,14 +2224,14 @@ impl MinimaxBot {
                     if let Some(king_pos) = board.get_king_coordinate(stack[d].player)
                         && Rules::is_square_attacked(board, &king_pos, stack[d].player.opponent())
                     {
-                        return_value = -CHECKMATE_SCORE + (self.depth - stack[d].depth) as i32;
+                        return_value = mated_score(stack[d].ply);
                         stack.pop();
                         if stack.is_empty() {
                             return return_value;
                         }
                         continue;
                     }
-                    return_value = 0;
+                    return_value = self.draw_score(stack[d].ply);
                     stack.pop();
                     if stack.is_empty() {
                         return return_value;
@@ -1318,7 +2269,7 @@ impl MinimaxBot {
                     None
                 };
 
-                let sort_ply = self.depth.saturating_sub(stack[d].depth);
+                let sort_ply = self.limits.depth.saturating_sub(stack[d].depth);
                 let sort_pawn_hash = Self::pawn_hash(board);
                 self.sort_moves(
                     board,
@@ -1371,6 +2322,7 @@ impl MinimaxBot {
                                     -stack[d].beta,
                                     -stack[d].beta + 1,
                                     stack[d].player.opponent(),
+                                    stack[d].ply + 1,
                                     start_time,
                                     false,
                                     killers,
@@ -1382,6 +2334,9 @@ impl MinimaxBot {
                                     pawn_history,
                                     low_ply_history,
                                     board.coords_to_index(&mv.to.values),
+                                    // Separate scratch: this call must not
+                                    // overwrite the lines of our own frames.
+                                    &mut Vec::new(),
                                 );
                                 if child_score >= stack[d].beta {
                                     cutoffs += 1;
@@ -1411,7 +2366,7 @@ impl MinimaxBot {
                     && let Some((tt_score, tt_depth, tt_flag, _)) = self.tt.get(stack[d].hash)
                     && tt_depth as usize >= stack[d].depth - 3
                     && (tt_flag == Flag::Exact || tt_flag == Flag::LowerBound)
-                    && tt_score.abs() < CHECKMATE_SCORE - 100
+                    && tt_score.abs() < MATE_THRESHOLD
                 {
                     stack[d].singular_beta = tt_score - 2 * stack[d].depth as i32;
                     stack[d].singular_tt_move = stack[d].tt_move;
@@ -1434,6 +2389,7 @@ impl MinimaxBot {
                         false, // no null move in verification
                     );
                     se_child.ancestor_to_idx = stack[d].ancestor_to_idx;
+                    se_child.ply = stack[d].ply;
                     stack.push(se_child);
                     // The singular verification child will search the full position.
                     // The TT move will get its TT cutoff; if others all fail low,
@@ -1543,11 +2499,8 @@ impl MinimaxBot {
                         // Adjust by history score (good history = less reduction)
                         let from_idx_h = board.coords_to_index(&mv.from.values).unwrap_or(0);
                         let to_idx_h = board.coords_to_index(&mv.to.values).unwrap_or(0);
-                        let hist_idx = from_idx_h * board.total_cells() + to_idx_h;
-                        if hist_idx < history[stack[d].player as usize].len() {
-                            let h = history[stack[d].player as usize][hist_idx];
-                            r -= (h / 2048).clamp(-2, 2);
-                        }
+                        let h = history[stack[d].player as usize].get(from_idx_h, to_idx_h);
+                        r -= (h / 2048).clamp(-2, 2);
 
                         reduction = r.max(0) as usize;
                         // Don't reduce into negative depth
@@ -1581,6 +2534,7 @@ impl MinimaxBot {
                         );
                         child.ancestor_to_idx =
                             shift_ancestors(&stack[d].ancestor_to_idx, move_to_idx);
+                        child.ply = stack[d].ply + 1;
                         stack.push(child);
                     } else {
                         // Scout/LMR search: null window
@@ -1597,6 +2551,7 @@ impl MinimaxBot {
                         );
                         child.ancestor_to_idx =
                             shift_ancestors(&stack[d].ancestor_to_idx, move_to_idx);
+                        child.ply = stack[d].ply + 1;
                         stack.push(child);
                     }
                     continue 'outer;
@@ -1605,9 +2560,9 @@ impl MinimaxBot {
                 // All moves exhausted
                 if stack[d].legal_count == 0 {
                     if stack[d].in_check {
-                        return_value = -CHECKMATE_SCORE + (self.depth - stack[d].depth) as i32;
+                        return_value = mated_score(stack[d].ply);
                     } else {
-                        return_value = 0;
+                        return_value = self.draw_score(stack[d].ply);
                     }
                     stack.pop();
                     if stack.is_empty() {
@@ -1645,13 +2600,15 @@ impl MinimaxBot {
         mv: &Move,
         score: i32,
         killers: &mut [[Option<Move>; 2]],
-        history: &mut [Vec<i32>],
+        history: &mut [PairTable],
         countermoves: &mut [Vec<Option<Move>>],
-        cont_history: &mut [Vec<i32>],
+        cont_history: &mut [PairTable],
         capture_history: &mut [Vec<i32>],
-        pawn_history: &mut [Vec<i32>],
-        low_ply_history: &mut [Vec<i32>],
+        pawn_history: &mut [PairTable],
+        low_ply_history: &mut [PairTable],
         ply: usize,
+        pv_table: &mut [Vec<Move>],
+        d: usize,
     ) {
         let is_capture = frame
             .pending_unmake
@@ -1668,6 +2625,14 @@ impl MinimaxBot {
 
         if score > frame.alpha {
             frame.alpha = score;
+            // Triangular PV: this move followed by the child's line.
+            let (head, tail) = pv_table.split_at_mut(d + 1);
+            let line = &mut head[d];
+            line.clear();
+            line.push(mv.clone());
+            if let Some(child) = tail.first() {
+                line.extend_from_slice(child);
+            }
         }
 
         // Track searched quiet moves for fail-low penalty (#14)
@@ -1681,38 +2646,28 @@ impl MinimaxBot {
             let bonus = ((121 * frame.depth as i32 - 75).min(932)).max(0);
             let from_idx = board.coords_to_index(&mv.from.values).unwrap_or(0);
             let to_idx = board.coords_to_index(&mv.to.values).unwrap_or(0);
-            let total = board.total_cells();
 
             // Bonus for the cutoff move (history gravity #29)
-            let hist_idx = from_idx * total + to_idx;
-            if hist_idx < history[frame.player as usize].len() {
-                update_history(&mut history[frame.player as usize][hist_idx], bonus);
-            }
+            history[frame.player as usize].update(from_idx, to_idx, bonus);
 
             // Multi-ply continuation history bonus (#11)
             for ply_back in 0..CONT_HIST_PLIES {
-                if let Some(anc_to) = frame.ancestor_to_idx[ply_back] {
-                    let cont_idx = anc_to * total + to_idx;
-                    let table_idx = ply_back * 2 + frame.player as usize;
-                    if table_idx < cont_history.len() && cont_idx < cont_history[table_idx].len() {
-                        update_history(&mut cont_history[table_idx][cont_idx], bonus);
-                    }
+                if let Some(anc_to) = frame.ancestor_to_idx[ply_back]
+                    && let Some(table) = cont_history.get_mut(ply_back * 2 + frame.player as usize)
+                {
+                    table.update(anc_to, to_idx, bonus);
                 }
             }
 
             // Pawn history bonus (#12)
-            let pawn_hash = Self::pawn_hash(board);
-            let ph_idx = (pawn_hash as usize & (PAWN_HIST_SIZE - 1)) * total + to_idx;
-            if ph_idx < pawn_history[frame.player as usize].len() {
-                update_history(&mut pawn_history[frame.player as usize][ph_idx], bonus);
-            }
+            let pawn_bucket = Self::pawn_hash(board) as usize & (PAWN_HIST_SIZE - 1);
+            pawn_history[frame.player as usize].update(pawn_bucket, to_idx, bonus);
 
             // Low-ply history bonus (#13)
-            if ply < LOW_PLY_MAX {
-                let lp_table = ply * 2 + frame.player as usize;
-                if lp_table < low_ply_history.len() && hist_idx < low_ply_history[lp_table].len() {
-                    update_history(&mut low_ply_history[lp_table][hist_idx], bonus);
-                }
+            if ply < LOW_PLY_MAX
+                && let Some(table) = low_ply_history.get_mut(ply * 2 + frame.player as usize)
+            {
+                table.update(from_idx, to_idx, bonus);
             }
 
             // Penalty for all previously searched quiets that didn't cause cutoff (#14)
@@ -1721,18 +2676,14 @@ impl MinimaxBot {
                 if f == from_idx && t == to_idx {
                     continue;
                 }
-                let idx = f * total + t;
-                if idx < history[frame.player as usize].len() {
-                    update_history(&mut history[frame.player as usize][idx], penalty);
-                }
+                history[frame.player as usize].update(f, t, penalty);
                 // Multi-ply cont history penalty
                 for ply_back in 0..CONT_HIST_PLIES {
-                    if let Some(anc_to) = frame.ancestor_to_idx[ply_back] {
-                        let cidx = anc_to * total + t;
-                        let ti = ply_back * 2 + frame.player as usize;
-                        if ti < cont_history.len() && cidx < cont_history[ti].len() {
-                            update_history(&mut cont_history[ti][cidx], penalty);
-                        }
+                    if let Some(anc_to) = frame.ancestor_to_idx[ply_back]
+                        && let Some(table) =
+                            cont_history.get_mut(ply_back * 2 + frame.player as usize)
+                    {
+                        table.update(anc_to, t, penalty);
                     }
                 }
             }
@@ -1796,7 +2747,7 @@ impl MinimaxBot {
 
         self.tt.store(
             frame.hash,
-            frame.best_score,
+            score_to_tt(frame.best_score, frame.ply),
             frame.depth as u8,
             flag,
             packed_move,
@@ -1860,6 +2811,9 @@ struct SearchFrame {
     nmp_min_ply: usize,
     /// Whether the TT move was already tried in the staged phase (#8)
     tt_move_tried: bool,
+    /// Distance from the root in plies, for mate scores. Verification
+    /// searches of the same position (IID, singular) keep their parent's.
+    ply: usize,
 }
 
 impl SearchFrame {
@@ -1895,254 +2849,14 @@ impl SearchFrame {
             static_eval: 0,
             nmp_min_ply: 0,
             tt_move_tried: false,
+            ply: 0,
         }
     }
 }
 
 impl PlayerStrategy for MinimaxBot {
     fn get_move(&mut self, board: &Board, player: Player) -> Option<Move> {
-        self.nodes_searched.store(0, Ordering::Relaxed);
-        self.stop_flag.store(false, Ordering::Relaxed);
-
-        let start_time = Instant::now();
-        let root_moves = Rules::generate_legal_moves(&mut board.clone(), player);
-        if root_moves.is_empty() {
-            return None;
-        }
-
-        let nodes_counter = self.nodes_searched.clone();
-        let stop_flag = self.stop_flag.clone();
-
-        let search_active = Arc::new(AtomicBool::new(true));
-        let search_active_clone = search_active.clone();
-
-        thread::spawn(move || {
-            let mut last_nodes = 0;
-            let mut last_time = Instant::now();
-
-            while search_active_clone.load(Ordering::Relaxed) {
-                thread::sleep(Duration::from_millis(500));
-
-                if stop_flag.load(Ordering::Relaxed) {
-                    break;
-                }
-
-                let current_nodes = nodes_counter.load(Ordering::Relaxed);
-                let now = Instant::now();
-                let duration = now.duration_since(last_time).as_secs_f64();
-
-                if duration > 0.0 {
-                    let nps = (current_nodes - last_nodes) as f64 / duration;
-                    let nps_fmt = if nps > 1_000_000.0 {
-                        format!("{:.2} MN/s", nps / 1_000_000.0)
-                    } else {
-                        format!("{:.2} kN/s", nps / 1_000.0)
-                    };
-
-                    print!(
-                        "\rinfo nodes {} nps {} time {:.1}s  ",
-                        current_nodes,
-                        nps_fmt,
-                        start_time.elapsed().as_secs_f32()
-                    );
-                    use std::io::Write;
-                    std::io::stdout().flush().unwrap();
-                }
-
-                last_nodes = current_nodes;
-                last_time = now;
-            }
-        });
-
-        let results: Vec<(Move, i32)> = (0..self.num_threads)
-            .into_par_iter()
-            .map(|thread_idx| {
-                let mut local_board = board.clone();
-                let mut local_best_move = None;
-                let mut local_best_score = -i32::MAX;
-
-                let mut my_moves = root_moves.clone();
-                if thread_idx > 0 {
-                    use rand::seq::SliceRandom;
-                    let mut rng = rand::thread_rng();
-                    my_moves.shuffle(&mut rng);
-                }
-
-                let mut prev_score = 0;
-                let mut killers = (0..=self.depth).map(|_| [None, None]).collect::<Vec<_>>();
-
-                let total_cells = local_board.total_cells();
-                let hist_size = total_cells * total_cells;
-                let mut history = vec![vec![0i32; hist_size], vec![0i32; hist_size]];
-                let mut countermoves = vec![vec![None; total_cells], vec![None; total_cells]];
-                let mut cont_history = vec![vec![0i32; hist_size]; CONT_HIST_PLIES * 2];
-                let cap_hist_size = total_cells * 6;
-                let mut capture_history =
-                    vec![vec![0i32; cap_hist_size], vec![0i32; cap_hist_size]];
-                let mut correction_history = CorrectionHistory::new();
-                let pawn_hist_entry_size = PAWN_HIST_SIZE * total_cells;
-                let mut pawn_history = vec![
-                    vec![0i32; pawn_hist_entry_size],
-                    vec![0i32; pawn_hist_entry_size],
-                ];
-                let mut low_ply_history = vec![vec![0i32; hist_size]; LOW_PLY_MAX * 2];
-
-                // Adaptive time management (#24, #25)
-                let mut best_move_stable_count: usize = 0;
-                let mut prev_best_move: Option<Move> = None;
-                let mut prev_iter_score: i32 = 0;
-                let base_time = self.time_limit;
-
-                // Depth staggering (#27): helper threads start at higher depths
-                // to diversify TT population. Thread 0 starts at 1, thread 1 at 2, etc.
-                let start_depth = if thread_idx == 0 {
-                    1
-                } else {
-                    1 + (thread_idx % 3)
-                };
-                for d in start_depth..=self.depth {
-                    let mut delta = 50;
-                    let mut alpha;
-                    let mut beta;
-
-                    if d > 4 {
-                        alpha = prev_score - delta;
-                        beta = prev_score + delta;
-                    } else {
-                        alpha = -i32::MAX;
-                        beta = i32::MAX;
-                    }
-
-                    loop {
-                        let mut best_score_this_iter = -i32::MAX;
-                        let mut best_move_this_iter = None;
-                        let mut alpha_inner = alpha;
-                        let mut failed_high = false;
-                        let mut failed_low = false;
-
-                        for mv in &my_moves {
-                            let mv_to_idx = local_board.coords_to_index(&mv.to.values);
-                            let info = local_board.apply_move(mv).unwrap();
-                            let score = -self.minimax(
-                                &mut local_board,
-                                d - 1,
-                                -beta,
-                                -alpha_inner,
-                                player.opponent(),
-                                start_time,
-                                true,
-                                &mut killers,
-                                &mut history,
-                                &mut countermoves,
-                                &mut cont_history,
-                                &mut capture_history,
-                                &mut correction_history,
-                                &mut pawn_history,
-                                &mut low_ply_history,
-                                mv_to_idx,
-                            );
-                            local_board.unmake_move(mv, info);
-
-                            if self.stop_flag.load(Ordering::Relaxed) {
-                                break;
-                            }
-
-                            if score > best_score_this_iter {
-                                best_score_this_iter = score;
-                                best_move_this_iter = Some(mv.clone());
-                            }
-                            if score > alpha_inner {
-                                alpha_inner = score;
-                            }
-                            if score >= beta {
-                                failed_high = true;
-                                break;
-                            }
-                        }
-
-                        if self.stop_flag.load(Ordering::Relaxed) {
-                            local_best_score = best_score_this_iter;
-                            break;
-                        }
-
-                        if best_score_this_iter <= alpha {
-                            failed_low = true;
-                        }
-
-                        if d > 4 {
-                            if failed_low {
-                                beta = (alpha + beta) / 2;
-                                alpha -= delta;
-                                delta += delta / 3;
-                                continue;
-                            }
-                            if failed_high {
-                                beta += delta;
-                                delta += delta / 3;
-                                continue;
-                            }
-                        }
-
-                        local_best_score = best_score_this_iter;
-                        local_best_move = best_move_this_iter;
-                        prev_score = local_best_score;
-                        break;
-                    }
-                    if self.stop_flag.load(Ordering::Relaxed) {
-                        break;
-                    }
-
-                    // Adaptive time: check if we should stop early (#24, #25)
-                    if d >= 5 && thread_idx == 0 {
-                        // Best move stability (#25)
-                        if local_best_move == prev_best_move {
-                            best_move_stable_count += 1;
-                        } else {
-                            best_move_stable_count = 0;
-                        }
-                        prev_best_move = local_best_move.clone();
-
-                        // Falling eval factor (#24): if eval is dropping, use more time
-                        let eval_drop = (prev_iter_score - local_best_score).max(0);
-                        prev_iter_score = local_best_score;
-                        let falling_eval_factor = if eval_drop > 50 {
-                            1.5_f64 // eval falling significantly: 50% more time
-                        } else if eval_drop > 20 {
-                            1.2
-                        } else {
-                            1.0
-                        };
-
-                        // Stability factor: stable best move → reduce time
-                        let stability_factor = match best_move_stable_count {
-                            0..=1 => 1.2, // unstable: more time
-                            2..=3 => 1.0, // normal
-                            4..=6 => 0.7, // stable: less time
-                            _ => 0.5,     // very stable: much less time
-                        };
-
-                        let adjusted_time =
-                            base_time.as_secs_f64() * falling_eval_factor * stability_factor;
-                        if start_time.elapsed().as_secs_f64() > adjusted_time * 0.6 {
-                            // Used 60% of adjusted time — stop iterating
-                            break;
-                        }
-                    }
-                }
-
-                (
-                    local_best_move.unwrap_or(my_moves[0].clone()),
-                    local_best_score,
-                )
-            })
-            .collect();
-
-        search_active.store(false, Ordering::Relaxed);
-
-        println!();
-
-        let best = results.into_iter().max_by_key(|r| r.1);
-
-        best.map(|(m, _)| m)
+        self.search(board, player, &SearchLimits::default())
+            .map(|result| result.best_move)
     }
 }
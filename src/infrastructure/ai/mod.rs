@@ -1,7 +1,31 @@
 pub mod eval;
+pub mod eval_params;
+pub mod handle;
+pub mod limits;
+pub mod mate;
+pub mod mcts;
+pub mod memory;
 pub mod minimax;
+pub mod nnue;
+pub mod nnue_trainer;
+pub mod observer;
+pub mod personality;
+pub mod result;
 pub mod search_core;
 pub mod see;
+pub mod skill;
 pub mod transposition;
+pub mod tuner;
 
+pub use eval_params::{EvalParamSet, EvalParams, EvalParamsError};
+pub use handle::SearchHandle;
+pub use limits::{Clock, SearchLimits};
+pub use mate::{MateResult, MateSolver, SolutionNode};
+pub use mcts::MctsBot;
+pub use memory::{MemoryError, MemoryEstimate, MemoryPlan};
 pub use minimax::MinimaxBot;
+pub use nnue::{Network, NnueAccumulator, NnueError};
+pub use observer::{SearchEvent, SearchInfo, SearchObserver};
+pub use personality::{Personality, PersonalityError};
+pub use result::SearchResult;
+pub use skill::{MAX_SKILL, Skill, SkillError};
@@ -0,0 +1,421 @@
+//! NNUE-style evaluation: one hidden layer over `(piece, cell)` features,
+//! whose first layer is updated move by move rather than recomputed.
+//!
+//! Each side views the board in its own terms: pieces are "ours" or
+//! "theirs", and Black's view is mirrored along every axis but the file, so
+//! both sides see themselves start at the low corner. A view's accumulator
+//! is the first-layer bias plus the weight row of each of its active
+//! features; a move adds and subtracts a few rows. The output layer reads
+//! White's accumulator then Black's through a clipped ReLU and gives
+//! centipawns from White's view, like [`Evaluator`](super::eval::Evaluator).
+//!
+//! Weights are quantised: the first layer by [`QA`], which is also the
+//! clipped ReLU's ceiling, and the output layer by [`QB`]. The inner loops
+//! are plain slice arithmetic over `i16` weights and `i32` accumulators,
+//! which the compiler turns into SIMD adds and multiplies.
+//!
+//! Networks are stored in the versioned `HCNN` format, little-endian: the
+//! magic `HCNN`, a `u16` version, `u8` dimension and side, a `u32` hidden
+//! size, then the feature weights (feature-major, `i16`), the feature biases
+//! (`i16`), the output weights (`i16`, White's view first) and the output
+//! bias (`i32`).
+
+use std::any::Any;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::domain::board::{Board, PieceListener};
+use crate::domain::models::{Piece, PieceType, Player};
+
+pub const MAGIC: &[u8; 4] = b"HCNN";
+pub const VERSION: u16 = 1;
+/// Scale of the first layer, and the clipped ReLU's ceiling.
+pub const QA: i32 = 255;
+/// Scale of the output layer.
+pub const QB: i32 = 64;
+/// Centipawns per unit of unquantised network output.
+pub const OUTPUT_SCALE: i32 = 400;
+
+/// Piece kinds per view: six of ours, then six of theirs.
+const KINDS: usize = 12;
+
+#[derive(Debug)]
+pub enum NnueError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    /// Layer sizes that do not match the header.
+    BadShape(String),
+    /// The network was trained for another board.
+    GeometryMismatch {
+        network: (usize, usize),
+        board: (usize, usize),
+    },
+}
+
+impl fmt::Display for NnueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NnueError::Io(e) => write!(f, "{e}"),
+            NnueError::BadMagic => write!(f, "not an HCNN network file"),
+            NnueError::UnsupportedVersion(v) => {
+                write!(f, "unsupported HCNN version {v} (expected {VERSION})")
+            }
+            NnueError::BadShape(message) => write!(f, "malformed network: {message}"),
+            NnueError::GeometryMismatch { network, board } => write!(
+                f,
+                "network is for {}D side {}, board is {}D side {}",
+                network.0, network.1, board.0, board.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NnueError {}
+
+impl From<io::Error> for NnueError {
+    fn from(e: io::Error) -> Self {
+        NnueError::Io(e)
+    }
+}
+
+/// Quantised weights for one board geometry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Network {
+    dimension: usize,
+    side: usize,
+    hidden: usize,
+    /// `feature_count() × hidden`, one row per feature.
+    feature_weights: Vec<i16>,
+    feature_bias: Vec<i16>,
+    /// `2 × hidden`: White's view, then Black's.
+    output_weights: Vec<i16>,
+    output_bias: i32,
+    /// Each cell's index in Black's mirrored view.
+    mirror: Vec<u32>,
+}
+
+impl Network {
+    /// Assemble a network from quantised layers, checking their sizes.
+    pub fn from_parts(
+        dimension: usize,
+        side: usize,
+        hidden: usize,
+        feature_weights: Vec<i16>,
+        feature_bias: Vec<i16>,
+        output_weights: Vec<i16>,
+        output_bias: i32,
+    ) -> Result<Self, NnueError> {
+        let total_cells = side.pow(dimension as u32);
+        let expected = [
+            (
+                "feature weights",
+                KINDS * total_cells * hidden,
+                feature_weights.len(),
+            ),
+            ("feature biases", hidden, feature_bias.len()),
+            ("output weights", 2 * hidden, output_weights.len()),
+        ];
+        for (layer, want, got) in expected {
+            if want != got {
+                return Err(NnueError::BadShape(format!(
+                    "{layer}: expected {want} values, got {got}"
+                )));
+            }
+        }
+        Ok(Self {
+            dimension,
+            side,
+            hidden,
+            feature_weights,
+            feature_bias,
+            output_weights,
+            output_bias,
+            mirror: mirror_table(dimension, side),
+        })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// Inputs per view: twelve piece kinds on every cell.
+    pub fn feature_count(&self) -> usize {
+        KINDS * self.mirror.len()
+    }
+
+    /// Input index of `piece` on cell `index` as `view` sees it.
+    pub fn feature(&self, view: Player, index: usize, piece: Piece) -> usize {
+        feature_index(&self.mirror, view, index, piece)
+    }
+
+    /// Fail unless the network was trained for `board`'s geometry.
+    pub fn check_board(&self, dimension: usize, side: usize) -> Result<(), NnueError> {
+        if (dimension, side) == (self.dimension, self.side) {
+            Ok(())
+        } else {
+            Err(NnueError::GeometryMismatch {
+                network: (self.dimension, self.side),
+                board: (dimension, side),
+            })
+        }
+    }
+
+    /// A copy of `board` that carries this network's accumulators, so that
+    /// [`Evaluator::static_eval`](super::eval::Evaluator::static_eval) on it
+    /// and on every position reached from it uses the network.
+    ///
+    /// # Panics
+    ///
+    /// If the network is for another board; see [`Self::check_board`].
+    pub fn attach(self: &Arc<Self>, board: &Board) -> Board {
+        self.check_board(board.dimension(), board.side())
+            .unwrap_or_else(|e| panic!("{e}"));
+        let mut attached = board.clone();
+        attached.listener = Some(Box::new(NnueAccumulator::new(self.clone(), board)));
+        attached
+    }
+
+    /// Score `board` from scratch, in centipawns from White's view.
+    pub fn evaluate(&self, board: &Board) -> i32 {
+        let mut values = vec![0; 2 * self.hidden];
+        self.refresh(board, &mut values);
+        self.output(&values)
+    }
+
+    fn refresh(&self, board: &Board, values: &mut [i32]) {
+        let (white, black) = values.split_at_mut(self.hidden);
+        for view in [white, black] {
+            for (v, &b) in view.iter_mut().zip(&self.feature_bias) {
+                *v = b as i32;
+            }
+        }
+        for index in 0..board.total_cells() {
+            if let Some(piece) = board.get_piece_at_index(index) {
+                self.apply(values, index, piece, 1);
+            }
+        }
+    }
+
+    /// Add (`sign` 1) or subtract (`sign` -1) the rows of `piece` on
+    /// `index` in both views.
+    fn apply(&self, values: &mut [i32], index: usize, piece: Piece, sign: i32) {
+        let (white, black) = values.split_at_mut(self.hidden);
+        for (view, acc) in [(Player::White, white), (Player::Black, black)] {
+            let start = self.feature(view, index, piece) * self.hidden;
+            let row = &self.feature_weights[start..start + self.hidden];
+            for (a, &w) in acc.iter_mut().zip(row) {
+                *a += sign * w as i32;
+            }
+        }
+    }
+
+    fn output(&self, values: &[i32]) -> i32 {
+        let sum: i64 = values
+            .iter()
+            .zip(&self.output_weights)
+            .map(|(&a, &w)| (a.clamp(0, QA) * w as i32) as i64)
+            .sum();
+        ((sum + self.output_bias as i64) * OUTPUT_SCALE as i64 / (QA * QB) as i64) as i32
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, NnueError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), NnueError> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    pub fn read(mut input: impl Read) -> Result<Self, NnueError> {
+        let mut header = [0u8; 12];
+        input.read_exact(&mut header).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => NnueError::BadMagic,
+            _ => NnueError::Io(e),
+        })?;
+        if &header[..4] != MAGIC {
+            return Err(NnueError::BadMagic);
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(NnueError::UnsupportedVersion(version));
+        }
+        let (dimension, side) = (header[6] as usize, header[7] as usize);
+        let hidden = u32::from_le_bytes(header[8..12].try_into().unwrap()) as usize;
+        let total_cells = side.pow(dimension as u32);
+
+        let mut read_i16s = |n: usize| -> Result<Vec<i16>, NnueError> {
+            let mut bytes = vec![0u8; 2 * n];
+            input.read_exact(&mut bytes).map_err(truncated)?;
+            Ok(bytes
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                .collect())
+        };
+        let feature_weights = read_i16s(KINDS * total_cells * hidden)?;
+        let feature_bias = read_i16s(hidden)?;
+        let output_weights = read_i16s(2 * hidden)?;
+        let mut bias = [0u8; 4];
+        input.read_exact(&mut bias).map_err(truncated)?;
+
+        Self::from_parts(
+            dimension,
+            side,
+            hidden,
+            feature_weights,
+            feature_bias,
+            output_weights,
+            i32::from_le_bytes(bias),
+        )
+    }
+
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&[self.dimension as u8, self.side as u8])?;
+        out.write_all(&(self.hidden as u32).to_le_bytes())?;
+        for layer in [
+            &self.feature_weights,
+            &self.feature_bias,
+            &self.output_weights,
+        ] {
+            let bytes: Vec<u8> = layer.iter().flat_map(|w| w.to_le_bytes()).collect();
+            out.write_all(&bytes)?;
+        }
+        out.write_all(&self.output_bias.to_le_bytes())
+    }
+}
+
+fn truncated(e: io::Error) -> NnueError {
+    match e.kind() {
+        io::ErrorKind::UnexpectedEof => NnueError::BadShape("file is truncated".to_string()),
+        _ => NnueError::Io(e),
+    }
+}
+
+/// Each cell's index with every axis but the file (axis 1) reversed.
+pub(crate) fn mirror_table(dimension: usize, side: usize) -> Vec<u32> {
+    let total_cells = side.pow(dimension as u32);
+    (0..total_cells)
+        .map(|index| {
+            let (mut rest, mut stride, mut mirrored) = (index, 1, 0);
+            for axis in 0..dimension {
+                let c = rest % side;
+                rest /= side;
+                let c = if axis == 1 { c } else { side - 1 - c };
+                mirrored += c * stride;
+                stride *= side;
+            }
+            mirrored as u32
+        })
+        .collect()
+}
+
+/// Input index of `piece` on `index` as `view` sees it; shared with the
+/// trainer.
+pub(crate) fn feature_index(mirror: &[u32], view: Player, index: usize, piece: Piece) -> usize {
+    let kind = match piece.piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    } + if piece.owner == view { 0 } else { 6 };
+    let cell = match view {
+        Player::White => index,
+        Player::Black => mirror[index] as usize,
+    };
+    kind * mirror.len() + cell
+}
+
+/// Both views' accumulators for every move made since the root, kept on the
+/// board as its [`PieceListener`].
+#[derive(Clone, Debug)]
+pub struct NnueAccumulator {
+    network: Arc<Network>,
+    /// One `2 × hidden` frame per ply, the current position last.
+    stack: Vec<i32>,
+}
+
+impl NnueAccumulator {
+    pub fn new(network: Arc<Network>, board: &Board) -> Self {
+        let mut stack = vec![0; 2 * network.hidden];
+        network.refresh(board, &mut stack);
+        Self { network, stack }
+    }
+
+    /// The accumulators `board` carries, if any.
+    pub fn of(board: &Board) -> Option<&Self> {
+        board.listener.as_ref()?.as_any().downcast_ref::<Self>()
+    }
+
+    pub fn network(&self) -> &Arc<Network> {
+        &self.network
+    }
+
+    /// The current position's score in centipawns from White's view.
+    pub fn evaluate(&self) -> i32 {
+        self.network.output(self.current())
+    }
+
+    /// The current frame: White's view, then Black's.
+    pub fn current(&self) -> &[i32] {
+        &self.stack[self.stack.len() - 2 * self.network.hidden..]
+    }
+
+    fn current_mut(&mut self) -> &mut [i32] {
+        let start = self.stack.len() - 2 * self.network.hidden;
+        &mut self.stack[start..]
+    }
+
+    fn update(&mut self, index: usize, piece: Piece, sign: i32) {
+        let network = self.network.clone();
+        network.apply(self.current_mut(), index, piece, sign);
+    }
+}
+
+impl PieceListener for NnueAccumulator {
+    fn begin_move(&mut self) {
+        let start = self.stack.len() - 2 * self.network.hidden;
+        self.stack.extend_from_within(start..);
+    }
+
+    fn undo_move(&mut self) {
+        // The root frame stays even if moves made before attaching are undone.
+        if self.stack.len() > 2 * self.network.hidden {
+            self.stack
+                .truncate(self.stack.len() - 2 * self.network.hidden);
+        }
+    }
+
+    fn piece_added(&mut self, index: usize, piece: Piece) {
+        self.update(index, piece, 1);
+    }
+
+    fn piece_removed(&mut self, index: usize, piece: Piece) {
+        self.update(index, piece, -1);
+    }
+
+    fn box_clone(&self) -> Box<dyn PieceListener> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,345 @@
+//! Train an [`nnue`](super::nnue) network on `HCTD` self-play data, in
+//! floating point on the CPU, then quantise it.
+//!
+//! Each position's target blends its game result with its search score:
+//! `lambda · result + (1 - lambda) · sigmoid(score / 400)`. The network's
+//! output goes through the same sigmoid and the mean squared error is
+//! minimised with Adam over shuffled mini-batches. Weights are kept within
+//! what their quantised `i16` forms can hold.
+
+use std::path::Path;
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rayon::prelude::*;
+
+use crate::domain::models::Player;
+use crate::infrastructure::ai::nnue::{self, Network, QA, QB};
+use crate::infrastructure::training_data::{TrainingDataError, TrainingDataReader, TrainingRecord};
+
+/// Centipawns per unit of the sigmoid's argument.
+const SCORE_SCALE: f32 = nnue::OUTPUT_SCALE as f32;
+/// Search scores beyond this are mate scores and are clamped to it.
+const MAX_SCORE: f32 = 2000.0;
+
+#[derive(Clone, Debug)]
+pub struct TrainerOptions {
+    /// Hidden neurons per view.
+    pub hidden: usize,
+    pub epochs: usize,
+    pub batch_size: usize,
+    pub learning_rate: f32,
+    /// Weight of the game result in each target, against the search score.
+    pub lambda: f32,
+    /// Seed for the initial weights and the shuffling.
+    pub seed: u64,
+}
+
+impl Default for TrainerOptions {
+    fn default() -> Self {
+        Self {
+            hidden: 64,
+            epochs: 10,
+            batch_size: 256,
+            learning_rate: 0.001,
+            lambda: 0.5,
+            seed: 0,
+        }
+    }
+}
+
+/// A position reduced to its active features and its target.
+#[derive(Clone, Debug)]
+pub struct Sample {
+    /// Feature indices in White's view, then in Black's.
+    pub features: [Vec<u32>; 2],
+    /// Expected score for White in `[0, 1]`.
+    pub target: f32,
+}
+
+/// The samples of an `HCTD` file with the board they are for.
+#[derive(Clone, Debug)]
+pub struct TrainingSet {
+    pub dimension: usize,
+    pub side: usize,
+    pub samples: Vec<Sample>,
+}
+
+impl TrainingSet {
+    pub fn load(path: impl AsRef<Path>, lambda: f32) -> Result<Self, TrainingDataError> {
+        let reader = TrainingDataReader::open(path)?;
+        let (dimension, side) = (reader.dimension(), reader.side());
+        let records = reader.collect::<Result<Vec<_>, _>>()?;
+        Self::from_records(dimension, side, &records, lambda)
+    }
+
+    pub fn from_records(
+        dimension: usize,
+        side: usize,
+        records: &[TrainingRecord],
+        lambda: f32,
+    ) -> Result<Self, TrainingDataError> {
+        let mirror = nnue::mirror_table(dimension, side);
+        let samples = records
+            .iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let (board, _) = record.position().map_err(|e| TrainingDataError::Corrupt {
+                    record: i,
+                    message: e.to_string(),
+                })?;
+                let (mut white, mut black) = (Vec::new(), Vec::new());
+                for index in 0..board.total_cells() {
+                    if let Some(piece) = board.get_piece_at_index(index) {
+                        let feature =
+                            |view| nnue::feature_index(&mirror, view, index, piece) as u32;
+                        white.push(feature(Player::White));
+                        black.push(feature(Player::Black));
+                    }
+                }
+                let score = (record.score as f32).clamp(-MAX_SCORE, MAX_SCORE);
+                let result = record.result.white_points() as f32;
+                let target = lambda * result + (1.0 - lambda) * sigmoid(score / SCORE_SCALE);
+                Ok(Sample {
+                    features: [white, black],
+                    target,
+                })
+            })
+            .collect::<Result<_, TrainingDataError>>()?;
+        Ok(Self {
+            dimension,
+            side,
+            samples,
+        })
+    }
+}
+
+/// One pass over the training set.
+#[derive(Clone, Copy, Debug)]
+pub struct TrainEpoch {
+    pub epoch: usize,
+    /// Mean squared error over the epoch's batches.
+    pub loss: f64,
+}
+
+/// Float weights laid out as in [`Network`], with Adam's moment estimates.
+pub struct NnueTrainer {
+    dimension: usize,
+    side: usize,
+    hidden: usize,
+    options: TrainerOptions,
+    /// Feature weights, feature biases, output weights, output bias.
+    weights: Vec<f32>,
+    first_moment: Vec<f32>,
+    second_moment: Vec<f32>,
+    steps: i32,
+    rng: StdRng,
+}
+
+impl NnueTrainer {
+    pub fn new(dimension: usize, side: usize, options: TrainerOptions) -> Self {
+        let hidden = options.hidden.max(1);
+        let features = 12 * side.pow(dimension as u32);
+        let mut rng = StdRng::seed_from_u64(options.seed);
+        let mut weights = vec![0.0; features * hidden + 3 * hidden + 1];
+        // Small random first layer; a few dozen pieces are summed per neuron.
+        for w in &mut weights[..features * hidden] {
+            *w = rng.gen_range(-0.05..0.05);
+        }
+        let output = features * hidden + hidden;
+        for w in &mut weights[output..output + 2 * hidden] {
+            *w = rng.gen_range(-0.5..0.5);
+        }
+        Self {
+            dimension,
+            side,
+            hidden,
+            first_moment: vec![0.0; weights.len()],
+            second_moment: vec![0.0; weights.len()],
+            weights,
+            options,
+            steps: 0,
+            rng,
+        }
+    }
+
+    fn feature_count(&self) -> usize {
+        12 * self.side.pow(self.dimension as u32)
+    }
+
+    /// Offsets of the feature biases, output weights and output bias.
+    fn offsets(&self) -> (usize, usize, usize) {
+        let bias = self.feature_count() * self.hidden;
+        (bias, bias + self.hidden, bias + 3 * self.hidden)
+    }
+
+    /// Both views' pre-activation accumulators.
+    fn accumulate(&self, sample: &Sample) -> Vec<f32> {
+        let (bias, _, _) = self.offsets();
+        let h = self.hidden;
+        let mut acc = Vec::with_capacity(2 * h);
+        for view in &sample.features {
+            let start = acc.len();
+            acc.extend_from_slice(&self.weights[bias..bias + h]);
+            for &f in view {
+                let row = &self.weights[f as usize * h..(f as usize + 1) * h];
+                for (a, &w) in acc[start..].iter_mut().zip(row) {
+                    *a += w;
+                }
+            }
+        }
+        acc
+    }
+
+    /// Network output (before the sigmoid) for pre-activations `acc`.
+    fn output(&self, acc: &[f32]) -> f32 {
+        let (_, out, out_bias) = self.offsets();
+        let sum: f32 = acc
+            .iter()
+            .zip(&self.weights[out..out_bias])
+            .map(|(&a, &w)| a.clamp(0.0, 1.0) * w)
+            .sum();
+        sum + self.weights[out_bias]
+    }
+
+    /// Mean squared error over `samples`.
+    pub fn loss(&self, samples: &[Sample]) -> f64 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = samples
+            .par_iter()
+            .map(|s| {
+                let predicted = sigmoid(self.output(&self.accumulate(s)));
+                ((predicted - s.target) as f64).powi(2)
+            })
+            .sum();
+        total / samples.len() as f64
+    }
+
+    /// Shuffle `samples` and take one Adam step per batch; returns the mean
+    /// loss seen over the epoch.
+    pub fn train_epoch(&mut self, samples: &mut [Sample]) -> f64 {
+        samples.shuffle(&mut self.rng);
+        let batch_size = self.options.batch_size.max(1);
+        let mut total = 0.0;
+        for batch in samples.chunks(batch_size) {
+            total += self.step(batch) * batch.len() as f64;
+        }
+        total / samples.len().max(1) as f64
+    }
+
+    /// Run `options.epochs` epochs, reporting after each.
+    pub fn train(&mut self, samples: &mut [Sample], mut on_epoch: impl FnMut(&TrainEpoch)) {
+        for epoch in 1..=self.options.epochs {
+            let loss = self.train_epoch(samples);
+            on_epoch(&TrainEpoch { epoch, loss });
+        }
+    }
+
+    /// One Adam step on `batch`; returns its mean loss.
+    fn step(&mut self, batch: &[Sample]) -> f64 {
+        let h = self.hidden;
+        let (bias, out, out_bias) = self.offsets();
+
+        // Forward passes and per-sample gradients in parallel, with respect
+        // to the output layer and to the accumulators.
+        let per_sample: Vec<(f64, f32, Vec<f32>, Vec<f32>)> = batch
+            .par_iter()
+            .map(|s| {
+                let acc = self.accumulate(s);
+                let predicted = sigmoid(self.output(&acc));
+                let error = predicted - s.target;
+                let d_out = 2.0 * error * predicted * (1.0 - predicted);
+                let activations: Vec<f32> = acc.iter().map(|a| a.clamp(0.0, 1.0)).collect();
+                let d_acc = acc
+                    .iter()
+                    .zip(&self.weights[out..out_bias])
+                    .map(|(&a, &w)| if a > 0.0 && a < 1.0 { d_out * w } else { 0.0 })
+                    .collect();
+                ((error as f64).powi(2), d_out, activations, d_acc)
+            })
+            .collect();
+
+        let mut gradient = vec![0.0f32; self.weights.len()];
+        let mut loss = 0.0;
+        for (sample, (sample_loss, d_out, activations, d_acc)) in batch.iter().zip(&per_sample) {
+            loss += sample_loss;
+            gradient[out_bias] += d_out;
+            for (g, &a) in gradient[out..out_bias].iter_mut().zip(activations) {
+                *g += d_out * a;
+            }
+            for (view, d_view) in sample.features.iter().zip(d_acc.chunks(h)) {
+                for (g, &d) in gradient[bias..bias + h].iter_mut().zip(d_view) {
+                    *g += d;
+                }
+                for &f in view {
+                    let row = &mut gradient[f as usize * h..(f as usize + 1) * h];
+                    for (g, &d) in row.iter_mut().zip(d_view) {
+                        *g += d;
+                    }
+                }
+            }
+        }
+
+        self.adam(&mut gradient, batch.len() as f32);
+        loss / batch.len() as f64
+    }
+
+    fn adam(&mut self, gradient: &mut [f32], batch_len: f32) {
+        const BETA1: f32 = 0.9;
+        const BETA2: f32 = 0.999;
+        const EPSILON: f32 = 1e-8;
+        self.steps += 1;
+        let lr = self.options.learning_rate * (1.0 - BETA2.powi(self.steps)).sqrt()
+            / (1.0 - BETA1.powi(self.steps));
+
+        let (_, out, _) = self.offsets();
+        // Largest magnitudes whose quantised forms fit an `i16`.
+        let first_limit = i16::MAX as f32 / QA as f32;
+        let output_limit = i16::MAX as f32 / QB as f32;
+        for (i, g) in gradient.iter_mut().enumerate() {
+            if *g == 0.0 && self.first_moment[i] == 0.0 {
+                continue;
+            }
+            let g = *g / batch_len;
+            let m = &mut self.first_moment[i];
+            let v = &mut self.second_moment[i];
+            *m = BETA1 * *m + (1.0 - BETA1) * g;
+            *v = BETA2 * *v + (1.0 - BETA2) * g * g;
+            let limit = if i < out { first_limit } else { output_limit };
+            self.weights[i] =
+                (self.weights[i] - lr * *m / (v.sqrt() + EPSILON)).clamp(-limit, limit);
+        }
+    }
+
+    /// The current weights, quantised.
+    pub fn network(&self) -> Network {
+        let (bias, out, out_bias) = self.offsets();
+        let quantise = |w: &[f32], scale: i32| -> Vec<i16> {
+            w.iter()
+                .map(|&w| {
+                    (w * scale as f32)
+                        .round()
+                        .clamp(i16::MIN as f32, i16::MAX as f32) as i16
+                })
+                .collect()
+        };
+        Network::from_parts(
+            self.dimension,
+            self.side,
+            self.hidden,
+            quantise(&self.weights[..bias], QA),
+            quantise(&self.weights[bias..out], QA),
+            quantise(&self.weights[out..out_bias], QB),
+            (self.weights[out_bias] * (QA * QB) as f32).round() as i32,
+        )
+        .expect("trainer layers match the network's shape")
+    }
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
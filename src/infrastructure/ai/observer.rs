@@ -0,0 +1,68 @@
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use crate::domain::models::Move;
+
+/// Snapshot of one root line from a completed iterative-deepening iteration.
+#[derive(Clone, Debug)]
+pub struct SearchInfo {
+    pub depth: usize,
+    /// Deepest ply reached by the main search, including extensions.
+    pub seldepth: usize,
+    /// 1-based rank of this line when searching several root moves (MultiPV).
+    pub multipv: usize,
+    /// Score from the perspective of the side to move, in centipawns.
+    pub score: i32,
+    pub pv: Vec<Move>,
+    pub nodes: u64,
+    pub nps: u64,
+    /// TT occupancy in permille, as reported by `LockFreeTT::hashfull`.
+    pub hashfull: u32,
+    pub time: Duration,
+}
+
+/// Events emitted while a search is running.
+#[derive(Clone, Debug)]
+pub enum SearchEvent {
+    /// Periodic node counter update (roughly every 500 ms).
+    Progress {
+        nodes: u64,
+        nps: u64,
+        time: Duration,
+    },
+    /// The main search thread finished an iteration; sent once per MultiPV line.
+    Iteration(SearchInfo),
+}
+
+/// Receives search progress. Called from search threads, so implementations
+/// should return quickly; anything slow belongs behind a channel.
+pub trait SearchObserver: Send + Sync {
+    fn on_event(&self, event: &SearchEvent);
+}
+
+impl<F> SearchObserver for F
+where
+    F: Fn(&SearchEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &SearchEvent) {
+        self(event)
+    }
+}
+
+/// Forward events to a channel. A disconnected receiver is ignored so a
+/// consumer may stop listening without affecting the search.
+impl SearchObserver for Sender<SearchEvent> {
+    fn on_event(&self, event: &SearchEvent) {
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Format a node rate for human-readable output (`1.25 MN/s`, `830.10 kN/s`).
+pub fn format_nps(nps: u64) -> String {
+    let nps = nps as f64;
+    if nps > 1_000_000.0 {
+        format!("{:.2} MN/s", nps / 1_000_000.0)
+    } else {
+        format!("{:.2} kN/s", nps / 1_000.0)
+    }
+}
@@ -0,0 +1,176 @@
+//! Playing styles for minimax bots.
+//!
+//! A personality is a preset over the evaluation weights plus a contempt.
+//! The preset scales the weights the bot was configured with, so tuning for
+//! a particular board carries over to every style. Contempt is how much
+//! worse than an equal position the bot scores a draw: positive values play
+//! on for a win, negative ones welcome a draw.
+//!
+//! Presets only change the classical evaluation; a bot evaluating with a
+//! network keeps just the contempt.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::ai::eval_params::EvalParams;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalityError(pub String);
+
+impl fmt::Display for PersonalityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&str> = Personality::ALL.iter().map(|p| p.name()).collect();
+        write!(
+            f,
+            "unknown personality '{}' (expected one of {})",
+            self.0,
+            names.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for PersonalityError {}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Personality {
+    /// The configured weights, unchanged.
+    #[default]
+    Balanced,
+    /// Goes for the enemy king: king tropism and king-zone contact count
+    /// double.
+    Attacking,
+    /// Plays for space, outposts, open files and a sound pawn structure.
+    Positional,
+    /// Values material above activity and king attacks.
+    Materialistic,
+    /// Trades pieces when ahead, already in the middlegame, and is content
+    /// with a draw.
+    Simplifying,
+}
+
+impl Personality {
+    pub const ALL: [Personality; 5] = [
+        Personality::Balanced,
+        Personality::Attacking,
+        Personality::Positional,
+        Personality::Materialistic,
+        Personality::Simplifying,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Personality::Balanced => "balanced",
+            Personality::Attacking => "attacking",
+            Personality::Positional => "positional",
+            Personality::Materialistic => "materialistic",
+            Personality::Simplifying => "simplifying",
+        }
+    }
+
+    /// Centipawns a draw is worse than an equal position for this style.
+    pub fn contempt(self) -> i32 {
+        match self {
+            Personality::Balanced => 0,
+            Personality::Attacking => 30,
+            Personality::Positional => 10,
+            Personality::Materialistic => 20,
+            Personality::Simplifying => -20,
+        }
+    }
+
+    /// `params` with this style's preset applied.
+    pub fn weights(self, params: &EvalParams) -> EvalParams {
+        let mut p = params.clone();
+        match self {
+            Personality::Balanced => {}
+            Personality::Attacking => {
+                scale(200, [&mut p.tropism_total_mg, &mut p.king_zone_contact_mg]);
+                scale(150, [&mut p.tropism_total_eg, &mut p.king_zone_contact_eg]);
+                scale(
+                    150,
+                    [&mut p.open_line_penalty_mg, &mut p.attacked_line_penalty_mg],
+                );
+            }
+            Personality::Positional => {
+                scale(300, [&mut p.space_bonus_mg, &mut p.space_bonus_eg]);
+                scale(
+                    200,
+                    [
+                        &mut p.outpost_knight_mg,
+                        &mut p.outpost_knight_eg,
+                        &mut p.outpost_bishop_mg,
+                        &mut p.outpost_bishop_eg,
+                    ],
+                );
+                scale(
+                    150,
+                    [
+                        &mut p.rook_open_file_bonus_mg,
+                        &mut p.rook_semi_open_file_bonus_mg,
+                        &mut p.isolated_pawn_penalty_mg,
+                        &mut p.isolated_pawn_penalty_eg,
+                        &mut p.doubled_pawn_penalty_mg,
+                        &mut p.doubled_pawn_penalty_eg,
+                    ],
+                );
+            }
+            Personality::Materialistic => {
+                scale(
+                    120,
+                    [
+                        &mut p.pawn_mg,
+                        &mut p.pawn_eg,
+                        &mut p.knight_mg,
+                        &mut p.knight_eg,
+                        &mut p.bishop_mg,
+                        &mut p.bishop_eg,
+                        &mut p.rook_mg,
+                        &mut p.rook_eg,
+                        &mut p.queen_mg,
+                        &mut p.queen_eg,
+                    ],
+                );
+                scale(
+                    75,
+                    [
+                        &mut p.tropism_total_mg,
+                        &mut p.tropism_total_eg,
+                        &mut p.king_zone_contact_mg,
+                        &mut p.king_zone_contact_eg,
+                    ],
+                );
+            }
+            Personality::Simplifying => {
+                p.trade_bonus_mg = p.trade_bonus_mg.max(p.trade_bonus_eg / 2);
+                scale(150, [&mut p.trade_bonus_eg]);
+            }
+        }
+        p
+    }
+}
+
+fn scale<const N: usize>(percent: i32, weights: [&mut i32; N]) {
+    for weight in weights {
+        *weight = *weight * percent / 100;
+    }
+}
+
+impl fmt::Display for Personality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Personality {
+    type Err = PersonalityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Personality::ALL
+            .into_iter()
+            .find(|p| p.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| PersonalityError(s.to_string()))
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use crate::domain::models::Move;
+use crate::infrastructure::ai::observer::SearchInfo;
+
+/// Score for delivering mate at the root. A mate found `n` plies from the
+/// root scores `CHECKMATE_SCORE - n`.
+pub const CHECKMATE_SCORE: i32 = 30000;
+
+/// Scores at least this far from zero are mate scores.
+pub(crate) const MATE_THRESHOLD: i32 = CHECKMATE_SCORE - 1000;
+
+/// Score of a node `ply` plies from the root whose side to move mates next
+/// move at best.
+pub fn mating_score(ply: usize) -> i32 {
+    CHECKMATE_SCORE - ply as i32
+}
+
+/// Score of a node `ply` plies from the root whose side to move is mated.
+pub fn mated_score(ply: usize) -> i32 {
+    -CHECKMATE_SCORE + ply as i32
+}
+
+/// Outcome of a completed search.
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub best_move: Move,
+    /// Score from the perspective of the side to move, in centipawns.
+    pub score: i32,
+    /// Last fully completed iteration.
+    pub depth: usize,
+    pub seldepth: usize,
+    pub nodes: u64,
+    /// Principal variation starting with `best_move`, checked for legality.
+    pub pv: Vec<Move>,
+    /// Moves to mate if the score is a mate score; see [`mate_in`].
+    pub mate: Option<i32>,
+    pub time: Duration,
+    /// The top `multi_pv` root lines of the final iteration, best first.
+    pub lines: Vec<SearchInfo>,
+    /// Played from the opening book without searching.
+    pub book: bool,
+}
+
+/// Convert a score into a mate distance in moves: positive when the side to
+/// move mates, negative when it gets mated, `None` for ordinary scores.
+pub fn mate_in(score: i32) -> Option<i32> {
+    if score >= MATE_THRESHOLD {
+        Some((CHECKMATE_SCORE - score + 1) / 2)
+    } else if score <= -MATE_THRESHOLD {
+        Some(-(CHECKMATE_SCORE + score) / 2)
+    } else {
+        None
+    }
+}
+
+/// A score for people: "mate in 3", "mated in 2", or pawns such as "+0.35".
+pub fn describe_score(score: i32) -> String {
+    match mate_in(score) {
+        Some(n) if n > 0 => format!("mate in {n}"),
+        Some(n) => format!("mated in {}", -n),
+        None => format!("{:+.2}", score as f64 / 100.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mate_in() {
+        assert_eq!(mate_in(CHECKMATE_SCORE - 1), Some(1));
+        assert_eq!(mate_in(CHECKMATE_SCORE - 3), Some(2));
+        assert_eq!(mate_in(-CHECKMATE_SCORE + 2), Some(-1));
+        assert_eq!(mate_in(-CHECKMATE_SCORE + 4), Some(-2));
+        assert_eq!(mate_in(250), None);
+        assert_eq!(mate_in(-250), None);
+    }
+
+    #[test]
+    fn test_describe_score() {
+        assert_eq!(describe_score(mating_score(9)), "mate in 5");
+        assert_eq!(describe_score(mated_score(4)), "mated in 2");
+        assert_eq!(describe_score(35), "+0.35");
+        assert_eq!(describe_score(-120), "-1.20");
+    }
+}
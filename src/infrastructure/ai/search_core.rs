@@ -5,8 +5,12 @@ use crate::domain::board::{Board, UnmakeInfo};
 use crate::domain::models::{Move, Player};
 use crate::domain::rules::Rules;
 use crate::infrastructure::ai::eval::Evaluator;
+use crate::infrastructure::ai::eval_params::EvalParams;
+use crate::infrastructure::ai::result::mated_score;
 use crate::infrastructure::ai::see::SEE;
-use crate::infrastructure::ai::transposition::{Flag, LockFreeTT, PackedMove};
+use crate::infrastructure::ai::transposition::{
+    Flag, LockFreeTT, PackedMove, score_from_tt, score_to_tt,
+};
 
 pub const VAL_PAWN: i32 = 100;
 pub const VAL_KNIGHT: i32 = 320;
@@ -53,11 +57,15 @@ struct QFrame {
 
 use smallvec::SmallVec;
 
+/// Quiescence search of a node `ply` plies from the root.
+#[allow(clippy::too_many_arguments)]
 pub fn q_search(
     board: &mut Board,
     alpha: i32,
     beta: i32,
     player: Player,
+    ply: usize,
+    params: &EvalParams,
     nodes_searched: &Arc<AtomicUsize>,
     stop_flag: &Arc<AtomicBool>,
     tt: Option<&Arc<LockFreeTT>>,
@@ -88,7 +96,8 @@ pub fn q_search(
 
             if score >= stack[depth].beta {
                 if let Some(t) = tt {
-                    t.store(stack[depth].hash, score, 0, Flag::LowerBound, None);
+                    let tt_score = score_to_tt(score, ply + depth);
+                    t.store(stack[depth].hash, tt_score, 0, Flag::LowerBound, None);
                 }
                 return_value = stack[depth].beta;
                 stack.pop();
@@ -124,6 +133,7 @@ pub fn q_search(
             if let Some(t) = tt
                 && let Some((tt_score, _, tt_flag, _)) = t.get(hash)
             {
+                let tt_score = score_from_tt(tt_score, ply + depth);
                 match tt_flag {
                     Flag::Exact => {
                         return_value = tt_score;
@@ -161,8 +171,7 @@ pub fn q_search(
                 // and skip stand-pat (being in check is not a valid "do nothing" option).
                 let legal = Rules::generate_legal_moves(board, stack[depth].player);
                 if legal.is_empty() {
-                    // Checkmate
-                    return_value = -30000;
+                    return_value = mated_score(ply + depth);
                     stack.pop();
                     if stack.is_empty() {
                         return return_value;
@@ -182,7 +191,7 @@ pub fn q_search(
                 stack[depth].moves = sorted;
                 stack[depth].move_idx = 0;
             } else {
-                let score_val = Evaluator::evaluate(board);
+                let score_val = Evaluator::static_eval(board, params);
                 let stand_pat = if stack[depth].player == Player::Black {
                     -score_val
                 } else {
@@ -299,6 +308,7 @@ struct ShallowFrame {
     pending_unmake: Option<(Move, UnmakeInfo)>,
 }
 
+/// Fixed-depth alpha-beta of a node `ply` plies from the root.
 #[allow(clippy::too_many_arguments)]
 pub fn minimax_shallow(
     board: &mut Board,
@@ -306,6 +316,8 @@ pub fn minimax_shallow(
     alpha: i32,
     beta: i32,
     player: Player,
+    ply: usize,
+    params: &EvalParams,
     nodes_searched: &Arc<AtomicUsize>,
     stop_flag: &Arc<AtomicBool>,
     tt: Option<&Arc<LockFreeTT>>,
@@ -345,7 +357,7 @@ pub fn minimax_shallow(
                 stack[d].alpha = score;
             }
             if stack[d].alpha >= stack[d].beta {
-                store_shallow_tt(board, &stack[d], tt);
+                store_shallow_tt(board, &stack[d], ply + d, tt);
                 return_value = stack[d].best_score;
                 stack.pop();
                 if stack.is_empty() {
@@ -373,6 +385,7 @@ pub fn minimax_shallow(
             if let Some(t) = tt
                 && let Some((tt_score, tt_depth, tt_flag, best_m)) = t.get(hash)
             {
+                let tt_score = score_from_tt(tt_score, ply + d);
                 if let Some(pm) = best_m {
                     stack[d].tt_move_coords = Some((pm.from_idx, pm.to_idx));
                 }
@@ -408,6 +421,8 @@ pub fn minimax_shallow(
                     stack[d].alpha,
                     stack[d].beta,
                     stack[d].player,
+                    ply + d,
+                    params,
                     nodes_searched,
                     stop_flag,
                     tt,
@@ -424,7 +439,7 @@ pub fn minimax_shallow(
                 if let Some(king_pos) = board.get_king_coordinate(stack[d].player)
                     && Rules::is_square_attacked(board, &king_pos, stack[d].player.opponent())
                 {
-                    return_value = -30000 + (100 - stack[d].depth as i32);
+                    return_value = mated_score(ply + d);
                     stack.pop();
                     if stack.is_empty() {
                         return return_value;
@@ -492,6 +507,8 @@ pub fn minimax_shallow(
                     child_alpha,
                     child_beta,
                     child_player,
+                    ply + d + 1,
+                    params,
                     nodes_searched,
                     stop_flag,
                     tt,
@@ -517,7 +534,7 @@ pub fn minimax_shallow(
         }
 
         // --- All moves exhausted ---
-        store_shallow_tt(board, &stack[d], tt);
+        store_shallow_tt(board, &stack[d], ply + d, tt);
         return_value = stack[d].best_score;
         stack.pop();
         if stack.is_empty() {
@@ -526,7 +543,7 @@ pub fn minimax_shallow(
     }
 }
 
-fn store_shallow_tt(board: &Board, frame: &ShallowFrame, tt: Option<&Arc<LockFreeTT>>) {
+fn store_shallow_tt(board: &Board, frame: &ShallowFrame, ply: usize, tt: Option<&Arc<LockFreeTT>>) {
     if let Some(t) = tt {
         let flag = if frame.best_score <= frame.original_alpha {
             Flag::UpperBound
@@ -559,7 +576,7 @@ fn store_shallow_tt(board: &Board, frame: &ShallowFrame, tt: Option<&Arc<LockFre
 
         t.store(
             frame.hash,
-            frame.best_score,
+            score_to_tt(frame.best_score, ply),
             frame.depth as u8,
             flag,
             packed,
@@ -0,0 +1,133 @@
+//! Weakened play for casual games.
+//!
+//! A skill level from 0 to [`MAX_SKILL`] makes a minimax bot play like a
+//! weaker player. Below full strength the search is capped in depth and
+//! nodes and scores several root lines (MultiPV); the bot then plays one of
+//! them rather than always the best, choosing by score plus random noise
+//! that grows as the level drops. At the lowest levels it also blunders now
+//! and then, deliberately playing the worst line it scored.
+//!
+//! Only searches that play a move are weakened; analysis (`infinite` or
+//! `mate`) always runs at full strength.
+
+use std::fmt;
+
+use rand::Rng;
+
+use crate::infrastructure::ai::limits::SearchLimits;
+use crate::infrastructure::ai::observer::SearchInfo;
+
+/// Full strength: nothing is weakened.
+pub const MAX_SKILL: u8 = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillError(pub u8);
+
+impl fmt::Display for SkillError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "skill level {} is not between 0 and {MAX_SKILL}", self.0)
+    }
+}
+
+impl std::error::Error for SkillError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Skill(u8);
+
+impl Skill {
+    pub fn new(level: u8) -> Result<Self, SkillError> {
+        if level > MAX_SKILL {
+            return Err(SkillError(level));
+        }
+        Ok(Self(level))
+    }
+
+    pub fn level(self) -> u8 {
+        self.0
+    }
+
+    /// Whether this level plays below full strength.
+    pub fn is_weakened(self) -> bool {
+        self.0 < MAX_SKILL
+    }
+
+    /// Deepest iteration searched: 1 at level 0, up to 10 at level 19.
+    pub fn max_depth(self) -> Option<usize> {
+        self.is_weakened().then(|| 1 + self.0 as usize / 2)
+    }
+
+    /// Nodes searched at most: 200 at level 0, doubling every two levels.
+    pub fn max_nodes(self) -> Option<u64> {
+        self.is_weakened()
+            .then(|| (200.0 * 2f64.powf(self.0 as f64 / 2.0)).round() as u64)
+    }
+
+    /// Root lines scored to choose between.
+    pub fn multi_pv(self) -> usize {
+        match self.0 {
+            MAX_SKILL => 1,
+            0..10 => 6,
+            _ => 4,
+        }
+    }
+
+    /// Largest random bonus, in centipawns, added to each line's score
+    /// before choosing.
+    pub fn noise(self) -> i32 {
+        (MAX_SKILL - self.0) as i32 * 15
+    }
+
+    /// Chance of playing the worst scored line outright.
+    pub fn blunder_chance(self) -> f64 {
+        10u8.saturating_sub(self.0) as f64 * 0.02
+    }
+
+    /// Rough Elo estimate on the standard 2D board, for display only: the
+    /// scale is anchored at 400 for level 0 and has not been calibrated
+    /// against rated players. `make calibrate-skill` plays even levels
+    /// against each other to check that each step is stronger. `None` at
+    /// full strength, which depends on the configured depth and time
+    /// instead.
+    pub fn elo(self) -> Option<u32> {
+        const ELO: [u32; MAX_SKILL as usize] = [
+            400, 520, 650, 770, 890, 970, 1050, 1160, 1280, 1410, 1550, 1650, 1760, 1830, 1900,
+            1970, 2050, 2150, 2240, 2340,
+        ];
+        ELO.get(self.0 as usize).copied()
+    }
+
+    /// `limits` within this level's caps.
+    pub fn limit(self, limits: &SearchLimits) -> SearchLimits {
+        let mut limits = limits.clone();
+        if let Some(depth) = self.max_depth() {
+            limits.depth = Some(limits.depth.map_or(depth, |d| d.min(depth)));
+        }
+        if let Some(nodes) = self.max_nodes() {
+            limits.nodes = Some(limits.nodes.map_or(nodes, |n| n.min(nodes)));
+        }
+        if self.is_weakened() {
+            limits.multi_pv = limits.multi_pv.max(self.multi_pv());
+        }
+        limits
+    }
+
+    /// The index of the line to play among `lines`, best first.
+    pub fn pick(self, lines: &[SearchInfo], rng: &mut impl Rng) -> usize {
+        if !self.is_weakened() || lines.len() < 2 {
+            return 0;
+        }
+        if rng.gen_bool(self.blunder_chance()) {
+            return lines.len() - 1;
+        }
+        let noise = self.noise();
+        (0..lines.len())
+            .max_by_key(|&i| lines[i].score + rng.gen_range(0..=noise))
+            .unwrap_or(0)
+    }
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        Self(MAX_SKILL)
+    }
+}
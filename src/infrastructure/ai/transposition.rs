@@ -1,4 +1,8 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+use crate::infrastructure::ai::result::CHECKMATE_SCORE;
+use crate::infrastructure::syzygy::TB_WIN_THRESHOLD;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Flag {
@@ -28,6 +32,36 @@ impl PackedMove {
     }
 }
 
+/// Convert a score at a node `ply` plies from the root into one relative to
+/// the node itself, so a mate or tablebase win stored from one path reads
+/// back correctly from another at a different ply. The `±i32::MAX` bounds
+/// pass through as-is.
+pub fn score_to_tt(score: i32, ply: usize) -> i32 {
+    if !is_ply_relative(score) {
+        score
+    } else if score > 0 {
+        score + ply as i32
+    } else {
+        score - ply as i32
+    }
+}
+
+/// Inverse of [`score_to_tt`] for a node `ply` plies from the root.
+pub fn score_from_tt(score: i32, ply: usize) -> i32 {
+    if !is_ply_relative(score) {
+        score
+    } else if score > 0 {
+        score - ply as i32
+    } else {
+        score + ply as i32
+    }
+}
+
+/// Mate and tablebase scores, which count plies from the root.
+fn is_ply_relative(score: i32) -> bool {
+    (TB_WIN_THRESHOLD..=CHECKMATE_SCORE).contains(&score.abs())
+}
+
 /// Number of entries per bucket (cluster). 3 entries = 48 bytes per bucket.
 const BUCKET_SIZE: usize = 3;
 
@@ -52,15 +86,13 @@ pub struct LockFreeTT {
     /// Number of buckets (power of 2).
     num_buckets: usize,
     bucket_mask: usize,
-    /// Current generation counter (0-63).
-    generation: u8,
+    /// Current generation counter (0-63), advanced once per search.
+    generation: AtomicU8,
 }
 
 impl LockFreeTT {
     pub fn new(size_mb: usize) -> Self {
-        // Each bucket = BUCKET_SIZE entries × 16 bytes = 48 bytes
-        let bytes = size_mb * 1024 * 1024;
-        let num_buckets = (bytes / (BUCKET_SIZE * 16)).next_power_of_two().max(1);
+        let num_buckets = Self::buckets_for(size_mb);
 
         let num_atomics = num_buckets * BUCKET_SIZE * 2;
         // Use zeroed allocation: AtomicU64(0) is all-zero bytes, so we can bulk-allocate
@@ -78,18 +110,41 @@ impl LockFreeTT {
             table,
             num_buckets,
             bucket_mask: num_buckets - 1,
-            generation: 0,
+            generation: AtomicU8::new(0),
         }
     }
 
-    /// Advance generation counter (call at start of each iterative deepening iteration).
-    pub fn new_search(&mut self) {
-        self.generation = (self.generation + 1) & GENERATION_MASK;
+    /// Bucket count for a table of at most `size_mb`: the largest power of
+    /// two that fits, so the table never exceeds its memory budget.
+    fn buckets_for(size_mb: usize) -> usize {
+        // Each bucket = BUCKET_SIZE entries × 16 bytes = 48 bytes
+        let fitting = (size_mb * 1024 * 1024 / (BUCKET_SIZE * 16)).max(1);
+        1 << fitting.ilog2()
+    }
+
+    /// Bytes allocated by `LockFreeTT::new(size_mb)`.
+    pub fn bytes_for(size_mb: usize) -> usize {
+        Self::buckets_for(size_mb) * BUCKET_SIZE * 16
+    }
+
+    /// Advance the generation counter. Call once at the start of each search
+    /// so entries from earlier moves are replaced first.
+    pub fn new_search(&self) {
+        let next = (self.generation() + 1) & GENERATION_MASK;
+        self.generation.store(next, Ordering::Relaxed);
     }
 
     /// Get the current generation.
     pub fn generation(&self) -> u8 {
-        self.generation
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Empty the table, e.g. for a new game.
+    pub fn clear(&self) {
+        self.table
+            .par_iter()
+            .for_each(|entry| entry.store(0, Ordering::Relaxed));
+        self.generation.store(0, Ordering::Relaxed);
     }
 
     /// Prefetch the bucket for this hash into L1 cache.
@@ -182,7 +237,7 @@ impl LockFreeTT {
         let bucket_idx = (hash as usize) & self.bucket_mask;
         let base = bucket_idx * BUCKET_SIZE * 2;
 
-        let new_data = Self::encode_data(score, depth, flag, best_move, is_pv, self.generation);
+        let new_data = Self::encode_data(score, depth, flag, best_move, is_pv, self.generation());
 
         // Find the best slot to replace:
         // 1. Empty slot
@@ -236,7 +291,7 @@ impl LockFreeTT {
 
     fn relative_age(&self, entry_gen: u8) -> u8 {
         // How many generations old is this entry? Handles wraparound.
-        (self.generation.wrapping_sub(entry_gen)) & GENERATION_MASK
+        (self.generation().wrapping_sub(entry_gen)) & GENERATION_MASK
     }
 
     fn extract_generation(data: u64) -> u8 {
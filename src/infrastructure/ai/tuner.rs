@@ -0,0 +1,292 @@
+//! Texel tuning: fit the evaluation weights to game outcomes.
+//!
+//! Each labelled position contributes `(result - sigmoid(eval))²`, where
+//! `sigmoid` maps centipawns to an expected score with a scale `K` fitted
+//! to the data first. Weights are then adjusted one at a time by a shrinking
+//! step for as long as the mean error falls. The positions should be quiet:
+//! the static evaluation is all that is scored.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use rayon::prelude::*;
+
+use crate::domain::board::Board;
+use crate::infrastructure::ai::eval::Evaluator;
+use crate::infrastructure::ai::eval_params::EvalParams;
+use crate::infrastructure::training_data::{self, TrainingDataError, TrainingDataReader};
+
+/// A position and the result of the game it was taken from.
+#[derive(Clone, Debug)]
+pub struct LabelledPosition {
+    pub board: Board,
+    /// 1.0 when White won, 0.5 for a draw, 0.0 when Black won.
+    pub result: f64,
+}
+
+#[derive(Debug)]
+pub enum TuneError {
+    Io(String, std::io::Error),
+    TrainingData(TrainingDataError),
+    Parse { line: usize, message: String },
+    MixedGeometry { line: usize },
+    NoPositions,
+}
+
+impl fmt::Display for TuneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuneError::Io(path, e) => write!(f, "{path}: {e}"),
+            TuneError::TrainingData(e) => write!(f, "{e}"),
+            TuneError::Parse { line, message } => write!(f, "line {line}: {message}"),
+            TuneError::MixedGeometry { line } => {
+                write!(f, "line {line}: board size differs from the first position")
+            }
+            TuneError::NoPositions => write!(f, "no labelled positions"),
+        }
+    }
+}
+
+impl std::error::Error for TuneError {}
+
+impl From<TrainingDataError> for TuneError {
+    fn from(e: TrainingDataError) -> Self {
+        TuneError::TrainingData(e)
+    }
+}
+
+/// Parse `<position> ; <result>` or the EPD form `<fen> c9 "<result>";`.
+///
+/// The position is HFEN when it starts with `<dim>x<side>`, FEN otherwise.
+/// Results are `1-0`, `1/2-1/2`, `0-1` or a number from 0 to 1, always from
+/// White's point of view.
+pub fn parse_labelled_position(line: &str) -> Result<LabelledPosition, String> {
+    let (position, result) = if let Some((position, rest)) = line.split_once(" c9 ") {
+        (
+            position,
+            rest.trim().trim_end_matches(';').trim_matches('"'),
+        )
+    } else if let Some((position, result)) = line.rsplit_once(';') {
+        (position, result)
+    } else {
+        return Err("expected '<position> ; <result>'".to_string());
+    };
+
+    let result = match result.trim() {
+        "1-0" => 1.0,
+        "0-1" => 0.0,
+        "1/2-1/2" => 0.5,
+        other => match other.parse::<f64>() {
+            Ok(r) if (0.0..=1.0).contains(&r) => r,
+            _ => return Err(format!("invalid result '{other}'")),
+        },
+    };
+
+    let position = position.trim();
+    let is_hfen = position
+        .split_whitespace()
+        .next()
+        .and_then(|geometry| geometry.split_once('x'))
+        .is_some_and(|(dim, side)| dim.parse::<usize>().is_ok() && side.parse::<usize>().is_ok());
+    let board = if is_hfen {
+        Board::from_hfen(position)
+            .map(|(board, _)| board)
+            .map_err(|e| e.to_string())?
+    } else if position.split_whitespace().count() == 4 {
+        // EPD leaves out the move counters.
+        Board::from_fen(&format!("{position} 0 1")).map_err(|e| e.to_string())?
+    } else {
+        Board::from_fen(position).map_err(|e| e.to_string())?
+    };
+    Ok(LabelledPosition { board, result })
+}
+
+/// Read labelled positions from an `HCTD` training data file, or from text
+/// with one position per line, skipping blank lines and `#` comments. Every
+/// position must be on the same size of board.
+pub fn read_labelled_positions(path: impl AsRef<Path>) -> Result<Vec<LabelledPosition>, TuneError> {
+    let path = path.as_ref();
+    let io_error = |e| TuneError::Io(path.display().to_string(), e);
+    let bytes = fs::read(path).map_err(io_error)?;
+
+    // Each position with its line, or record number for training data.
+    let numbered: Vec<(usize, LabelledPosition)> = if bytes.starts_with(training_data::MAGIC) {
+        let records = TrainingDataReader::new(bytes.as_slice())?.collect::<Result<Vec<_>, _>>()?;
+        records
+            .par_iter()
+            .enumerate()
+            .map(|(i, record)| {
+                let (board, _) = record.position().map_err(|e| TuneError::Parse {
+                    line: i + 1,
+                    message: e.to_string(),
+                })?;
+                let result = record.result.white_points();
+                Ok((i + 1, LabelledPosition { board, result }))
+            })
+            .collect::<Result<_, TuneError>>()?
+    } else {
+        let contents = String::from_utf8(bytes)
+            .map_err(|e| io_error(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+        let lines: Vec<(usize, &str)> = contents
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        lines
+            .par_iter()
+            .map(|&(line, text)| {
+                let position = parse_labelled_position(text)
+                    .map_err(|message| TuneError::Parse { line, message })?;
+                Ok((line, position))
+            })
+            .collect::<Result<_, TuneError>>()?
+    };
+
+    // Share one geometry between all boards rather than one per position.
+    let Some((_, first)) = numbered.first() else {
+        return Err(TuneError::NoPositions);
+    };
+    let (geo, zobrist) = (
+        Arc::clone(&first.board.geo),
+        Arc::clone(&first.board.zobrist),
+    );
+    numbered
+        .into_iter()
+        .map(|(line, mut position)| {
+            if position.board.dimension() != geo.dimension || position.board.side() != geo.side {
+                return Err(TuneError::MixedGeometry { line });
+            }
+            position.board.geo = Arc::clone(&geo);
+            position.board.zobrist = Arc::clone(&zobrist);
+            Ok(position)
+        })
+        .collect()
+}
+
+/// Expected score for White at `eval` centipawns.
+fn sigmoid(eval: i32, k: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * eval as f64 / 400.0))
+}
+
+/// One pass over the tuned weights.
+#[derive(Clone, Debug)]
+pub struct EpochReport {
+    pub epoch: usize,
+    pub step: i32,
+    pub error: f64,
+    /// Weights changed during the pass.
+    pub improved: usize,
+}
+
+pub struct Tuner<'a> {
+    positions: &'a [LabelledPosition],
+    k: f64,
+}
+
+impl<'a> Tuner<'a> {
+    pub fn new(positions: &'a [LabelledPosition]) -> Self {
+        Self { positions, k: 1.0 }
+    }
+
+    /// Scale of the sigmoid, fitted by [`Self::fit_scale`].
+    pub fn scale(&self) -> f64 {
+        self.k
+    }
+
+    /// Mean squared error of `params` over all positions.
+    pub fn error(&self, params: &EvalParams) -> f64 {
+        let k = self.k;
+        let total: f64 = self
+            .positions
+            .par_iter()
+            .map(|p| {
+                let predicted = sigmoid(Evaluator::evaluate(&p.board, params), k);
+                (p.result - predicted).powi(2)
+            })
+            .sum();
+        total / self.positions.len().max(1) as f64
+    }
+
+    /// Choose the sigmoid scale that best fits `params` as they stand, so
+    /// tuning changes the weights rather than the units.
+    pub fn fit_scale(&mut self, params: &EvalParams) -> f64 {
+        let evals: Vec<(i32, f64)> = self
+            .positions
+            .par_iter()
+            .map(|p| (Evaluator::evaluate(&p.board, params), p.result))
+            .collect();
+        let error = |k: f64| -> f64 {
+            evals
+                .iter()
+                .map(|&(eval, result)| (result - sigmoid(eval, k)).powi(2))
+                .sum()
+        };
+
+        // Golden-section search; the error is unimodal in K.
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut lo, mut hi) = (0.0, 10.0);
+        while hi - lo > 1e-4 {
+            let a = hi - ratio * (hi - lo);
+            let b = lo + ratio * (hi - lo);
+            if error(a) < error(b) {
+                hi = b;
+            } else {
+                lo = a;
+            }
+        }
+        self.k = (lo + hi) / 2.0;
+        self.k
+    }
+
+    /// Local search over the weights in `names`, starting at `initial_step`
+    /// and halving it whenever a pass changes nothing. Stops when the step
+    /// reaches zero or after `max_epochs` passes.
+    pub fn tune(
+        &self,
+        start: &EvalParams,
+        names: &[&str],
+        initial_step: i32,
+        max_epochs: usize,
+        mut on_epoch: impl FnMut(&EpochReport),
+    ) -> EvalParams {
+        let mut params = start.clone();
+        let mut best = self.error(&params);
+        let mut step = initial_step.max(1);
+
+        for epoch in 1..=max_epochs {
+            let mut improved = 0;
+            for name in names {
+                let Some(original) = params.get(name) else {
+                    continue;
+                };
+                for candidate in [original + step, original - step] {
+                    *params.get_mut(name).unwrap() = candidate;
+                    let error = self.error(&params);
+                    if error < best {
+                        best = error;
+                        improved += 1;
+                        break;
+                    }
+                    *params.get_mut(name).unwrap() = original;
+                }
+            }
+
+            on_epoch(&EpochReport {
+                epoch,
+                step,
+                error: best,
+                improved,
+            });
+            if improved == 0 {
+                step /= 2;
+                if step == 0 {
+                    break;
+                }
+            }
+        }
+        params
+    }
+}
@@ -0,0 +1,485 @@
+//! Opening books: weighted moves for positions keyed by Zobrist hash.
+//!
+//! Books for the 2D 8×8 board are Polyglot books, so books from Polyglot
+//! tools play here and ours play there: no header, then 16-byte big-endian
+//! entries sorted by key:
+//!
+//! | bytes | field                                                       |
+//! |-------|-------------------------------------------------------------|
+//! | 8     | position key                                                |
+//! | 2     | move: to file, to rank, from file, from rank and promotion, |
+//! |       | 3 bits each from the lowest                                 |
+//! | 2     | weight                                                      |
+//! | 4     | learn data, unused and kept as read                         |
+//!
+//! Promotions are 0 for none, then 1 knight, 2 bishop, 3 rook and 4 queen.
+//! Castling is written as the king taking its own rook, as Polyglot does.
+//! Keys are Polyglot's, computed by `shakmaty`.
+//!
+//! Every other board uses the `HCBK` format, little-endian: the magic `HCBK`,
+//! a `u16` version, `u8` dimension and side, then entries sorted by key of a
+//! `u64` key, from and to coordinates (`dimension` bytes each), a promotion
+//! byte as above, a `u16` weight and a `u32` learn value. Keys there are the
+//! board's own [`ZobristKeys`](crate::domain::zobrist::ZobristKeys) hash.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use rand::Rng;
+use shakmaty::fen::Fen;
+use shakmaty::zobrist::Zobrist64;
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Position};
+
+use crate::domain::board::Board;
+use crate::domain::board::notation;
+use crate::domain::coordinate::Coordinate;
+use crate::domain::models::{Move, PieceType, Player};
+use crate::domain::rules::Rules;
+use crate::infrastructure::training_data::GameOutcome;
+
+pub const MAGIC: &[u8; 4] = b"HCBK";
+pub const VERSION: u16 = 3;
+
+#[derive(Debug)]
+pub enum BookError {
+    Io(io::Error),
+    UnsupportedVersion(u16),
+    Corrupt(String),
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::Io(e) => write!(f, "{e}"),
+            BookError::UnsupportedVersion(v) => {
+                write!(f, "unsupported HCBK version {v} (expected {VERSION})")
+            }
+            BookError::Corrupt(message) => write!(f, "malformed book: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for BookError {}
+
+impl From<io::Error> for BookError {
+    fn from(e: io::Error) -> Self {
+        BookError::Io(e)
+    }
+}
+
+/// A book move for one position. Castling moves are stored as the king
+/// taking its own rook.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookEntry {
+    pub key: u64,
+    pub mv: Move,
+    pub weight: u16,
+    pub learn: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OpeningBook {
+    dimension: usize,
+    side: usize,
+    /// Sorted by key, heaviest move first within a key.
+    entries: Vec<BookEntry>,
+}
+
+impl OpeningBook {
+    pub fn new(dimension: usize, side: usize, mut entries: Vec<BookEntry>) -> Self {
+        entries.sort_by(|a, b| a.key.cmp(&b.key).then(b.weight.cmp(&a.weight)));
+        Self {
+            dimension,
+            side,
+            entries,
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    pub fn entries(&self) -> &[BookEntry] {
+        &self.entries
+    }
+
+    /// Number of distinct positions.
+    pub fn positions(&self) -> usize {
+        self.entries.chunk_by(|a, b| a.key == b.key).count()
+    }
+
+    /// Whether the book is stored in the Polyglot layout.
+    pub fn is_polyglot(&self) -> bool {
+        (self.dimension, self.side) == (2, 8)
+    }
+
+    /// The entries for `key`, heaviest first.
+    pub fn lookup(&self, key: u64) -> &[BookEntry] {
+        let start = self.entries.partition_point(|e| e.key < key);
+        let end = self.entries.partition_point(|e| e.key <= key);
+        &self.entries[start..end]
+    }
+
+    /// The legal book moves for `player` on `board`, with their weights.
+    /// Empty if the book is for another board.
+    pub fn moves(&self, board: &Board, player: Player) -> Vec<(Move, u16)> {
+        if (board.dimension(), board.side()) != (self.dimension, self.side) {
+            return Vec::new();
+        }
+        let entries = self.lookup(position_key(board, player));
+        if entries.is_empty() {
+            return Vec::new();
+        }
+        let legal = Rules::generate_legal_moves(&mut board.clone(), player);
+        entries
+            .iter()
+            .filter(|e| e.weight > 0)
+            .filter_map(|e| {
+                let mv = legal.iter().find(|m| book_form(board, m) == e.mv)?;
+                Some((mv.clone(), e.weight))
+            })
+            .collect()
+    }
+
+    /// Pick a book move at random. `variety` 1 plays each move in proportion
+    /// to its weight, larger values flatten the odds and 0 always plays the
+    /// heaviest move.
+    pub fn pick(
+        &self,
+        board: &Board,
+        player: Player,
+        variety: f64,
+        rng: &mut impl Rng,
+    ) -> Option<Move> {
+        let moves = self.moves(board, player);
+        if variety <= 0.0 {
+            return moves.into_iter().max_by_key(|(_, w)| *w).map(|(m, _)| m);
+        }
+        let odds: Vec<f64> = moves
+            .iter()
+            .map(|(_, w)| (*w as f64).powf(1.0 / variety))
+            .collect();
+        let mut roll = rng.r#gen::<f64>() * odds.iter().sum::<f64>();
+        for ((mv, _), p) in moves.iter().zip(&odds) {
+            if roll < *p {
+                return Some(mv.clone());
+            }
+            roll -= p;
+        }
+        moves.last().map(|(m, _)| m.clone())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BookError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BookError> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Read an `HCBK` book, or a Polyglot one if the magic is missing.
+    pub fn read(mut input: impl Read) -> Result<Self, BookError> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        if bytes.starts_with(MAGIC) {
+            Self::read_native(&bytes)
+        } else {
+            Self::read_polyglot(&bytes)
+        }
+    }
+
+    fn read_polyglot(bytes: &[u8]) -> Result<Self, BookError> {
+        if !bytes.len().is_multiple_of(16) {
+            return Err(BookError::Corrupt(
+                "Polyglot book size is not a multiple of 16 bytes".to_string(),
+            ));
+        }
+        let entries = bytes
+            .chunks_exact(16)
+            .map(|e| {
+                let mv = u16::from_be_bytes([e[8], e[9]]);
+                let square = |bits: u16| vec![((bits >> 3) & 7) as u8, (bits & 7) as u8];
+                Ok(BookEntry {
+                    key: u64::from_be_bytes(e[..8].try_into().unwrap()),
+                    mv: Move {
+                        from: Coordinate::new(square(mv >> 6)),
+                        to: Coordinate::new(square(mv)),
+                        promotion: decode_promotion((mv >> 12) as u8 & 7)?,
+                    },
+                    weight: u16::from_be_bytes([e[10], e[11]]),
+                    learn: u32::from_be_bytes(e[12..16].try_into().unwrap()),
+                })
+            })
+            .collect::<Result<_, BookError>>()?;
+        Ok(Self::new(2, 8, entries))
+    }
+
+    fn read_native(bytes: &[u8]) -> Result<Self, BookError> {
+        let truncated = || BookError::Corrupt("file is truncated".to_string());
+        let header = bytes.get(..8).ok_or_else(truncated)?;
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(BookError::UnsupportedVersion(version));
+        }
+        let (dimension, side) = (header[6] as usize, header[7] as usize);
+        let size = 8 + 2 * dimension + 1 + 2 + 4;
+        let body = &bytes[8..];
+        if !body.len().is_multiple_of(size) {
+            return Err(truncated());
+        }
+        let entries = body
+            .chunks_exact(size)
+            .map(|e| {
+                let (from, rest) = e[8..].split_at(dimension);
+                let (to, rest) = rest.split_at(dimension);
+                Ok(BookEntry {
+                    key: u64::from_le_bytes(e[..8].try_into().unwrap()),
+                    mv: Move {
+                        from: Coordinate::new(from.to_vec()),
+                        to: Coordinate::new(to.to_vec()),
+                        promotion: decode_promotion(rest[0])?,
+                    },
+                    weight: u16::from_le_bytes([rest[1], rest[2]]),
+                    learn: u32::from_le_bytes(rest[3..7].try_into().unwrap()),
+                })
+            })
+            .collect::<Result<_, BookError>>()?;
+        Ok(Self::new(dimension, side, entries))
+    }
+
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        if self.is_polyglot() {
+            for e in &self.entries {
+                let square = |c: &Coordinate| (c.values[0] as u16) << 3 | c.values[1] as u16;
+                let mv = square(&e.mv.to)
+                    | square(&e.mv.from) << 6
+                    | (encode_promotion(e.mv.promotion) as u16) << 12;
+                out.write_all(&e.key.to_be_bytes())?;
+                out.write_all(&mv.to_be_bytes())?;
+                out.write_all(&e.weight.to_be_bytes())?;
+                out.write_all(&e.learn.to_be_bytes())?;
+            }
+            return Ok(());
+        }
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&[self.dimension as u8, self.side as u8])?;
+        for e in &self.entries {
+            out.write_all(&e.key.to_le_bytes())?;
+            out.write_all(&e.mv.from.values)?;
+            out.write_all(&e.mv.to.values)?;
+            out.write_all(&[encode_promotion(e.mv.promotion)])?;
+            out.write_all(&e.weight.to_le_bytes())?;
+            out.write_all(&e.learn.to_le_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+fn encode_promotion(promotion: Option<PieceType>) -> u8 {
+    match promotion {
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(PieceType::Queen) => 4,
+        _ => 0,
+    }
+}
+
+fn decode_promotion(code: u8) -> Result<Option<PieceType>, BookError> {
+    Ok(match code {
+        0 => None,
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        p => return Err(BookError::Corrupt(format!("invalid promotion {p}"))),
+    })
+}
+
+/// The key of `board` with `player` to move: Polyglot's on the 2D 8×8 board,
+/// the board's own Zobrist hash elsewhere and for positions `shakmaty`
+/// rejects as illegal.
+pub fn position_key(board: &Board, player: Player) -> u64 {
+    if (board.dimension(), board.side()) == (2, 8)
+        && let Some(key) = polyglot_key(board, player)
+    {
+        return key;
+    }
+    board.state.hash
+}
+
+/// Polyglot hashes the en passant file only when a pawn of the side to move
+/// stands beside the pawn that just pushed, whether or not it may capture.
+fn polyglot_key(board: &Board, player: Player) -> Option<u64> {
+    let fen: Fen = board.to_fen(player).parse().ok()?;
+    let pos: Chess = match fen.into_position(CastlingMode::Standard) {
+        Ok(pos) => pos,
+        Err(e) => match e.ignore_invalid_castling_rights() {
+            Ok(pos) => pos,
+            Err(e) => e.ignore_invalid_ep_square().ok()?,
+        },
+    };
+    Some(pos.zobrist_hash::<Zobrist64>(EnPassantMode::PseudoLegal).0)
+}
+
+/// `mv` as the book stores it: castling becomes the king taking its rook,
+/// on the same files as `Board::apply_move` uses.
+fn book_form(board: &Board, mv: &Move) -> Move {
+    let is_king = board.get_piece(&mv.from).map(|p| p.piece_type) == Some(PieceType::King);
+    let (from, to) = (&mv.from.values, &mv.to.values);
+    let castles = is_king
+        && from[1].abs_diff(to[1]) == 2
+        && (0..from.len()).all(|axis| axis == 1 || from[axis] == to[axis]);
+    if !castles {
+        return mv.clone();
+    }
+    let mut rook = to.clone();
+    rook[1] = if to[1] > from[1] { 7 } else { 0 };
+    Move {
+        from: mv.from.clone(),
+        to: Coordinate::new(rook),
+        promotion: None,
+    }
+}
+
+/// Collects weighted moves from whole games. Each move played in the first
+/// `depth` plies scores 2 for a win of the side that played it, 1 for a draw
+/// and 0 for a loss, as Polyglot weights them; moves that only lost are left
+/// out of the book.
+#[derive(Clone, Debug)]
+pub struct BookBuilder {
+    dimension: usize,
+    side: usize,
+    depth: usize,
+    games: usize,
+    scores: HashMap<(u64, Move), u64>,
+}
+
+impl BookBuilder {
+    pub fn new(dimension: usize, side: usize, depth: usize) -> Self {
+        Self {
+            dimension,
+            side,
+            depth,
+            games: 0,
+            scores: HashMap::new(),
+        }
+    }
+
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Replay `moves` from the start position. Fails on the first illegal
+    /// move, leaving the builder unchanged.
+    pub fn add_game(&mut self, moves: &[Move], outcome: GameOutcome) -> Result<(), String> {
+        let mut board = Board::new(self.dimension, self.side);
+        let mut player = Player::White;
+        let mut found = Vec::new();
+        for (ply, mv) in moves.iter().take(self.depth).enumerate() {
+            let legal = Rules::generate_legal_moves(&mut board, player);
+            if !legal.contains(mv) {
+                return Err(format!(
+                    "ply {}: illegal move {}",
+                    ply + 1,
+                    notation::format_move(mv)
+                ));
+            }
+            let points = match (outcome, player) {
+                (GameOutcome::Draw, _) => 1,
+                (GameOutcome::WhiteWin, Player::White) | (GameOutcome::BlackWin, Player::Black) => {
+                    2
+                }
+                _ => 0,
+            };
+            found.push((
+                (position_key(&board, player), book_form(&board, mv)),
+                points,
+            ));
+            board
+                .apply_move(mv)
+                .map_err(|e| format!("ply {}: {e}", ply + 1))?;
+            player = player.opponent();
+        }
+        for (key, points) in found {
+            *self.scores.entry(key).or_default() += points;
+        }
+        self.games += 1;
+        Ok(())
+    }
+
+    /// The book, with weights scaled down if need be to fit a `u16`.
+    pub fn build(self) -> OpeningBook {
+        let max = self.scores.values().copied().max().unwrap_or(0);
+        let divisor = max.div_ceil(u16::MAX as u64).max(1);
+        let entries = self
+            .scores
+            .into_iter()
+            .filter(|(_, score)| *score > 0)
+            .map(|((key, mv), score)| BookEntry {
+                key,
+                mv,
+                weight: (score / divisor).max(1) as u16,
+                learn: 0,
+            })
+            .collect();
+        OpeningBook::new(self.dimension, self.side, entries)
+    }
+}
+
+/// A game record line: moves in coordinate notation separated by spaces,
+/// optionally ending with the result (`1-0`, `0-1`, `1/2-1/2`, or `*` when
+/// unknown, counted as a draw). Move numbers such as `1.` are skipped.
+pub fn parse_game(line: &str, dim: usize, side: usize) -> Result<(Vec<Move>, GameOutcome), String> {
+    let mut moves = Vec::new();
+    let mut outcome = GameOutcome::Draw;
+    for token in line.split_whitespace() {
+        let move_number = token
+            .strip_suffix('.')
+            .is_some_and(|n| n.bytes().all(|b| b.is_ascii_digit()));
+        match token {
+            "1-0" => outcome = GameOutcome::WhiteWin,
+            "0-1" => outcome = GameOutcome::BlackWin,
+            "1/2-1/2" | "*" => outcome = GameOutcome::Draw,
+            _ if move_number => {}
+            _ => {
+                let mv = notation::parse_move_unchecked(token, dim, side);
+                moves.push(mv.map_err(|e| e.to_string())?);
+            }
+        }
+    }
+    Ok((moves, outcome))
+}
+
+/// How a bot plays from a book.
+#[derive(Clone, Debug)]
+pub struct BookProbe {
+    pub book: Arc<OpeningBook>,
+    /// Plies from the start of the game after which the book is left.
+    pub depth: usize,
+    /// See [`OpeningBook::pick`].
+    pub variety: f64,
+}
+
+impl BookProbe {
+    /// A book move if the game is still within `depth` plies.
+    pub fn pick(&self, board: &Board, player: Player) -> Option<Move> {
+        if board.state.history.len() >= self.depth {
+            return None;
+        }
+        self.book
+            .pick(board, player, self.variety, &mut rand::thread_rng())
+    }
+}
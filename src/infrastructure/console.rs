@@ -1,7 +1,9 @@
 use crate::domain::board::Board;
-use crate::domain::coordinate::Coordinate;
+use crate::domain::board::notation;
 use crate::domain::models::{Move, Player};
 use crate::domain::services::PlayerStrategy;
+use crate::infrastructure::ai::observer::{SearchEvent, SearchObserver, format_nps};
+use crate::infrastructure::ai::result::describe_score;
 use std::io::{self, Write};
 
 pub struct HumanConsolePlayer;
@@ -16,69 +18,6 @@ impl HumanConsolePlayer {
     pub fn new() -> Self {
         Self
     }
-
-    fn parse_coordinate(input: &str, dim: usize, side: usize) -> Result<Coordinate, String> {
-        let mut remaining = input.trim();
-        let mut coords: Vec<u8> = vec![0; dim];
-
-        for d in (0..dim).rev() {
-            if remaining.is_empty() {
-                return Err(format!(
-                    "Insufficient parts for {}-dimensional coordinate",
-                    dim
-                ));
-            }
-
-            if d % 2 != 0 {
-                let end_idx = remaining
-                    .find(|c: char| !c.is_ascii_alphabetic())
-                    .unwrap_or(remaining.len());
-
-                if end_idx == 0 {
-                    return Err(format!(
-                        "Expected Letter for Dimension {}, found number/symbol",
-                        d + 1
-                    ));
-                }
-
-                let letter_part = &remaining[..end_idx];
-                remaining = &remaining[end_idx..];
-
-                let c = letter_part.chars().last().unwrap().to_ascii_uppercase();
-                let val = (c as u8).saturating_sub(b'A') as usize;
-
-                if val >= side {
-                    return Err(format!("Coordinate letter '{}' out of bounds", letter_part));
-                }
-                coords[d] = val as u8;
-            } else {
-                let end_idx = remaining
-                    .find(|c: char| !c.is_ascii_digit())
-                    .unwrap_or(remaining.len());
-
-                if end_idx == 0 {
-                    return Err(format!(
-                        "Expected Number for Dimension {}, found letter",
-                        d + 1
-                    ));
-                }
-
-                let number_part = &remaining[..end_idx];
-                remaining = &remaining[end_idx..];
-
-                let val: usize = number_part.parse().map_err(|_| "Invalid number")?;
-                if val == 0 || val > side {
-                    return Err(format!(
-                        "Coordinate number '{}' out of bounds (1-{})",
-                        val, side
-                    ));
-                }
-                coords[d] = (val - 1) as u8;
-            }
-        }
-
-        Ok(Coordinate::new(coords))
-    }
 }
 
 impl PlayerStrategy for HumanConsolePlayer {
@@ -110,8 +49,8 @@ impl PlayerStrategy for HumanConsolePlayer {
                 continue;
             }
 
-            let from_res = Self::parse_coordinate(parts[0], dim, side);
-            let to_res = Self::parse_coordinate(parts[1], dim, side);
+            let from_res = notation::parse_square(parts[0], dim, side);
+            let to_res = notation::parse_square(parts[1], dim, side);
 
             match (from_res, to_res) {
                 (Ok(from_coord), Ok(to_coord)) => {
@@ -139,3 +78,37 @@ impl PlayerStrategy for HumanConsolePlayer {
         }
     }
 }
+
+/// Renders search progress on stdout: a self-overwriting node counter while
+/// an iteration runs, and one line per completed iteration.
+pub struct ConsoleSearchObserver;
+
+impl SearchObserver for ConsoleSearchObserver {
+    fn on_event(&self, event: &SearchEvent) {
+        match event {
+            SearchEvent::Progress { nodes, nps, time } => {
+                print!(
+                    "\rinfo nodes {} nps {} time {:.1}s  ",
+                    nodes,
+                    format_nps(*nps),
+                    time.as_secs_f32()
+                );
+            }
+            SearchEvent::Iteration(info) => {
+                let pv: Vec<String> = info.pv.iter().map(notation::format_move).collect();
+                println!(
+                    "\rinfo depth {} seldepth {} score {} nodes {} nps {} hashfull {} time {:.1}s pv {}  ",
+                    info.depth,
+                    info.seldepth,
+                    describe_score(info.score),
+                    info.nodes,
+                    format_nps(info.nps),
+                    info.hashfull,
+                    info.time.as_secs_f32(),
+                    pv.join(" ")
+                );
+            }
+        }
+        io::stdout().flush().unwrap_or(());
+    }
+}
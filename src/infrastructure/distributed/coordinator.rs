@@ -4,6 +4,7 @@ use crate::config::AppConfig;
 use crate::domain::board::Board;
 use crate::domain::models::{Move, Player};
 use crate::domain::rules::Rules;
+use crate::infrastructure::ai::result::describe_score;
 
 use super::discovery::WorkerDiscovery;
 use super::proto::SearchRequest;
@@ -30,7 +31,8 @@ impl DistributedSearch {
 
     /// Execute a distributed search across available workers.
     /// All moves are partitioned across remote workers; the gateway does no local search.
-    pub async fn search(&self, board: &Board, player: Player) -> Option<Move> {
+    /// `game_id` lets workers reuse their search tables between moves of a game.
+    pub async fn search(&self, board: &Board, player: Player, game_id: &str) -> Option<Move> {
         let root_moves_sv = Rules::generate_legal_moves(&mut board.clone(), player);
         if root_moves_sv.is_empty() {
             return None;
@@ -52,13 +54,21 @@ impl DistributedSearch {
 
         let chunks = partition_moves(root_moves, workers.len());
 
-        // Serialize board once
-        let board_data = bincode::serialize(board).expect("Failed to serialize board");
-        let player_str = match player {
-            Player::White => "white",
-            Player::Black => "black",
+        // Everything but the moves is the same for every worker.
+        let request = SearchRequest {
+            board_data: bincode::serialize(board).expect("Failed to serialize board"),
+            player: match player {
+                Player::White => "white",
+                Player::Black => "black",
+            }
+            .to_string(),
+            moves_data: Vec::new(),
+            depth: self.config.minimax.depth as u32,
+            time_limit_ms: self.config.compute.minutes * 60 * 1000,
+            memory_mb: self.config.compute.memory as u32,
+            num_threads: self.config.compute.concurrency as u32,
+            game_id: game_id.to_string(),
         };
-        let time_limit_ms = self.config.compute.minutes * 60 * 1000;
 
         // Spawn remote searches
         let mut remote_handles = Vec::new();
@@ -68,25 +78,10 @@ impl DistributedSearch {
                 continue;
             }
 
-            let bd = board_data.clone();
             let addr = worker_addr.clone();
-            let depth = self.config.minimax.depth as u32;
-            let memory = self.config.compute.memory as u32;
-            let threads = self.config.compute.concurrency as u32;
-            let player_s = player_str.to_string();
-
+            let request = request.clone();
             remote_handles.push(tokio::spawn(async move {
-                remote_search(
-                    addr,
-                    bd,
-                    player_s,
-                    chunk,
-                    depth,
-                    time_limit_ms,
-                    memory,
-                    threads,
-                )
-                .await
+                remote_search(addr, chunk, request).await
             }));
         }
 
@@ -104,24 +99,20 @@ impl DistributedSearch {
 
         // Pick best move across all results
         results.into_iter().max_by_key(|r| r.1).map(|(mv, score)| {
-            eprintln!("[coordinator] Best move score: {}", score);
+            eprintln!("[coordinator] Best move score: {}", describe_score(score));
             mv
         })
     }
 }
 
-/// Execute a search on a remote worker via gRPC.
+/// Execute a search of `moves` on a remote worker via gRPC. `request`
+/// carries the rest of the search; its `moves_data` is filled in here.
 async fn remote_search(
     addr: String,
-    board_data: Vec<u8>,
-    player: String,
     moves: Vec<Move>,
-    depth: u32,
-    time_limit_ms: u64,
-    memory_mb: u32,
-    num_threads: u32,
+    mut request: SearchRequest,
 ) -> Option<(Move, i32)> {
-    let moves_data = bincode::serialize(&moves).expect("Failed to serialize moves");
+    request.moves_data = bincode::serialize(&moves).expect("Failed to serialize moves");
 
     eprintln!(
         "[coordinator] Sending {} moves to worker {}",
@@ -137,15 +128,7 @@ async fn remote_search(
         }
     };
 
-    let request = tonic::Request::new(SearchRequest {
-        board_data,
-        player,
-        moves_data,
-        depth,
-        time_limit_ms,
-        memory_mb,
-        num_threads,
-    });
+    let request = tonic::Request::new(request);
 
     match client.search_moves(request).await {
         Ok(response) => {
@@ -1,3 +1,5 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use tonic::{Request, Response, Status};
@@ -7,10 +9,58 @@ use super::proto::{PingRequest, PingResponse, SearchRequest, SearchResponse};
 use crate::config::AppConfig;
 use crate::domain::board::Board;
 use crate::domain::models::{Move, Player};
-use crate::infrastructure::ai::MinimaxBot;
+use crate::infrastructure::ai::observer::{SearchEvent, format_nps};
+use crate::infrastructure::ai::result::describe_score;
+use crate::infrastructure::ai::{MemoryError, MinimaxBot};
+
+/// Log search progress to stderr, prefixed like the rest of the worker output.
+fn worker_observer(event: &SearchEvent) {
+    match event {
+        SearchEvent::Progress { nodes, nps, time } => {
+            eprint!(
+                "\r[worker] nodes {} nps {} time {:.1}s  ",
+                nodes,
+                format_nps(*nps),
+                time.as_secs_f32()
+            );
+        }
+        SearchEvent::Iteration(info) => {
+            eprintln!(
+                "\r[worker] depth {} seldepth {} score {} nodes {} nps {}  ",
+                info.depth,
+                info.seldepth,
+                describe_score(info.score),
+                info.nodes,
+                format_nps(info.nps)
+            );
+        }
+    }
+    std::io::stderr().flush().unwrap_or(());
+}
+
+/// A bot kept between requests so that its transposition and history
+/// tables carry over from one move of a game to the next.
+struct CachedBot {
+    game_id: String,
+    /// Search parameters and board geometry, which the bot's memory plan
+    /// depends on.
+    params: (usize, Duration, usize, usize, usize, usize),
+    bot: MinimaxBot,
+}
 
 pub struct SearchWorkerService {
-    pub config: AppConfig,
+    config: AppConfig,
+    /// `None` while the bot is busy; concurrent requests get a fresh one.
+    cached: Arc<Mutex<Option<CachedBot>>>,
+}
+
+impl SearchWorkerService {
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            config,
+            cached: Arc::new(Mutex::new(None)),
+        }
+    }
 }
 
 #[tonic::async_trait]
@@ -50,12 +100,44 @@ impl SearchWorker for SearchWorkerService {
             req.time_limit_ms
         );
 
+        let (dimension, side) = (board.dimension(), board.side());
+        let params = (depth, time_limit, memory_mb, num_threads, dimension, side);
+        let eval_params = self.config.eval_params(dimension, side);
+        let game_id = req.game_id;
+        let cached = self.cached.clone();
         let result = tokio::task::spawn_blocking(move || {
-            let mut bot = MinimaxBot::new_from_params(depth, time_limit, memory_mb, num_threads);
-            bot.search_subset(&board, player, moves)
+            let reusable = cached.lock().unwrap().take().filter(|c| c.params == params);
+            let mut entry = match reusable {
+                Some(entry) if entry.game_id == game_id => entry,
+                Some(entry) => {
+                    entry.bot.new_game();
+                    CachedBot { game_id, ..entry }
+                }
+                None => {
+                    let mut bot = MinimaxBot::try_new_from_params(
+                        depth,
+                        time_limit,
+                        memory_mb,
+                        num_threads,
+                        dimension,
+                        side,
+                    )?;
+                    bot.set_eval_params(eval_params);
+                    bot.set_observer(Some(Arc::new(worker_observer)));
+                    CachedBot {
+                        game_id,
+                        params,
+                        bot,
+                    }
+                }
+            };
+            let result = entry.bot.search_subset(&board, player, moves);
+            *cached.lock().unwrap() = Some(entry);
+            Ok::<_, MemoryError>(result)
         })
         .await
-        .map_err(|e| Status::internal(format!("Search task failed: {}", e)))?;
+        .map_err(|e| Status::internal(format!("Search task failed: {}", e)))?
+        .map_err(|e| Status::resource_exhausted(e.to_string()))?;
 
         let (best_move, score, nodes, completed) = result;
 
@@ -87,9 +169,7 @@ pub async fn start_grpc_worker(config: AppConfig) {
         .parse()
         .expect("Invalid gRPC address");
 
-    let service = SearchWorkerService {
-        config: config.clone(),
-    };
+    let service = SearchWorkerService::new(config.clone());
 
     eprintln!("[worker] Starting gRPC worker on {}", addr);
 
@@ -0,0 +1,317 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::EngineConfig;
+use crate::domain::board::Board;
+use crate::domain::board::notation;
+use crate::domain::models::{Move, Player};
+use crate::domain::services::PlayerStrategy;
+
+/// How long to wait for `uciok` / `readyok` during the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A `PlayerStrategy` backed by an external engine process.
+///
+/// 2D 8×8 games are driven over plain UCI (`position fen`). Every other
+/// geometry uses the N-D extension spoken by `hyperchess-uci`: the engine must
+/// advertise `Dimension` and `Side` spin options, receives the geometry via
+/// `setoption`, positions via `position hfen`, and replies with coordinate
+/// notation moves (see [`notation`]).
+///
+/// A crashed engine is restarted and the request retried once. An engine that
+/// overruns the move time is sent `stop`; if it still does not answer within
+/// the timeout margin it is killed and restarted on the next request.
+pub struct ExternalEngine {
+    config: EngineConfig,
+    process: Option<EngineProcess>,
+    last_error: Option<EngineError>,
+}
+
+#[derive(Debug)]
+pub enum EngineError {
+    Spawn(io::Error),
+    Io(io::Error),
+    Crashed,
+    Timeout(&'static str),
+    Unsupported(String),
+    IllegalMove(String),
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spawn(e) => write!(f, "Failed to start engine: {e}"),
+            Self::Io(e) => write!(f, "Engine I/O error: {e}"),
+            Self::Crashed => write!(f, "Engine process exited unexpectedly"),
+            Self::Timeout(what) => write!(f, "Engine timed out waiting for {what}"),
+            Self::Unsupported(s) => write!(f, "Engine does not support {s}"),
+            Self::IllegalMove(s) => write!(f, "Engine returned an illegal move: '{s}'"),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+struct EngineProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    name: Option<String>,
+    supports_nd: bool,
+    geometry: Option<(usize, usize)>,
+}
+
+impl EngineProcess {
+    fn spawn(config: &EngineConfig) -> Result<Self, EngineError> {
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(EngineError::Spawn)?;
+
+        let stdin = child.stdin.take().ok_or(EngineError::Crashed)?;
+        let stdout = child.stdout.take().ok_or(EngineError::Crashed)?;
+
+        // Reader thread: forwards lines until EOF, at which point the channel
+        // disconnects and pending receives report a crash.
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut process = Self {
+            child,
+            stdin,
+            lines,
+            name: None,
+            supports_nd: false,
+            geometry: None,
+        };
+        process.handshake()?;
+        for (name, value) in &config.options {
+            process.send(&format!("setoption name {name} value {value}"))?;
+        }
+        Ok(process)
+    }
+
+    fn handshake(&mut self) -> Result<(), EngineError> {
+        self.send("uci")?;
+        let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+        loop {
+            let line = self.recv_until(deadline, "uciok")?;
+            let line = line.trim();
+            if line == "uciok" {
+                return Ok(());
+            }
+            if let Some(name) = line.strip_prefix("id name ") {
+                self.name = Some(name.to_string());
+            } else if line.starts_with("option name Dimension ") {
+                self.supports_nd = true;
+            }
+        }
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), EngineError> {
+        writeln!(self.stdin, "{command}").map_err(|e| match e.kind() {
+            io::ErrorKind::BrokenPipe => EngineError::Crashed,
+            _ => EngineError::Io(e),
+        })?;
+        self.stdin.flush().map_err(EngineError::Io)
+    }
+
+    fn recv_until(&self, deadline: Instant, what: &'static str) -> Result<String, EngineError> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match self.lines.recv_timeout(remaining) {
+            Ok(line) => Ok(line),
+            Err(RecvTimeoutError::Timeout) => Err(EngineError::Timeout(what)),
+            Err(RecvTimeoutError::Disconnected) => Err(EngineError::Crashed),
+        }
+    }
+
+    fn sync(&mut self) -> Result<(), EngineError> {
+        self.send("isready")?;
+        let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+        while self.recv_until(deadline, "readyok")?.trim() != "readyok" {}
+        Ok(())
+    }
+
+    fn set_geometry(&mut self, dimension: usize, side: usize) -> Result<(), EngineError> {
+        if self.geometry == Some((dimension, side)) {
+            return Ok(());
+        }
+        let standard = dimension == 2 && side == 8;
+        if !standard && !self.supports_nd {
+            return Err(EngineError::Unsupported(format!(
+                "{dimension}D boards with side {side}"
+            )));
+        }
+        if self.supports_nd {
+            self.send(&format!("setoption name Dimension value {dimension}"))?;
+            self.send(&format!("setoption name Side value {side}"))?;
+        }
+        self.send("ucinewgame")?;
+        self.sync()?;
+        self.geometry = Some((dimension, side));
+        Ok(())
+    }
+
+    /// Run one `go` and return the raw `bestmove` token.
+    fn best_move(
+        &mut self,
+        board: &Board,
+        player: Player,
+        move_time: Duration,
+        margin: Duration,
+    ) -> Result<String, EngineError> {
+        self.set_geometry(board.dimension(), board.side())?;
+
+        let position = if board.dimension() == 2 && board.side() == 8 {
+            format!("position fen {}", board.to_fen(player))
+        } else {
+            format!("position hfen {}", board.to_hfen(player))
+        };
+        self.send(&position)?;
+        self.send(&format!("go movetime {}", move_time.as_millis()))?;
+
+        let mut deadline = Instant::now() + move_time + margin;
+        let mut stop_sent = false;
+        loop {
+            match self.recv_until(deadline, "bestmove") {
+                Ok(line) => {
+                    let mut tokens = line.split_whitespace();
+                    if tokens.next() == Some("bestmove") {
+                        return Ok(tokens.next().unwrap_or("(none)").to_string());
+                    }
+                }
+                Err(EngineError::Timeout(_)) if !stop_sent => {
+                    self.send("stop")?;
+                    stop_sent = true;
+                    deadline = Instant::now() + margin;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn shutdown(mut self) {
+        let _ = self.send("quit");
+        let deadline = Instant::now() + Duration::from_millis(200);
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
+    fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl ExternalEngine {
+    pub fn new(config: EngineConfig) -> Self {
+        Self {
+            config,
+            process: None,
+            last_error: None,
+        }
+    }
+
+    /// The engine's `id name`, once it has been started.
+    pub fn name(&self) -> Option<&str> {
+        self.process.as_ref().and_then(|p| p.name.as_deref())
+    }
+
+    /// The error behind the most recent `None` returned from `get_move`.
+    pub fn last_error(&self) -> Option<&EngineError> {
+        self.last_error.as_ref()
+    }
+
+    /// Start the engine process now rather than on the first move.
+    pub fn start(&mut self) -> Result<(), EngineError> {
+        if self.process.is_none() {
+            self.process = Some(EngineProcess::spawn(&self.config)?);
+        }
+        Ok(())
+    }
+
+    /// Ask the engine for a move. `Ok(None)` means the engine reported no
+    /// move (`bestmove (none)` / `0000`), i.e. it has no legal moves.
+    pub fn request_move(
+        &mut self,
+        board: &Board,
+        player: Player,
+    ) -> Result<Option<Move>, EngineError> {
+        let move_time = Duration::from_millis(self.config.move_time_ms);
+        let margin = Duration::from_millis(self.config.timeout_ms);
+
+        let mut restarted = false;
+        let token = loop {
+            self.start()?;
+            let process = self.process.as_mut().expect("engine started");
+            match process.best_move(board, player, move_time, margin) {
+                Ok(token) => break token,
+                Err(EngineError::Crashed) if !restarted => {
+                    eprintln!("[engine] {} crashed, restarting", self.config.command);
+                    if let Some(p) = self.process.take() {
+                        p.kill();
+                    }
+                    restarted = true;
+                }
+                Err(e) => {
+                    // A timed-out or crashed engine is in an unknown state.
+                    if let Some(p) = self.process.take() {
+                        p.kill();
+                    }
+                    return Err(e);
+                }
+            }
+        };
+
+        if token == "(none)" || token == "0000" {
+            return Ok(None);
+        }
+        notation::parse_move(&mut board.clone(), player, &token)
+            .map(Some)
+            .map_err(|_| EngineError::IllegalMove(token))
+    }
+}
+
+impl PlayerStrategy for ExternalEngine {
+    fn get_move(&mut self, board: &Board, player: Player) -> Option<Move> {
+        match self.request_move(board, player) {
+            Ok(mv) => {
+                self.last_error = None;
+                mv
+            }
+            Err(e) => {
+                eprintln!("[engine] {}", e);
+                self.last_error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+impl Drop for ExternalEngine {
+    fn drop(&mut self) {
+        if let Some(p) = self.process.take() {
+            p.shutdown();
+        }
+    }
+}
@@ -1,8 +1,13 @@
 pub mod ai;
+pub mod book;
 pub mod console;
 pub mod display;
+pub mod external_engine;
 pub mod symmetries;
+pub mod syzygy;
+pub mod tablebase;
 pub mod time;
+pub mod training_data;
 
 #[cfg(feature = "distributed")]
 pub mod distributed;
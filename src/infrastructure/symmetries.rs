@@ -1,42 +1,208 @@
+use smallvec::SmallVec;
+
+use crate::domain::board::{Board, PieceMap, PositionState};
+use crate::domain::coordinate::Coordinate;
+use crate::domain::models::Move;
+
+/// An axis permutation followed by reflections: a cell's new coordinates are
+/// `new[dest] = old[permutation[dest]]`, after which every axis whose bit is
+/// set in `reflections` is mirrored.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Symmetry {
+    pub permutation: Vec<usize>,
+    pub reflections: usize,
+}
+
+impl Symmetry {
+    pub fn apply(&self, coords: &[u8], side: usize) -> SmallVec<[u8; 8]> {
+        self.permutation
+            .iter()
+            .enumerate()
+            .map(|(dest_axis, &src_axis)| {
+                let val = coords[src_axis];
+                if (self.reflections >> dest_axis) & 1 == 1 {
+                    (side - 1) as u8 - val
+                } else {
+                    val
+                }
+            })
+            .collect()
+    }
+
+    /// The symmetry that undoes this one.
+    pub fn inverse(&self) -> Symmetry {
+        let mut permutation = vec![0; self.permutation.len()];
+        for (dest_axis, &src_axis) in self.permutation.iter().enumerate() {
+            permutation[src_axis] = dest_axis;
+        }
+        let reflections = permutation
+            .iter()
+            .enumerate()
+            .filter(|&(_, &src_axis)| (self.reflections >> src_axis) & 1 == 1)
+            .fold(0, |mask, (dest_axis, _)| mask | 1 << dest_axis);
+        Symmetry {
+            permutation,
+            reflections,
+        }
+    }
+
+    /// Whether the move rules are unchanged by this symmetry on `board`.
+    ///
+    /// Pawns advance along axis 0 and the higher axes, so none of those may be
+    /// mirrored, and axes 0 and 1 stay in place; the higher axes may be
+    /// permuted freely. The file axis may be mirrored only while castling is
+    /// impossible, as castling is not file-symmetric.
+    pub fn preserves_rules(&self, board: &Board) -> bool {
+        let dimension = self.permutation.len();
+        let fixed = dimension.min(2);
+        (0..fixed).all(|axis| self.permutation[axis] == axis)
+            && self.reflections & !0b10 == 0
+            && (self.reflections == 0 || board.state.castling_rights == 0 || board.side() != 8)
+    }
+}
+
 pub struct SymmetryHandler {
     pub maps: Vec<Vec<usize>>,
+    pub symmetries: Vec<Symmetry>,
 }
 
 impl SymmetryHandler {
+    /// Every axis permutation with every combination of reflections.
     pub fn new(dimension: usize, side: usize) -> Self {
-        let total_cells = side.pow(dimension as u32);
-        let mut maps = Vec::new();
-
         let mut axes: Vec<usize> = (0..dimension).collect();
-        let permutations = permute(&mut axes);
+        let symmetries = permute(&mut axes)
+            .into_iter()
+            .flat_map(|permutation| {
+                (0..1 << dimension).map(move |reflections| Symmetry {
+                    permutation: permutation.clone(),
+                    reflections,
+                })
+            })
+            .collect();
+        Self::from_symmetries(dimension, side, symmetries)
+    }
 
-        let num_reflections = 1 << dimension;
+    /// The symmetries the rules respect: permutations of the axes above the
+    /// file axis, each with and without mirroring the file axis. Mirroring the
+    /// file only applies to positions without castling; see
+    /// [`Symmetry::preserves_rules`].
+    pub fn rule_preserving(dimension: usize, side: usize) -> Self {
+        let fixed = dimension.min(2);
+        let mut higher: Vec<usize> = (fixed..dimension).collect();
+        let mirrors: &[usize] = if dimension >= 2 { &[0, 0b10] } else { &[0] };
+        let symmetries = permute(&mut higher)
+            .into_iter()
+            .flat_map(|higher| {
+                let permutation: Vec<usize> = (0..fixed).chain(higher).collect();
+                mirrors.iter().map(move |&reflections| Symmetry {
+                    permutation: permutation.clone(),
+                    reflections,
+                })
+            })
+            .collect();
+        Self::from_symmetries(dimension, side, symmetries)
+    }
 
-        for perm in &permutations {
-            for ref_mask in 0..num_reflections {
-                let mut map = vec![0; total_cells];
+    fn from_symmetries(dimension: usize, side: usize, symmetries: Vec<Symmetry>) -> Self {
+        let total_cells = side.pow(dimension as u32);
+        let maps = symmetries
+            .iter()
+            .map(|symmetry| {
+                (0..total_cells)
+                    .map(|i| {
+                        let coords = index_to_coords(i, dimension, side);
+                        coords_to_index(&symmetry.apply(&coords, side), side)
+                    })
+                    .collect()
+            })
+            .collect();
+        SymmetryHandler { maps, symmetries }
+    }
 
-                for (i, map_entry) in map.iter_mut().enumerate().take(total_cells) {
-                    let coords = index_to_coords(i, dimension, side);
+    /// The Zobrist key `board` would have after symmetry `i`, computed from
+    /// its own key without building the transformed board.
+    pub fn transformed_key(&self, i: usize, board: &Board) -> u64 {
+        let map = &self.maps[i];
+        let zobrist = &board.zobrist;
+        let mut key = board.state.hash;
+        for idx in board.pieces.all_occupancy.iter_indices() {
+            if map[idx] != idx
+                && let Some(piece) = board.pieces.get_piece_at_index(idx)
+            {
+                key ^= zobrist.piece_key(idx, piece) ^ zobrist.piece_key(map[idx], piece);
+            }
+        }
+        if let Some((ep, _)) = board.state.en_passant_target
+            && ep < zobrist.en_passant_keys.len()
+        {
+            key ^= zobrist.en_passant_keys[ep] ^ zobrist.en_passant_keys[map[ep]];
+        }
+        key
+    }
 
-                    let mut new_coords = vec![0; dimension];
-                    for (dest_axis, &src_axis) in perm.iter().enumerate() {
-                        new_coords[dest_axis] = coords[src_axis];
-                    }
+    /// The smallest key among the rule-preserving images of `board`, with the
+    /// index of the symmetry that produces it. Positions related by such a
+    /// symmetry share their canonical key.
+    pub fn canonical(&self, board: &Board) -> (u64, usize) {
+        self.symmetries
+            .iter()
+            .enumerate()
+            .filter(|(_, symmetry)| symmetry.preserves_rules(board))
+            .map(|(i, _)| (self.transformed_key(i, board), i))
+            .min()
+            .unwrap_or((board.state.hash, 0))
+    }
 
-                    for (axis, val) in new_coords.iter_mut().enumerate() {
-                        if (ref_mask >> axis) & 1 == 1 {
-                            *val = side - 1 - *val;
-                        }
-                    }
+    pub fn canonical_key(&self, board: &Board) -> u64 {
+        self.canonical(board).0
+    }
 
-                    *map_entry = coords_to_index(&new_coords, side);
-                }
-                maps.push(map);
+    /// `board` with symmetry `i` applied. Clocks and castling rights carry
+    /// over; the history does not, as earlier positions are known only by
+    /// their keys.
+    pub fn transform_board(&self, i: usize, board: &Board) -> Board {
+        let map = &self.maps[i];
+        let mut pieces = PieceMap::new_empty(board.dimension(), board.side());
+        for idx in board.pieces.all_occupancy.iter_indices() {
+            if let Some(piece) = board.pieces.get_piece_at_index(idx) {
+                pieces.place_piece_at_index(map[idx], piece);
             }
         }
+        let state = PositionState {
+            hash: self.transformed_key(i, board),
+            history: Vec::new(),
+            en_passant_target: board
+                .state
+                .en_passant_target
+                .map(|(target, victim)| (map[target], map[victim])),
+            ..board.state.clone()
+        };
+        Board {
+            geo: board.geo.clone(),
+            zobrist: board.zobrist.clone(),
+            pieces,
+            state,
+            listener: None,
+        }
+    }
+
+    /// `mv` as played on the board transformed by symmetry `i`.
+    pub fn transform_move(&self, i: usize, mv: &Move, side: usize) -> Move {
+        transform(&self.symmetries[i], mv, side)
+    }
+
+    /// A move on the board transformed by symmetry `i`, as played on the
+    /// original board.
+    pub fn inverse_move(&self, i: usize, mv: &Move, side: usize) -> Move {
+        transform(&self.symmetries[i].inverse(), mv, side)
+    }
+}
 
-        SymmetryHandler { maps }
+fn transform(symmetry: &Symmetry, mv: &Move, side: usize) -> Move {
+    Move {
+        from: Coordinate::new(symmetry.apply(&mv.from.values, side)),
+        to: Coordinate::new(symmetry.apply(&mv.to.values, side)),
+        promotion: mv.promotion,
     }
 }
 
@@ -63,20 +229,20 @@ fn permute(arr: &mut [usize]) -> Vec<Vec<usize>> {
     result
 }
 
-fn index_to_coords(mut index: usize, dim: usize, side: usize) -> Vec<usize> {
+fn index_to_coords(mut index: usize, dim: usize, side: usize) -> Vec<u8> {
     let mut coords = Vec::with_capacity(dim);
     for _ in 0..dim {
-        coords.push(index % side);
+        coords.push((index % side) as u8);
         index /= side;
     }
     coords
 }
 
-fn coords_to_index(coords: &[usize], side: usize) -> usize {
+fn coords_to_index(coords: &[u8], side: usize) -> usize {
     let mut idx = 0;
     let mut mul = 1;
     for &c in coords {
-        idx += c * mul;
+        idx += c as usize * mul;
         mul *= side;
     }
     idx
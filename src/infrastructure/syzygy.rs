@@ -0,0 +1,211 @@
+//! Syzygy endgame tablebases for standard 2D 8×8 play.
+//!
+//! Syzygy tables (`KQvK.rtbw`, `KQvK.rtbz`, ...) come in two kinds: WDL
+//! tables hold whether a position is won, drawn or lost under the 50-move
+//! rule, and DTZ tables how many plies remain until the next capture or pawn
+//! move on the way. Probing goes through `shakmaty-syzygy`, so positions are
+//! handed over as `shakmaty` positions. Tables never cover positions with
+//! castling rights.
+//!
+//! Minimax bots use the tables twice. At the root, moves that give away the
+//! best result are dropped before searching, and while winning only the
+//! moves that reach the next capture or pawn move soonest are kept, so the
+//! win is converted within the 50-move rule. Inside the search, positions
+//! reached by a capture or pawn move are scored from the WDL tables without
+//! searching further.
+
+use std::fmt;
+use std::io;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use shakmaty::{CastlingMode, Chess, Color, File, FromSetup, Position, Rank, Role, Setup, Square};
+use shakmaty_syzygy::{AmbiguousWdl, Tablebase};
+
+pub use shakmaty_syzygy::Wdl;
+
+use crate::domain::board::Board;
+use crate::domain::models::{Move, PieceType, Player};
+use crate::infrastructure::ai::result::MATE_THRESHOLD;
+
+/// Score of a tablebase win with no known distance to mate: above every
+/// evaluation, below every mate score.
+pub const TB_WIN_SCORE: i32 = MATE_THRESHOLD - 1000;
+
+/// Scores at least this far from zero are tablebase wins or mates, found up
+/// to 1000 plies from the root.
+pub(crate) const TB_WIN_THRESHOLD: i32 = TB_WIN_SCORE - 1000;
+
+/// A directory of Syzygy tables.
+pub struct SyzygyTables {
+    tables: Tablebase<Chess>,
+    files: usize,
+}
+
+impl fmt::Debug for SyzygyTables {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyzygyTables")
+            .field("files", &self.files)
+            .field("max_pieces", &self.max_pieces())
+            .finish()
+    }
+}
+
+impl SyzygyTables {
+    /// The tables in `dir`. Files are opened lazily, on first probe.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut tables = Tablebase::new();
+        let files = tables.add_directory(dir.as_ref())?;
+        if files == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no Syzygy tables in directory",
+            ));
+        }
+        Ok(Self { tables, files })
+    }
+
+    /// Table files found, counting WDL and DTZ tables separately.
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    pub fn max_pieces(&self) -> usize {
+        self.tables.max_pieces()
+    }
+
+    /// `board` with `player` to move as a `shakmaty` position, if the tables
+    /// may cover it: a 2D 8×8 board without castling rights and with few
+    /// enough pieces.
+    pub fn position(&self, board: &Board, player: Player) -> Option<Chess> {
+        if board.pieces.all_occupancy.count_ones() as usize > self.max_pieces() {
+            return None;
+        }
+        to_chess(board, player)
+    }
+
+    /// The WDL value of a position just reached by a capture or pawn move,
+    /// for the side to move. Other positions are not probed, as their value
+    /// depends on the moves since.
+    pub fn probe(&self, board: &Board, player: Player) -> Option<Wdl> {
+        if board.state.halfmove_clock != 0 {
+            return None;
+        }
+        let pos = self.position(board, player)?;
+        self.tables.probe_wdl_after_zeroing(&pos).ok()
+    }
+
+    /// The moves of `moves` to search at the root: those keeping the best
+    /// result and, while winning, those that reach the next capture or pawn
+    /// move soonest. `None` if the position is not in the tables, or a table
+    /// it leads into is missing.
+    pub fn root_moves(&self, board: &Board, player: Player, moves: &[Move]) -> Option<Vec<Move>> {
+        self.position(board, player)?;
+        let mut ranked = Vec::with_capacity(moves.len());
+        for mv in moves {
+            let mut child = board.clone();
+            child.apply_move(mv).ok()?;
+            let pos = to_chess(&child, player.opponent())?;
+            let (wdl, plies) = if pos.is_checkmate() {
+                (AmbiguousWdl::Win, 0)
+            } else {
+                self.rank(&pos)?
+            };
+            ranked.push((mv, wdl, plies));
+        }
+
+        let best = ranked.iter().map(|&(_, wdl, _)| wdl).max()?;
+        let fastest = ranked
+            .iter()
+            .filter(|&&(_, wdl, _)| wdl == best)
+            .map(|&(_, _, plies)| plies)
+            .min()?;
+        let winning = best.signum() > 0;
+        Some(
+            ranked
+                .into_iter()
+                .filter(|&(_, wdl, plies)| wdl == best && (!winning || plies == fastest))
+                .map(|(mv, _, _)| mv.clone())
+                .collect(),
+        )
+    }
+
+    /// The result for the side that just moved into `pos`, and the plies
+    /// until the next capture or pawn move: from the DTZ tables if present,
+    /// and from the WDL tables alone for moves that capture or push a pawn.
+    fn rank(&self, pos: &Chess) -> Option<(AmbiguousWdl, u32)> {
+        match self.tables.probe_dtz(pos) {
+            Ok(dtz) => {
+                let wdl = AmbiguousWdl::from_dtz_and_halfmoves(dtz, pos.halfmoves());
+                let plies = if pos.halfmoves() == 0 {
+                    0
+                } else {
+                    dtz.ignore_rounding().0.unsigned_abs()
+                };
+                Some((-wdl, plies))
+            }
+            Err(_) if pos.halfmoves() == 0 => {
+                let wdl = self.tables.probe_wdl_after_zeroing(pos).ok()?;
+                Some((-AmbiguousWdl::from(wdl), 0))
+            }
+            Err(_) => None,
+        }
+    }
+}
+
+/// The search score of `wdl` for a node `ply` plies from the root. Wins and
+/// losses the 50-move rule saves score as draws.
+pub fn wdl_score(wdl: Wdl, ply: usize) -> i32 {
+    match wdl {
+        Wdl::Win => TB_WIN_SCORE - ply as i32,
+        Wdl::Loss => -TB_WIN_SCORE + ply as i32,
+        Wdl::CursedWin | Wdl::Draw | Wdl::BlessedLoss => 0,
+    }
+}
+
+/// `board` with `player` to move as a `shakmaty` position, or `None` if it
+/// is not a 2D 8×8 board, has castling rights, or is not a legal position.
+pub fn to_chess(board: &Board, player: Player) -> Option<Chess> {
+    if board.dimension() != 2 || board.side() != 8 || board.state.castling_rights != 0 {
+        return None;
+    }
+    let mut setup = Setup::empty();
+    for index in board.pieces.all_occupancy.iter_indices() {
+        let piece = board.pieces.get_piece_at_index(index)?;
+        let role = match piece.piece_type {
+            PieceType::Pawn => Role::Pawn,
+            PieceType::Knight => Role::Knight,
+            PieceType::Bishop => Role::Bishop,
+            PieceType::Rook => Role::Rook,
+            PieceType::Queen => Role::Queen,
+            PieceType::King => Role::King,
+        };
+        setup
+            .board
+            .set_piece_at(square(index), role.of(color(piece.owner)));
+    }
+    setup.turn = color(player);
+    setup.ep_square = board
+        .state
+        .en_passant_target
+        .map(|(target, _)| square(target));
+    setup.halfmoves = board.state.halfmove_clock.into();
+    setup.fullmoves =
+        NonZeroU32::new(board.state.fullmove_number.into()).unwrap_or(NonZeroU32::MIN);
+    match Chess::from_setup(setup, CastlingMode::Standard) {
+        Ok(pos) => Some(pos),
+        Err(e) => e.ignore_invalid_ep_square().ok(),
+    }
+}
+
+fn color(player: Player) -> Color {
+    match player {
+        Player::White => Color::White,
+        Player::Black => Color::Black,
+    }
+}
+
+/// Coordinate order: [rank, file], index = rank + file * 8
+fn square(index: usize) -> Square {
+    Square::from_coords(File::new((index / 8) as u32), Rank::new((index % 8) as u32))
+}
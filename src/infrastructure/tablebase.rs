@@ -0,0 +1,1073 @@
+//! Endgame tablebases for small boards, built by retrograde analysis.
+//!
+//! A table covers one material balance (`KQvK`, `KRvKN`, ...) on one board:
+//! for every placement of its pieces, with either side to move, it holds the
+//! distance to mate in plies, or a draw. The 50-move rule is ignored. Captures
+//! and promotions lead into smaller tables, which are generated first.
+//!
+//! Generation starts from the checkmates and walks backwards one ply at a
+//! time, un-making moves: a predecessor of a lost position is won, and a
+//! predecessor all of whose moves lead to won positions is lost. Whatever is
+//! left once nothing changes is drawn.
+//!
+//! Positions are indexed by the side to move, then the white king's cell,
+//! then the cells of the other pieces in material order. A board symmetry
+//! first brings the white king into a small set of representative cells, and
+//! the remaining symmetries that fix it pick the smallest index, so every
+//! family of symmetric positions is stored once. Without pawns every axis
+//! permutation and reflection is a symmetry; with pawns, only those of
+//! [`SymmetryHandler::rule_preserving`]. Materials in which both sides have
+//! pawns are not supported, as en passant would need its own index.
+//!
+//! Tables are stored in the `HCTB` format, little-endian: the magic `HCTB`, a
+//! `u16` version, `u8` dimension, side and material length, the material
+//! (e.g. `KQvK`), a `u8` entry width of 1 or 2 bytes, a `u32` entry count,
+//! then the entries in index order: 0 for a draw, all ones for a placement
+//! that cannot occur or is stored under another index, and otherwise the
+//! distance to mate plus one. Odd distances are wins for the side to move.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::domain::board::Board;
+use crate::domain::models::{Move, Piece, PieceType, Player};
+use crate::domain::rules::Rules;
+use crate::infrastructure::ai::result::{mated_score, mating_score};
+use crate::infrastructure::symmetries::SymmetryHandler;
+
+pub const MAGIC: &[u8; 4] = b"HCTB";
+pub const VERSION: u16 = 1;
+/// File extension of stored tables.
+pub const EXTENSION: &str = "hctb";
+
+const DRAW: u16 = 0;
+const INVALID: u16 = u16::MAX;
+
+#[derive(Debug)]
+pub enum TablebaseError {
+    Io(io::Error),
+    UnsupportedVersion(u16),
+    Corrupt(String),
+    InvalidMaterial(String),
+    /// A table that the requested one leads into has not been generated.
+    MissingTable(Material),
+    GeometryMismatch,
+}
+
+impl fmt::Display for TablebaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TablebaseError::Io(e) => write!(f, "{e}"),
+            TablebaseError::UnsupportedVersion(v) => {
+                write!(f, "unsupported HCTB version {v} (expected {VERSION})")
+            }
+            TablebaseError::Corrupt(message) => write!(f, "malformed tablebase: {message}"),
+            TablebaseError::InvalidMaterial(message) => write!(f, "invalid material: {message}"),
+            TablebaseError::MissingTable(material) => write!(f, "missing table {material}"),
+            TablebaseError::GeometryMismatch => {
+                write!(f, "tablebase is for a different board")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TablebaseError {}
+
+impl From<io::Error> for TablebaseError {
+    fn from(e: io::Error) -> Self {
+        TablebaseError::Io(e)
+    }
+}
+
+/// The pieces on the board: each side's, strongest first, starting with
+/// its king.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Material {
+    pub white: Vec<PieceType>,
+    pub black: Vec<PieceType>,
+}
+
+const ORDER: [PieceType; 6] = [
+    PieceType::King,
+    PieceType::Queen,
+    PieceType::Rook,
+    PieceType::Bishop,
+    PieceType::Knight,
+    PieceType::Pawn,
+];
+
+fn strength(piece_type: PieceType) -> usize {
+    ORDER.iter().position(|&t| t == piece_type).unwrap()
+}
+
+fn letter(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::King => 'K',
+        PieceType::Queen => 'Q',
+        PieceType::Rook => 'R',
+        PieceType::Bishop => 'B',
+        PieceType::Knight => 'N',
+        PieceType::Pawn => 'P',
+    }
+}
+
+impl Material {
+    /// Parse `KQvK`-style notation, White's pieces first.
+    pub fn parse(text: &str) -> Result<Self, TablebaseError> {
+        let invalid = |message: &str| TablebaseError::InvalidMaterial(format!("{text}: {message}"));
+        let (white, black) = text
+            .split_once(['v', 'V'])
+            .ok_or_else(|| invalid("expected e.g. KQvK"))?;
+        let side = |pieces: &str| {
+            pieces
+                .chars()
+                .map(|c| {
+                    ORDER
+                        .into_iter()
+                        .find(|&t| letter(t) == c.to_ascii_uppercase())
+                        .ok_or_else(|| invalid(&format!("unknown piece '{c}'")))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Self::new(side(white)?, side(black)?)
+    }
+
+    pub fn new(
+        mut white: Vec<PieceType>,
+        mut black: Vec<PieceType>,
+    ) -> Result<Self, TablebaseError> {
+        for pieces in [&mut white, &mut black] {
+            pieces.sort_by_key(|&t| strength(t));
+        }
+        let material = Self { white, black };
+        for pieces in [&material.white, &material.black] {
+            if pieces.iter().filter(|&&t| t == PieceType::King).count() != 1 {
+                return Err(TablebaseError::InvalidMaterial(format!(
+                    "{material}: each side needs one king"
+                )));
+            }
+        }
+        if material.white.contains(&PieceType::Pawn) && material.black.contains(&PieceType::Pawn) {
+            return Err(TablebaseError::InvalidMaterial(format!(
+                "{material}: pawns on both sides are not supported"
+            )));
+        }
+        Ok(material)
+    }
+
+    /// The material on `board`, if it is a valid one.
+    pub fn of(board: &Board) -> Option<Self> {
+        let mut white = Vec::new();
+        let mut black = Vec::new();
+        for idx in board.pieces.all_occupancy.iter_indices() {
+            let piece = board.pieces.get_piece_at_index(idx)?;
+            match piece.owner {
+                Player::White => white.push(piece.piece_type),
+                Player::Black => black.push(piece.piece_type),
+            }
+        }
+        Self::new(white, black).ok()
+    }
+
+    pub fn pieces(&self) -> usize {
+        self.white.len() + self.black.len()
+    }
+
+    pub fn has_pawns(&self) -> bool {
+        self.white.contains(&PieceType::Pawn) || self.black.contains(&PieceType::Pawn)
+    }
+
+    /// Only the two kings, which is always a draw.
+    pub fn is_bare_kings(&self) -> bool {
+        self.pieces() == 2
+    }
+
+    /// The same material with the colours swapped.
+    pub fn flipped(&self) -> Self {
+        Self {
+            white: self.black.clone(),
+            black: self.white.clone(),
+        }
+    }
+
+    /// The materials a capture or a promotion leads to.
+    pub fn successors(&self) -> Vec<Material> {
+        let mut result = Vec::new();
+        let mut push = |white: Vec<PieceType>, black: Vec<PieceType>| {
+            if let Ok(material) = Material::new(white, black)
+                && !result.contains(&material)
+            {
+                result.push(material);
+            }
+        };
+        for (i, &t) in self.white.iter().enumerate() {
+            if t == PieceType::King {
+                continue;
+            }
+            let mut white = self.white.clone();
+            white.remove(i);
+            push(white, self.black.clone());
+        }
+        for (i, &t) in self.black.iter().enumerate() {
+            if t == PieceType::King {
+                continue;
+            }
+            let mut black = self.black.clone();
+            black.remove(i);
+            push(self.white.clone(), black);
+        }
+        for promotion in [
+            PieceType::Queen,
+            PieceType::Rook,
+            PieceType::Bishop,
+            PieceType::Knight,
+        ] {
+            if let Some(i) = self.white.iter().position(|&t| t == PieceType::Pawn) {
+                let mut white = self.white.clone();
+                white[i] = promotion;
+                push(white, self.black.clone());
+            }
+            if let Some(i) = self.black.iter().position(|&t| t == PieceType::Pawn) {
+                let mut black = self.black.clone();
+                black[i] = promotion;
+                push(self.white.clone(), black);
+            }
+        }
+        result
+    }
+
+    /// The pieces in index order: White's, then Black's.
+    fn slots(&self) -> Vec<Piece> {
+        let white = self.white.iter().map(|&piece_type| Piece {
+            piece_type,
+            owner: Player::White,
+        });
+        let black = self.black.iter().map(|&piece_type| Piece {
+            piece_type,
+            owner: Player::Black,
+        });
+        white.chain(black).collect()
+    }
+
+    fn of_placement(placement: &[(Piece, usize)]) -> Result<Self, TablebaseError> {
+        let side = |owner| {
+            placement
+                .iter()
+                .filter(|(p, _)| p.owner == owner)
+                .map(|(p, _)| p.piece_type)
+                .collect()
+        };
+        Self::new(side(Player::White), side(Player::Black))
+    }
+}
+
+impl fmt::Display for Material {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let white: String = self.white.iter().map(|&t| letter(t)).collect();
+        let black: String = self.black.iter().map(|&t| letter(t)).collect();
+        write!(f, "{white}v{black}")
+    }
+}
+
+/// A tablebase result, for the side to move.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TbValue {
+    /// Mates in this many plies.
+    Win(u16),
+    Draw,
+    /// Is mated in this many plies.
+    Loss(u16),
+}
+
+impl TbValue {
+    fn decode(entry: u16) -> Option<Self> {
+        match entry {
+            INVALID => None,
+            DRAW => Some(TbValue::Draw),
+            _ if entry.is_multiple_of(2) => Some(TbValue::Win(entry - 1)),
+            _ => Some(TbValue::Loss(entry - 1)),
+        }
+    }
+
+    /// Search score of a node `ply` plies from the root with this value.
+    pub fn score(self, ply: usize) -> i32 {
+        match self {
+            TbValue::Win(dtm) => mating_score(ply + dtm as usize),
+            TbValue::Draw => 0,
+            TbValue::Loss(dtm) => mated_score(ply + dtm as usize),
+        }
+    }
+
+    /// How much the side to move prefers this value; higher is better.
+    fn preference(self) -> i32 {
+        match self {
+            TbValue::Win(dtm) => i32::MAX - dtm as i32,
+            TbValue::Draw => 0,
+            TbValue::Loss(dtm) => i32::MIN + 1 + dtm as i32,
+        }
+    }
+
+    /// The value one ply earlier, for the side that moved into this one.
+    fn parent(self) -> Self {
+        match self {
+            TbValue::Win(dtm) => TbValue::Loss(dtm + 1),
+            TbValue::Draw => TbValue::Draw,
+            TbValue::Loss(dtm) => TbValue::Win(dtm + 1),
+        }
+    }
+}
+
+/// How a table's positions divide, counting each symmetric family once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TableSummary {
+    /// Positions won by the side to move.
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// Longest distance to mate, in plies.
+    pub longest: u16,
+}
+
+/// How placements of one material map to indices.
+#[derive(Debug)]
+struct Layout {
+    dimension: usize,
+    side: usize,
+    cells: usize,
+    slots: Vec<Piece>,
+    maps: Vec<Vec<usize>>,
+    /// For each cell, the symmetry taking it to its representative.
+    king_symmetry: Vec<usize>,
+    /// For each representative cell, its position in `king_cells`.
+    king_slot: Vec<usize>,
+    king_cells: Vec<usize>,
+    /// For each representative cell, the symmetries that fix it.
+    stabilizers: Vec<Vec<usize>>,
+    /// Cells where each side's pawns promote, White's first.
+    promotes: [Vec<bool>; 2],
+}
+
+impl Layout {
+    fn new(dimension: usize, side: usize, material: &Material) -> Self {
+        let handler = if material.has_pawns() {
+            SymmetryHandler::rule_preserving(dimension, side)
+        } else {
+            SymmetryHandler::new(dimension, side)
+        };
+        let maps = handler.maps;
+        let cells = side.pow(dimension as u32);
+
+        let king_symmetry: Vec<usize> = (0..cells)
+            .map(|cell| (0..maps.len()).min_by_key(|&s| maps[s][cell]).unwrap())
+            .collect();
+        let mut king_cells: Vec<usize> = (0..cells)
+            .map(|cell| maps[king_symmetry[cell]][cell])
+            .collect();
+        king_cells.sort_unstable();
+        king_cells.dedup();
+        let mut king_slot = vec![usize::MAX; cells];
+        for (slot, &cell) in king_cells.iter().enumerate() {
+            king_slot[cell] = slot;
+        }
+        let stabilizers = king_cells
+            .iter()
+            .map(|&cell| (0..maps.len()).filter(|&s| maps[s][cell] == cell).collect())
+            .collect();
+
+        let promotes = [side - 1, 0].map(|far| {
+            (0..cells)
+                .map(|cell| {
+                    (0..dimension)
+                        .filter(|&axis| axis != 1)
+                        .all(|axis| cell / side.pow(axis as u32) % side == far)
+                })
+                .collect()
+        });
+
+        Self {
+            dimension,
+            side,
+            cells,
+            slots: material.slots(),
+            maps,
+            king_symmetry,
+            king_slot,
+            king_cells,
+            stabilizers,
+            promotes,
+        }
+    }
+
+    fn size(&self) -> usize {
+        2 * self.king_cells.len() * self.cells.pow(self.slots.len() as u32 - 1)
+    }
+
+    /// The index of the placement with a piece of each slot on `cells`.
+    fn index(&self, cells: &[usize], player: Player) -> usize {
+        let first = self.maps[self.king_symmetry[cells[0]]][cells[0]];
+        let slot = self.king_slot[first];
+        let base = player as usize * self.king_cells.len() + slot;
+        self.stabilizers[slot]
+            .iter()
+            .map(|&t| {
+                let (s, t) = (&self.maps[self.king_symmetry[cells[0]]], &self.maps[t]);
+                cells[1..]
+                    .iter()
+                    .fold(base, |index, &cell| index * self.cells + t[s[cell]])
+            })
+            .min()
+            .unwrap()
+    }
+
+    fn decode(&self, mut index: usize, cells: &mut [usize]) -> Player {
+        for cell in cells[1..].iter_mut().rev() {
+            *cell = index % self.cells;
+            index /= self.cells;
+        }
+        cells[0] = self.king_cells[index % self.king_cells.len()];
+        if index / self.king_cells.len() == 0 {
+            Player::White
+        } else {
+            Player::Black
+        }
+    }
+
+    /// `cells` for the slots, from an unordered placement of the same
+    /// material.
+    fn order(&self, placement: &[(Piece, usize)]) -> Vec<usize> {
+        let mut used = vec![false; placement.len()];
+        self.slots
+            .iter()
+            .map(|slot| {
+                let i = (0..placement.len())
+                    .find(|&i| !used[i] && placement[i].0 == *slot)
+                    .expect("placement matches the material");
+                used[i] = true;
+                placement[i].1
+            })
+            .collect()
+    }
+
+    fn coords(&self, cell: usize) -> Vec<usize> {
+        (0..self.dimension)
+            .map(|axis| cell / self.side.pow(axis as u32) % self.side)
+            .collect()
+    }
+
+    fn cell(&self, coords: &[usize]) -> usize {
+        coords
+            .iter()
+            .rev()
+            .fold(0, |cell, &value| cell * self.side + value)
+    }
+}
+
+/// One material's table.
+#[derive(Debug)]
+pub struct Tablebase {
+    material: Material,
+    layout: Layout,
+    entries: Vec<u16>,
+}
+
+impl Tablebase {
+    pub fn dimension(&self) -> usize {
+        self.layout.dimension
+    }
+
+    pub fn side(&self) -> usize {
+        self.layout.side
+    }
+
+    pub fn material(&self) -> &Material {
+        &self.material
+    }
+
+    /// Number of entries, including impossible placements.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The file name the table is saved under in a tablebase directory.
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}d{}-{}.{EXTENSION}",
+            self.dimension(),
+            self.side(),
+            self.material
+        )
+    }
+
+    pub fn summary(&self) -> TableSummary {
+        let mut summary = TableSummary::default();
+        for &entry in &self.entries {
+            match TbValue::decode(entry) {
+                Some(TbValue::Win(dtm)) => {
+                    summary.wins += 1;
+                    summary.longest = summary.longest.max(dtm);
+                }
+                Some(TbValue::Draw) => summary.draws += 1,
+                Some(TbValue::Loss(dtm)) => {
+                    summary.losses += 1;
+                    summary.longest = summary.longest.max(dtm);
+                }
+                None => {}
+            }
+        }
+        summary
+    }
+
+    fn value(&self, placement: &[(Piece, usize)], player: Player) -> Option<TbValue> {
+        let cells = self.layout.order(placement);
+        TbValue::decode(self.entries[self.layout.index(&cells, player)])
+    }
+
+    /// Build the table for `material`, looking up captures and promotions in
+    /// `smaller`.
+    pub fn generate(
+        dimension: usize,
+        side: usize,
+        material: &Material,
+        smaller: &Tablebases,
+    ) -> Result<Self, TablebaseError> {
+        if (smaller.dimension, smaller.side) != (dimension, side) {
+            return Err(TablebaseError::GeometryMismatch);
+        }
+        let layout = Layout::new(dimension, side, material);
+        let size = layout.size();
+        let mut generator = Generator {
+            layout: &layout,
+            board: Board::new_empty(dimension, side),
+            cells: vec![0; layout.slots.len()],
+        };
+
+        // Forward pass: mark impossible placements, find the mates and count
+        // each position's distinct successors. Captures and promotions are
+        // already known; they resolve their position at a later ply.
+        let mut entries = vec![DRAW; size];
+        let mut remaining = vec![0u32; size];
+        let mut mates = Vec::new();
+        let mut events: Vec<Vec<(u32, bool)>> = Vec::new();
+        let mut successors = Vec::new();
+        for index in 0..size {
+            let player = generator.set(index);
+            if !generator.is_valid(index, player) {
+                entries[index] = INVALID;
+                generator.clear();
+                continue;
+            }
+            let moves = Rules::generate_legal_moves(&mut generator.board, player);
+            if moves.is_empty() {
+                if Rules::is_in_check(&generator.board, player) {
+                    entries[index] = 1;
+                    mates.push(index as u32);
+                }
+                generator.clear();
+                continue;
+            }
+            successors.clear();
+            let mut external = 0;
+            for mv in &moves {
+                match generator.successor(mv) {
+                    Ok(cells) => successors.push(layout.index(&cells, player.opponent())),
+                    Err(placement) => {
+                        let value = smaller.value_of(&placement, player.opponent())?;
+                        // A won reply makes this position lost once every
+                        // other reply is too; a lost one wins it.
+                        let (ply, wins) = match value.parent() {
+                            TbValue::Win(dtm) => (dtm, true),
+                            TbValue::Loss(dtm) => (dtm, false),
+                            TbValue::Draw => (0, false),
+                        };
+                        if value != TbValue::Draw {
+                            let ply = ply as usize;
+                            if events.len() <= ply {
+                                events.resize_with(ply + 1, Vec::new);
+                            }
+                            events[ply].push((index as u32, wins));
+                        }
+                        external += 1;
+                    }
+                }
+            }
+            successors.sort_unstable();
+            successors.dedup();
+            remaining[index] = (successors.len() + external) as u32;
+            generator.clear();
+        }
+
+        // Backward pass, one ply at a time.
+        let mut current = mates;
+        let mut ply = 0usize;
+        let mut predecessors = Vec::new();
+        while !current.is_empty() || ply + 1 < events.len() {
+            let next_ply = ply + 1;
+            let entry = u16::try_from(next_ply + 1)
+                .ok()
+                .filter(|&e| e != INVALID)
+                .ok_or_else(|| TablebaseError::Corrupt("mate too long to store".to_string()))?;
+            let mut next = Vec::new();
+            let mut resolve = |index: usize, wins: bool, next: &mut Vec<u32>| {
+                if entries[index] != DRAW {
+                    return;
+                }
+                if !wins {
+                    remaining[index] -= 1;
+                    if remaining[index] > 0 {
+                        return;
+                    }
+                }
+                entries[index] = entry;
+                next.push(index as u32);
+            };
+            for &(index, wins) in events.get(next_ply).into_iter().flatten() {
+                resolve(index as usize, wins, &mut next);
+            }
+            for &index in &current {
+                let lost = ply.is_multiple_of(2);
+                let player = generator.set(index as usize);
+                predecessors.clear();
+                generator.predecessors(player, &mut predecessors);
+                generator.clear();
+                predecessors.sort_unstable();
+                predecessors.dedup();
+                for &before in &predecessors {
+                    resolve(before, lost, &mut next);
+                }
+            }
+            current = next;
+            ply = next_ply;
+        }
+
+        Ok(Self {
+            material: material.clone(),
+            layout,
+            entries,
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TablebaseError> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), TablebaseError> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write(&mut out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    pub fn read(mut input: impl Read) -> Result<Self, TablebaseError> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        let truncated = || TablebaseError::Corrupt("file is truncated".to_string());
+        if !bytes.starts_with(MAGIC) {
+            return Err(TablebaseError::Corrupt("missing HCTB magic".to_string()));
+        }
+        let header = bytes.get(..9).ok_or_else(truncated)?;
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(TablebaseError::UnsupportedVersion(version));
+        }
+        let (dimension, side, name_len) = (header[6] as usize, header[7] as usize, header[8]);
+        let name = bytes.get(9..9 + name_len as usize).ok_or_else(truncated)?;
+        let material = Material::parse(&String::from_utf8_lossy(name))?;
+        let rest = &bytes[9 + name.len()..];
+        let (width, count) = match rest.get(..5) {
+            Some(h) => (
+                h[0] as usize,
+                u32::from_le_bytes(h[1..5].try_into().unwrap()),
+            ),
+            None => return Err(truncated()),
+        };
+        let layout = Layout::new(dimension, side, &material);
+        if count as usize != layout.size() || !(1..=2).contains(&width) {
+            return Err(TablebaseError::Corrupt(format!(
+                "{count} entries of {width} bytes, expected {} for {material}",
+                layout.size()
+            )));
+        }
+        let body = &rest[5..];
+        if body.len() != count as usize * width {
+            return Err(truncated());
+        }
+        let entries = match width {
+            1 => body
+                .iter()
+                .map(|&e| if e == u8::MAX { INVALID } else { e as u16 })
+                .collect(),
+            _ => body
+                .chunks_exact(2)
+                .map(|e| u16::from_le_bytes([e[0], e[1]]))
+                .collect(),
+        };
+        Ok(Self {
+            material,
+            layout,
+            entries,
+        })
+    }
+
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        let name = self.material.to_string();
+        let narrow = self
+            .entries
+            .iter()
+            .all(|&e| e == INVALID || e < u8::MAX as u16);
+        out.write_all(MAGIC)?;
+        out.write_all(&VERSION.to_le_bytes())?;
+        out.write_all(&[self.dimension() as u8, self.side() as u8, name.len() as u8])?;
+        out.write_all(name.as_bytes())?;
+        out.write_all(&[if narrow { 1 } else { 2 }])?;
+        out.write_all(&(self.entries.len() as u32).to_le_bytes())?;
+        if narrow {
+            let bytes: Vec<u8> = self.entries.iter().map(|&e| e.min(255) as u8).collect();
+            out.write_all(&bytes)
+        } else {
+            for &e in &self.entries {
+                out.write_all(&e.to_le_bytes())?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// A board reused for every position of the table being generated.
+struct Generator<'a> {
+    layout: &'a Layout,
+    board: Board,
+    cells: Vec<usize>,
+}
+
+impl Generator<'_> {
+    /// Place the pieces of position `index` on the board.
+    fn set(&mut self, index: usize) -> Player {
+        let player = self.layout.decode(index, &mut self.cells);
+        for (&cell, &piece) in self.cells.iter().zip(&self.layout.slots) {
+            if !self.board.pieces.all_occupancy.get_bit(cell) {
+                self.board.pieces.place_piece_at_index(cell, piece);
+            }
+        }
+        player
+    }
+
+    fn clear(&mut self) {
+        for &cell in &self.cells {
+            self.board.pieces.remove_piece_at_index(cell);
+        }
+    }
+
+    /// Whether the placed position can occur, and is the one stored under
+    /// `index` rather than a symmetric copy.
+    fn is_valid(&mut self, index: usize, player: Player) -> bool {
+        let cells = &self.cells;
+        let distinct = (1..cells.len()).all(|i| !cells[..i].contains(&cells[i]));
+        distinct
+            && cells.iter().zip(&self.layout.slots).all(|(&cell, piece)| {
+                piece.piece_type != PieceType::Pawn
+                    || !self.layout.promotes[piece.owner as usize][cell]
+            })
+            && self.layout.index(cells, player) == index
+            && !Rules::is_in_check(&self.board, player.opponent())
+    }
+
+    /// The cells after `mv` if it stays within the material, or else the
+    /// resulting placement.
+    fn successor(&self, mv: &Move) -> Result<Vec<usize>, Vec<(Piece, usize)>> {
+        let from = self.board.coords_to_index(&mv.from.values).unwrap();
+        let to = self.board.coords_to_index(&mv.to.values).unwrap();
+        let captures = self.cells.contains(&to);
+        if !captures && mv.promotion.is_none() {
+            return Ok(self
+                .cells
+                .iter()
+                .map(|&cell| if cell == from { to } else { cell })
+                .collect());
+        }
+        Err(self
+            .cells
+            .iter()
+            .zip(&self.layout.slots)
+            .filter(|&(&cell, _)| cell != to)
+            .map(|(&cell, &piece)| {
+                if cell != from {
+                    return (piece, cell);
+                }
+                let piece_type = mv.promotion.unwrap_or(piece.piece_type);
+                (
+                    Piece {
+                        piece_type,
+                        ..piece
+                    },
+                    to,
+                )
+            })
+            .collect())
+    }
+
+    /// Indices of the positions one quiet move before the placed one, in
+    /// which the other side moves.
+    fn predecessors(&self, player: Player, out: &mut Vec<usize>) {
+        let mover = player.opponent();
+        let layout = self.layout;
+        let mut cells = self.cells.clone();
+        let mut moved_back = |slot: usize, from: usize, cells: &mut Vec<usize>| {
+            let at = cells[slot];
+            cells[slot] = from;
+            if !layout.promotes[mover as usize][from]
+                || layout.slots[slot].piece_type != PieceType::Pawn
+            {
+                out.push(layout.index(cells, mover));
+            }
+            cells[slot] = at;
+        };
+
+        // Pieces move back along the moves they could make from here.
+        for mv in Rules::generate_pseudo_legal_moves(&self.board, mover) {
+            let from = self.board.coords_to_index(&mv.from.values).unwrap();
+            let to = self.board.coords_to_index(&mv.to.values).unwrap();
+            let Some(slot) = cells.iter().position(|&c| c == from) else {
+                continue;
+            };
+            if layout.slots[slot].piece_type == PieceType::Pawn || cells.contains(&to) {
+                continue;
+            }
+            moved_back(slot, to, &mut cells);
+        }
+
+        // Pawns step back along every axis but the file, two steps from
+        // beside their start rank.
+        let (back, start): (isize, usize) = match mover {
+            Player::White => (-1, 1),
+            Player::Black => (1, layout.side - 2),
+        };
+        for slot in 0..cells.len() {
+            let piece = layout.slots[slot];
+            if piece.owner != mover || piece.piece_type != PieceType::Pawn {
+                continue;
+            }
+            let coords = layout.coords(cells[slot]);
+            for axis in (0..layout.dimension).filter(|&a| a != 1) {
+                let mut path = coords.clone();
+                for steps in 1..=2 {
+                    let value = coords[axis] as isize + back * steps;
+                    if !(0..layout.side as isize).contains(&value) {
+                        break;
+                    }
+                    path[axis] = value as usize;
+                    let from = layout.cell(&path);
+                    if cells.contains(&from) {
+                        break;
+                    }
+                    if steps == 1 || path[axis] == start {
+                        moved_back(slot, from, &mut cells);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Tables for one board, probed together.
+#[derive(Debug)]
+pub struct Tablebases {
+    dimension: usize,
+    side: usize,
+    tables: HashMap<Material, Arc<Tablebase>>,
+}
+
+impl Tablebases {
+    pub fn new(dimension: usize, side: usize) -> Self {
+        Self {
+            dimension,
+            side,
+            tables: HashMap::new(),
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    pub fn tables(&self) -> impl Iterator<Item = &Arc<Tablebase>> {
+        self.tables.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Most pieces in any table.
+    pub fn max_pieces(&self) -> usize {
+        self.tables.keys().map(Material::pieces).max().unwrap_or(0)
+    }
+
+    /// Whether `material`, or its colour swap, is covered.
+    pub fn contains(&self, material: &Material) -> bool {
+        material.is_bare_kings()
+            || self.tables.contains_key(material)
+            || self.tables.contains_key(&material.flipped())
+    }
+
+    pub fn insert(&mut self, table: Tablebase) -> Result<(), TablebaseError> {
+        if (table.dimension(), table.side()) != (self.dimension, self.side) {
+            return Err(TablebaseError::GeometryMismatch);
+        }
+        self.tables.insert(table.material.clone(), Arc::new(table));
+        Ok(())
+    }
+
+    /// Generate `material` and every table it leads into that is missing,
+    /// smallest first, calling `on_table` as each is done.
+    pub fn generate(
+        &mut self,
+        material: &Material,
+        on_table: &mut impl FnMut(&Tablebase),
+    ) -> Result<(), TablebaseError> {
+        if self.contains(material) {
+            return Ok(());
+        }
+        for smaller in material.successors() {
+            self.generate(&smaller, on_table)?;
+        }
+        let table = Tablebase::generate(self.dimension, self.side, material, self)?;
+        on_table(&table);
+        self.insert(table)
+    }
+
+    /// The value of `placement` with `player` to move. Materials stored
+    /// with the colours swapped are looked up mirrored on every axis but
+    /// the file, which turns each side's pawns around.
+    fn value_of(
+        &self,
+        placement: &[(Piece, usize)],
+        player: Player,
+    ) -> Result<TbValue, TablebaseError> {
+        let material = Material::of_placement(placement)?;
+        if material.is_bare_kings() {
+            return Ok(TbValue::Draw);
+        }
+        if let Some(table) = self.tables.get(&material) {
+            return table
+                .value(placement, player)
+                .ok_or_else(|| TablebaseError::Corrupt(format!("impossible {material} position")));
+        }
+        let table = self
+            .tables
+            .get(&material.flipped())
+            .ok_or_else(|| TablebaseError::MissingTable(material.clone()))?;
+        let layout = &table.layout;
+        let flipped: Vec<(Piece, usize)> = placement
+            .iter()
+            .map(|&(piece, cell)| {
+                let mut coords = layout.coords(cell);
+                for (axis, value) in coords.iter_mut().enumerate() {
+                    if axis != 1 {
+                        *value = layout.side - 1 - *value;
+                    }
+                }
+                let owner = piece.owner.opponent();
+                (Piece { owner, ..piece }, layout.cell(&coords))
+            })
+            .collect();
+        table
+            .value(&flipped, player.opponent())
+            .ok_or_else(|| TablebaseError::Corrupt(format!("impossible {material} position")))
+    }
+
+    /// The value of `board` with `player` to move, if it is covered. Boards
+    /// on which castling is still possible are not.
+    pub fn probe(&self, board: &Board, player: Player) -> Option<TbValue> {
+        if (board.dimension(), board.side()) != (self.dimension, self.side)
+            || (board.side() == 8 && board.state.castling_rights != 0)
+            || board.pieces.all_occupancy.count_ones() as usize > self.max_pieces()
+        {
+            return None;
+        }
+        let material = Material::of(board)?;
+        if !self.contains(&material) {
+            return None;
+        }
+        let placement: Vec<(Piece, usize)> = board
+            .pieces
+            .all_occupancy
+            .iter_indices()
+            .filter_map(|idx| Some((board.pieces.get_piece_at_index(idx)?, idx)))
+            .collect();
+        self.value_of(&placement, player).ok()
+    }
+
+    /// The move that keeps the best value for `player` among `moves`:
+    /// the fastest win, else a draw, else the slowest loss.
+    pub fn best_move(
+        &self,
+        board: &Board,
+        player: Player,
+        moves: &[Move],
+    ) -> Option<(Move, TbValue)> {
+        let mut best: Option<(Move, TbValue)> = None;
+        for mv in moves {
+            let mut after = board.clone();
+            after.listener = None;
+            after.apply_move(mv).ok()?;
+            let value = self.probe(&after, player.opponent())?.parent();
+            if best
+                .as_ref()
+                .is_none_or(|(_, b)| value.preference() > b.preference())
+            {
+                best = Some((mv.clone(), value));
+            }
+        }
+        best
+    }
+
+    /// Every table in `dir`, grouped by board.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Tablebases>, TablebaseError> {
+        let mut sets: Vec<Tablebases> = Vec::new();
+        let mut paths: Vec<_> = fs::read_dir(dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<_, _>>()?;
+        paths.sort();
+        for path in paths {
+            if path.extension().is_none_or(|e| e != EXTENSION) {
+                continue;
+            }
+            let table = Tablebase::load(&path)?;
+            let geometry = (table.dimension(), table.side());
+            let index = match sets.iter().position(|s| (s.dimension, s.side) == geometry) {
+                Some(index) => index,
+                None => {
+                    sets.push(Tablebases::new(geometry.0, geometry.1));
+                    sets.len() - 1
+                }
+            };
+            sets[index].insert(table)?;
+        }
+        Ok(sets)
+    }
+
+    /// Save every table into `dir` under its [`Tablebase::file_name`].
+    pub fn save_dir(&self, dir: impl AsRef<Path>) -> Result<(), TablebaseError> {
+        fs::create_dir_all(&dir)?;
+        for table in self.tables.values() {
+            table.save(dir.as_ref().join(table.file_name()))?;
+        }
+        Ok(())
+    }
+}
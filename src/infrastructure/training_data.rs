@@ -0,0 +1,284 @@
+//! Training positions in the versioned `HCTD` binary format.
+//!
+//! All integers are little-endian. A file starts with an 8-byte header:
+//!
+//! | bytes | field                        |
+//! |-------|------------------------------|
+//! | 4     | magic `HCTD`                 |
+//! | 2     | format version, currently 1  |
+//! | 1     | board dimension              |
+//! | 1     | board side                   |
+//!
+//! followed by records until the end of the file:
+//!
+//! | bytes     | field                                                  |
+//! |-----------|--------------------------------------------------------|
+//! | 2         | HFEN length `n`                                        |
+//! | `n`       | HFEN of the position, side to move included            |
+//! | 2         | search score in centipawns from White's view (`i16`)   |
+//! | dimension | from-square coordinates of the best move               |
+//! | dimension | to-square coordinates                                  |
+//! | 1         | promotion: 0 none, 1 knight, 2 bishop, 3 rook, 4 queen |
+//! | 1         | game result: 0 Black won, 1 draw, 2 White won          |
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use crate::domain::board::Board;
+use crate::domain::board::hfen::HfenError;
+use crate::domain::coordinate::Coordinate;
+use crate::domain::models::{Move, PieceType, Player};
+
+pub const MAGIC: &[u8; 4] = b"HCTD";
+pub const VERSION: u16 = 1;
+
+/// How the game a position came from ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameOutcome {
+    BlackWin,
+    Draw,
+    WhiteWin,
+}
+
+impl GameOutcome {
+    /// White's points: 0, 0.5 or 1.
+    pub fn white_points(self) -> f64 {
+        match self {
+            GameOutcome::BlackWin => 0.0,
+            GameOutcome::Draw => 0.5,
+            GameOutcome::WhiteWin => 1.0,
+        }
+    }
+}
+
+/// One searched position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrainingRecord {
+    pub hfen: String,
+    /// Centipawns from White's view; mate scores as the search reports them.
+    pub score: i16,
+    pub best_move: Move,
+    pub result: GameOutcome,
+}
+
+impl TrainingRecord {
+    pub fn position(&self) -> Result<(Board, Player), HfenError> {
+        Board::from_hfen(&self.hfen)
+    }
+}
+
+#[derive(Debug)]
+pub enum TrainingDataError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u16),
+    /// A record that cannot be decoded, numbered from 0.
+    Corrupt {
+        record: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for TrainingDataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrainingDataError::Io(e) => write!(f, "{e}"),
+            TrainingDataError::BadMagic => write!(f, "not an HCTD training data file"),
+            TrainingDataError::UnsupportedVersion(v) => {
+                write!(f, "unsupported HCTD version {v} (expected {VERSION})")
+            }
+            TrainingDataError::Corrupt { record, message } => {
+                write!(f, "record {record}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TrainingDataError {}
+
+impl From<io::Error> for TrainingDataError {
+    fn from(e: io::Error) -> Self {
+        TrainingDataError::Io(e)
+    }
+}
+
+/// Appends records to an `HCTD` stream for one board geometry.
+pub struct TrainingDataWriter<W: Write> {
+    inner: W,
+    dimension: usize,
+}
+
+impl TrainingDataWriter<BufWriter<File>> {
+    /// Create (or truncate) `path` and write the header.
+    pub fn create(path: impl AsRef<Path>, dimension: usize, side: usize) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), dimension, side)
+    }
+}
+
+impl<W: Write> TrainingDataWriter<W> {
+    pub fn new(mut inner: W, dimension: usize, side: usize) -> io::Result<Self> {
+        let (Ok(dim), Ok(side)) = (u8::try_from(dimension), u8::try_from(side)) else {
+            return Err(invalid_input("board geometry does not fit the header"));
+        };
+        inner.write_all(MAGIC)?;
+        inner.write_all(&VERSION.to_le_bytes())?;
+        inner.write_all(&[dim, side])?;
+        Ok(Self { inner, dimension })
+    }
+
+    pub fn write(&mut self, record: &TrainingRecord) -> io::Result<()> {
+        let mv = &record.best_move;
+        if mv.from.values.len() != self.dimension || mv.to.values.len() != self.dimension {
+            return Err(invalid_input(
+                "move coordinates do not match the board dimension",
+            ));
+        }
+        let hfen_len =
+            u16::try_from(record.hfen.len()).map_err(|_| invalid_input("HFEN too long"))?;
+
+        self.inner.write_all(&hfen_len.to_le_bytes())?;
+        self.inner.write_all(record.hfen.as_bytes())?;
+        self.inner.write_all(&record.score.to_le_bytes())?;
+        self.inner.write_all(&mv.from.values)?;
+        self.inner.write_all(&mv.to.values)?;
+        let promotion = match mv.promotion {
+            None => 0,
+            Some(PieceType::Knight) => 1,
+            Some(PieceType::Bishop) => 2,
+            Some(PieceType::Rook) => 3,
+            Some(PieceType::Queen) => 4,
+            Some(_) => return Err(invalid_input("invalid promotion piece")),
+        };
+        let result = match record.result {
+            GameOutcome::BlackWin => 0,
+            GameOutcome::Draw => 1,
+            GameOutcome::WhiteWin => 2,
+        };
+        self.inner.write_all(&[promotion, result])
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
+
+/// Reads the records of an `HCTD` stream in order.
+pub struct TrainingDataReader<R: Read> {
+    inner: R,
+    dimension: usize,
+    side: usize,
+    next_record: usize,
+}
+
+impl TrainingDataReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, TrainingDataError> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> TrainingDataReader<R> {
+    /// Read and check the header.
+    pub fn new(mut inner: R) -> Result<Self, TrainingDataError> {
+        let mut header = [0u8; 8];
+        inner.read_exact(&mut header).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => TrainingDataError::BadMagic,
+            _ => TrainingDataError::Io(e),
+        })?;
+        if &header[..4] != MAGIC {
+            return Err(TrainingDataError::BadMagic);
+        }
+        let version = u16::from_le_bytes([header[4], header[5]]);
+        if version != VERSION {
+            return Err(TrainingDataError::UnsupportedVersion(version));
+        }
+        Ok(Self {
+            inner,
+            dimension: header[6] as usize,
+            side: header[7] as usize,
+            next_record: 0,
+        })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// The next record, `Ok(None)` at a clean end of the stream.
+    pub fn read_record(&mut self) -> Result<Option<TrainingRecord>, TrainingDataError> {
+        let mut len = [0u8; 2];
+        match self.inner.read(&mut len[..1])? {
+            0 => return Ok(None),
+            _ => self.read_exact(&mut len[1..])?,
+        }
+        let mut hfen = vec![0u8; u16::from_le_bytes(len) as usize];
+        self.read_exact(&mut hfen)?;
+        let hfen = String::from_utf8(hfen).map_err(|_| self.corrupt("HFEN is not UTF-8"))?;
+
+        let mut fixed = vec![0u8; 2 + 2 * self.dimension + 2];
+        self.read_exact(&mut fixed)?;
+        let score = i16::from_le_bytes([fixed[0], fixed[1]]);
+        let (from, rest) = fixed[2..].split_at(self.dimension);
+        let (to, rest) = rest.split_at(self.dimension);
+        let promotion = match rest[0] {
+            0 => None,
+            1 => Some(PieceType::Knight),
+            2 => Some(PieceType::Bishop),
+            3 => Some(PieceType::Rook),
+            4 => Some(PieceType::Queen),
+            p => return Err(self.corrupt(&format!("invalid promotion {p}"))),
+        };
+        let result = match rest[1] {
+            0 => GameOutcome::BlackWin,
+            1 => GameOutcome::Draw,
+            2 => GameOutcome::WhiteWin,
+            r => return Err(self.corrupt(&format!("invalid result {r}"))),
+        };
+
+        self.next_record += 1;
+        Ok(Some(TrainingRecord {
+            hfen,
+            score,
+            best_move: Move {
+                from: Coordinate::new(from.to_vec()),
+                to: Coordinate::new(to.to_vec()),
+                promotion,
+            },
+            result,
+        }))
+    }
+
+    /// `read_exact` that reports a truncated record as corrupt.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), TrainingDataError> {
+        self.inner.read_exact(buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => self.corrupt("truncated record"),
+            _ => TrainingDataError::Io(e),
+        })
+    }
+
+    fn corrupt(&self, message: &str) -> TrainingDataError {
+        TrainingDataError::Corrupt {
+            record: self.next_record,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl<R: Read> Iterator for TrainingDataReader<R> {
+    type Item = Result<TrainingRecord, TrainingDataError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
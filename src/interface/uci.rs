@@ -0,0 +1,464 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::config::AppConfig;
+use crate::domain::board::Board;
+use crate::domain::board::notation;
+use crate::domain::models::{Move, Player};
+use crate::infrastructure::ai::memory::{self, MemoryError};
+use crate::infrastructure::ai::result::mate_in;
+use crate::infrastructure::ai::{Clock, MinimaxBot, SearchEvent, SearchHandle, SearchLimits};
+
+/// Largest geometry advertised through the `Dimension` / `Side` options.
+const MAX_DIMENSION: usize = 6;
+const MAX_SIDE: usize = 16;
+const MAX_MULTI_PV: usize = 64;
+
+/// How often the command loop checks whether a running search has finished.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Commands handled while a search runs. Anything else waits for `bestmove`.
+const SEARCH_COMMANDS: [&str; 4] = ["stop", "ponderhit", "isready", "quit"];
+
+/// Parameters of the `go` command, which end a `searchmoves` list.
+const GO_KEYWORDS: [&str; 11] = [
+    "wtime",
+    "btime",
+    "winc",
+    "binc",
+    "movestogo",
+    "depth",
+    "nodes",
+    "mate",
+    "movetime",
+    "infinite",
+    "ponder",
+];
+
+/// Input lines and search events, merged so the command loop can wait on both.
+enum Message {
+    Line(String),
+    Eof,
+    Event(SearchEvent),
+}
+
+/// UCI front end for `MinimaxBot`.
+///
+/// Standard UCI for 2D 8×8. Other geometries are selected with the
+/// `Dimension` and `Side` spin options; positions are then given with
+/// `position hfen <hfen>` (or `startpos`) and moves use coordinate notation.
+pub struct UciInterface {
+    config: AppConfig,
+    dimension: usize,
+    side: usize,
+    board: Board,
+    side_to_move: Player,
+    multi_pv: usize,
+    bot: Option<MinimaxBot>,
+    /// The search started by the last `go`, until its `bestmove` is sent.
+    search: Option<SearchHandle>,
+    /// The search came from `go infinite` and runs until `stop`.
+    infinite: bool,
+    /// Commands received during a search, run once it has finished.
+    pending: VecDeque<String>,
+    eof: bool,
+    tx: Sender<Message>,
+    rx: Receiver<Message>,
+}
+
+impl UciInterface {
+    pub fn new(config: AppConfig) -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            config,
+            dimension: 2,
+            side: 8,
+            board: Board::new(2, 8),
+            side_to_move: Player::White,
+            multi_pv: 1,
+            bot: None,
+            search: None,
+            infinite: false,
+            pending: VecDeque::new(),
+            eof: false,
+            tx,
+            rx,
+        }
+    }
+
+    /// Process commands from `input` until `quit`, or until EOF once the
+    /// last search has finished.
+    ///
+    /// `go` returns at once: the search streams `info` lines and ends with
+    /// `bestmove`, early on `stop`. Input is read on a separate thread so
+    /// commands keep arriving while a search runs.
+    pub fn run<R, W>(&mut self, input: R, output: &mut W) -> io::Result<()>
+    where
+        R: BufRead + Send + 'static,
+        W: Write,
+    {
+        let tx = self.tx.clone();
+        thread::spawn(move || {
+            for line in input.lines() {
+                let Ok(line) = line else { break };
+                if tx.send(Message::Line(line)).is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send(Message::Eof);
+        });
+
+        loop {
+            while self.search.is_none()
+                && let Some(line) = self.pending.pop_front()
+            {
+                if !self.handle_command(&line, output)? {
+                    return Ok(());
+                }
+            }
+            // A ponder search that ran out of depth waits for `ponderhit`,
+            // and an infinite one, however it ended, for `stop`.
+            if self.search.as_ref().is_some_and(|s| {
+                s.is_finished() && !s.is_pondering() && (!self.infinite || s.was_stopped())
+            }) {
+                self.finish_search(output)?;
+                continue;
+            }
+            if self.eof {
+                match &self.search {
+                    None => return Ok(()),
+                    // No `stop` or `ponderhit` can follow.
+                    Some(search) if self.infinite || search.is_pondering() => search.stop(),
+                    Some(_) => {}
+                }
+            }
+
+            let message = if self.search.is_some() {
+                match self.rx.recv_timeout(POLL_INTERVAL) {
+                    Ok(message) => message,
+                    Err(_) => continue,
+                }
+            } else {
+                match self.rx.recv() {
+                    Ok(message) => message,
+                    Err(_) => return Ok(()),
+                }
+            };
+
+            match message {
+                Message::Event(event) => write_info(output, &event)?,
+                Message::Eof => self.eof = true,
+                Message::Line(line) => {
+                    let command = line.split_whitespace().next().unwrap_or("");
+                    if self.search.is_some() && !SEARCH_COMMANDS.contains(&command) {
+                        self.pending.push_back(line);
+                    } else if !self.handle_command(&line, output)? {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handle one command line. Returns `false` on `quit`.
+    fn handle_command<W: Write>(&mut self, line: &str, out: &mut W) -> io::Result<bool> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some(&command) = tokens.first() else {
+            return Ok(true);
+        };
+
+        match command {
+            "uci" => {
+                writeln!(out, "id name HyperChess {}", env!("CARGO_PKG_VERSION"))?;
+                writeln!(out, "id author HyperChess developers")?;
+                writeln!(
+                    out,
+                    "option name Dimension type spin default 2 min 2 max {MAX_DIMENSION}"
+                )?;
+                writeln!(
+                    out,
+                    "option name Side type spin default 8 min 4 max {MAX_SIDE}"
+                )?;
+                writeln!(
+                    out,
+                    "option name Hash type spin default {} min 1 max 65536",
+                    self.config.compute.memory
+                )?;
+                writeln!(
+                    out,
+                    "option name Threads type spin default {} min 1 max 256",
+                    self.config.compute.concurrency
+                )?;
+                writeln!(
+                    out,
+                    "option name MultiPV type spin default 1 min 1 max {MAX_MULTI_PV}"
+                )?;
+                // Pondering needs no setting: `go ponder` is always honoured.
+                writeln!(out, "option name Ponder type check default false")?;
+                writeln!(out, "uciok")?;
+            }
+            "isready" => writeln!(out, "readyok")?,
+            "setoption" => {
+                if let Err(e) = self.set_option(&tokens[1..]) {
+                    writeln!(out, "info string {}", e)?;
+                }
+            }
+            "ucinewgame" => {
+                self.board = Board::new(self.dimension, self.side);
+                self.side_to_move = Player::White;
+                if let Some(bot) = &self.bot {
+                    bot.new_game();
+                }
+            }
+            "position" => {
+                if let Err(e) = self.set_position(&tokens[1..]) {
+                    writeln!(out, "info string {}", e)?;
+                }
+            }
+            "go" => {
+                if let Err(e) = self.go(&tokens[1..]) {
+                    writeln!(out, "info string {}", e)?;
+                    writeln!(out, "bestmove (none)")?;
+                }
+            }
+            "stop" => {
+                if let Some(search) = &self.search {
+                    search.stop();
+                }
+            }
+            "ponderhit" => {
+                if let Some(search) = &self.search {
+                    search.ponderhit();
+                }
+            }
+            "quit" => {
+                if let Some(search) = &self.search {
+                    search.stop();
+                    self.finish_search(out)?;
+                }
+                return Ok(false);
+            }
+            _ => writeln!(out, "info string Unknown command: {}", command)?,
+        }
+        out.flush()?;
+        Ok(true)
+    }
+
+    /// Apply a `setoption`. A change the search could not fit in the memory
+    /// budget is refused, keeping the previous settings.
+    fn set_option(&mut self, tokens: &[&str]) -> Result<(), String> {
+        // setoption name <name> value <value>
+        let name_pos = tokens.iter().position(|&t| t == "name");
+        let value_pos = tokens.iter().position(|&t| t == "value");
+        let (Some(n), Some(v)) = (name_pos, value_pos) else {
+            return Ok(());
+        };
+        let name = tokens[n + 1..v].join(" ");
+        let Ok(value) = tokens[v + 1..].join(" ").parse::<usize>() else {
+            return Ok(());
+        };
+
+        let (mut dimension, mut side) = (self.dimension, self.side);
+        let mut compute = self.config.compute.clone();
+        match name.to_ascii_lowercase().as_str() {
+            "dimension" => dimension = value.clamp(2, MAX_DIMENSION),
+            "side" => side = value.clamp(4, MAX_SIDE),
+            "hash" => compute.memory = value.max(1),
+            "threads" => compute.concurrency = value.max(1),
+            "multipv" => {
+                // Search setting only; the bot and position stay valid.
+                self.multi_pv = value.clamp(1, MAX_MULTI_PV);
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+        memory::plan(dimension, side, compute.concurrency, compute.memory)
+            .map_err(|e| e.to_string())?;
+        (self.dimension, self.side) = (dimension, side);
+        self.config.compute = compute;
+        // Geometry or resource changes invalidate the bot and the position.
+        self.bot = None;
+        if self.board.dimension() != self.dimension || self.board.side() != self.side {
+            self.board = Board::new(self.dimension, self.side);
+            self.side_to_move = Player::White;
+        }
+        Ok(())
+    }
+
+    fn set_position(&mut self, tokens: &[&str]) -> Result<(), String> {
+        let moves_pos = tokens.iter().position(|&t| t == "moves");
+        let spec = &tokens[..moves_pos.unwrap_or(tokens.len())];
+
+        let (board, side_to_move) = match spec.first() {
+            Some(&"startpos") => (Board::new(self.dimension, self.side), Player::White),
+            Some(&"fen") => {
+                let fen = spec[1..].join(" ");
+                let board = Board::from_fen(&fen).map_err(|e| e.to_string())?;
+                let stm = if spec.get(2) == Some(&"b") {
+                    Player::Black
+                } else {
+                    Player::White
+                };
+                (board, stm)
+            }
+            Some(&"hfen") => Board::from_hfen(&spec[1..].join(" ")).map_err(|e| e.to_string())?,
+            _ => return Err("Expected startpos, fen or hfen".to_string()),
+        };
+
+        let mut board = board;
+        let mut side_to_move = side_to_move;
+        if let Some(pos) = moves_pos {
+            for token in &tokens[pos + 1..] {
+                let mv = notation::parse_move(&mut board, side_to_move, token)
+                    .map_err(|e| e.to_string())?;
+                board.apply_move(&mv)?;
+                side_to_move = side_to_move.opponent();
+            }
+        }
+
+        if board.dimension() != self.dimension || board.side() != self.side {
+            self.dimension = board.dimension();
+            self.side = board.side();
+            self.bot = None;
+        }
+        self.board = board;
+        self.side_to_move = side_to_move;
+        Ok(())
+    }
+
+    fn go(&mut self, tokens: &[&str]) -> Result<(), MemoryError> {
+        let arg = |name: &str| -> Option<u64> {
+            let pos = tokens.iter().position(|&t| t == name)?;
+            tokens.get(pos + 1)?.parse().ok()
+        };
+
+        let ms = |name: &str| arg(name).map(Duration::from_millis);
+        let (time, inc) = match self.side_to_move {
+            Player::White => (ms("wtime"), ms("winc")),
+            Player::Black => (ms("btime"), ms("binc")),
+        };
+        let limits = SearchLimits {
+            depth: arg("depth").map(|d| d as usize),
+            movetime: ms("movetime"),
+            nodes: arg("nodes"),
+            mate: arg("mate").map(|n| n as u32),
+            search_moves: self.search_moves(tokens),
+            clock: time.map(|remaining| Clock {
+                remaining,
+                increment: inc.unwrap_or_default(),
+                moves_to_go: arg("movestogo").map(|n| n as u32),
+            }),
+            multi_pv: self.multi_pv,
+            infinite: tokens.contains(&"infinite"),
+            ponder: tokens.contains(&"ponder"),
+        };
+
+        let bot = match &mut self.bot {
+            Some(bot) => bot,
+            None => self.bot.insert(MinimaxBot::try_new(
+                &self.config,
+                self.dimension,
+                self.side,
+            )?),
+        };
+
+        let tx = self.tx.clone();
+        bot.set_observer(Some(Arc::new(move |event: &SearchEvent| {
+            let _ = tx.send(Message::Event(event.clone()));
+        })));
+        self.infinite = limits.infinite;
+        self.search = Some(bot.start_search(&self.board, self.side_to_move, limits));
+        Ok(())
+    }
+
+    /// Report the finished search: its remaining `info` lines, then `bestmove`.
+    fn finish_search<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        let Some(search) = self.search.take() else {
+            return Ok(());
+        };
+        let result = search.wait();
+
+        // Every event of the search is queued by now. Lines read meanwhile
+        // keep their order behind any already pending.
+        while let Ok(message) = self.rx.try_recv() {
+            match message {
+                Message::Event(event) => write_info(out, &event)?,
+                Message::Line(line) => self.pending.push_back(line),
+                Message::Eof => self.eof = true,
+            }
+        }
+
+        if result.as_ref().is_some_and(|r| r.book) {
+            writeln!(out, "info string book move")?;
+        }
+        match result {
+            Some(result) => match result.pv.get(1) {
+                Some(reply) => writeln!(
+                    out,
+                    "bestmove {} ponder {}",
+                    notation::format_move(&result.best_move),
+                    notation::format_move(reply)
+                )?,
+                None => writeln!(out, "bestmove {}", notation::format_move(&result.best_move))?,
+            },
+            None => writeln!(out, "bestmove (none)")?,
+        }
+        out.flush()
+    }
+
+    /// Moves listed after `searchmoves`, up to the next `go` keyword.
+    /// Unparseable moves are skipped.
+    fn search_moves(&self, tokens: &[&str]) -> Vec<Move> {
+        let Some(pos) = tokens.iter().position(|&t| t == "searchmoves") else {
+            return Vec::new();
+        };
+        let mut board = self.board.clone();
+        tokens[pos + 1..]
+            .iter()
+            .take_while(|t| !GO_KEYWORDS.contains(t))
+            .filter_map(|t| notation::parse_move(&mut board, self.side_to_move, t).ok())
+            .collect()
+    }
+}
+
+/// `mate <moves>` for mate scores, negative when mated, else `cp <score>`.
+fn uci_score(score: i32) -> String {
+    match mate_in(score) {
+        Some(moves) => format!("mate {moves}"),
+        None => format!("cp {score}"),
+    }
+}
+
+/// Render a search event as a UCI `info` line.
+fn write_info<W: Write>(out: &mut W, event: &SearchEvent) -> io::Result<()> {
+    match event {
+        SearchEvent::Progress { nodes, nps, time } => writeln!(
+            out,
+            "info nodes {} nps {} time {}",
+            nodes,
+            nps,
+            time.as_millis()
+        )?,
+        SearchEvent::Iteration(info) => {
+            let pv: Vec<String> = info.pv.iter().map(notation::format_move).collect();
+            writeln!(
+                out,
+                "info depth {} seldepth {} multipv {} score {} nodes {} nps {} hashfull {} time {} pv {}",
+                info.depth,
+                info.seldepth,
+                info.multipv,
+                uci_score(info.score),
+                info.nodes,
+                info.nps,
+                info.hashfull,
+                info.time.as_millis(),
+                pv.join(" ")
+            )?
+        }
+    }
+    out.flush()
+}
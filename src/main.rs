@@ -1,10 +1,17 @@
 use hyperchess::application::game_service::GameService;
 use hyperchess::config::AppConfig;
 use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
 use hyperchess::domain::services::PlayerStrategy;
-use hyperchess::infrastructure::ai::MinimaxBot;
-use hyperchess::infrastructure::console::HumanConsolePlayer;
+use hyperchess::infrastructure::ai::eval::Evaluator;
+use hyperchess::infrastructure::ai::{
+    MateResult, MateSolver, MctsBot, MinimaxBot, Personality, memory,
+};
+use hyperchess::infrastructure::console::{ConsoleSearchObserver, HumanConsolePlayer};
+use hyperchess::infrastructure::external_engine::ExternalEngine;
 use std::env;
+use std::sync::Arc;
+use std::time::Instant;
 
 #[cfg(feature = "api")]
 #[tokio::main]
@@ -23,6 +30,13 @@ fn run_cli() {
     let args: Vec<String> = env::args().collect();
 
     let mut config = AppConfig::load();
+    match args.get(1).map(String::as_str) {
+        Some("memory") => return print_memory_plan(&config, &args[2..]),
+        Some("mate") => return solve_mate(&config, &args[2..]),
+        Some("eval") => return print_eval_trace(&config, &args[2..]),
+        _ => {}
+    }
+
     let mut dimension = 2;
     let side = 8;
     let mut player_white_type = "h";
@@ -45,21 +59,61 @@ fn run_cli() {
     {
         config.minimax.depth = d;
     }
+    // One personality for both bots, or `white,black`.
+    let (white_personality, black_personality) = match args.get(4) {
+        Some(arg) => {
+            let parse = |name: &str| {
+                name.parse::<Personality>().unwrap_or_else(|e| {
+                    eprintln!("{e}");
+                    std::process::exit(1);
+                })
+            };
+            match arg.split_once(',') {
+                Some((white, black)) => (parse(white), parse(black)),
+                None => (parse(arg), parse(arg)),
+            }
+        }
+        None => Default::default(),
+    };
 
-    let create_bot = |config: &AppConfig| -> Box<dyn PlayerStrategy> {
-        Box::new(MinimaxBot::new(config, dimension, side))
+    let create_bot = |config: &AppConfig, personality: Personality| -> Box<dyn PlayerStrategy> {
+        let mut bot = MinimaxBot::try_new(config, dimension, side).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1);
+        });
+        bot.set_personality(personality);
+        bot.set_observer(Some(Arc::new(ConsoleSearchObserver)));
+        Box::new(bot)
+    };
+
+    let create_mcts = |config: &AppConfig| -> Box<dyn PlayerStrategy> {
+        let mut bot = MctsBot::new(config);
+        bot.set_observer(Some(Arc::new(ConsoleSearchObserver)));
+        Box::new(bot)
+    };
+
+    let create_engine = |config: &AppConfig| -> Box<dyn PlayerStrategy> {
+        if config.engine.command.is_empty() {
+            eprintln!("No engine configured: set [engine] command or HYPERCHESS_ENGINE_COMMAND");
+            std::process::exit(1);
+        }
+        Box::new(ExternalEngine::new(config.engine.clone()))
     };
 
     let player_white: Box<dyn PlayerStrategy> = match player_white_type {
         "h" => Box::new(HumanConsolePlayer::new()),
-        "c" => create_bot(&config),
+        "c" => create_bot(&config, white_personality),
+        "m" => create_mcts(&config),
+        "e" => create_engine(&config),
         _ => Box::new(HumanConsolePlayer::new()),
     };
 
     let player_black: Box<dyn PlayerStrategy> = match player_black_type {
         "h" => Box::new(HumanConsolePlayer::new()),
-        "c" => create_bot(&config),
-        _ => create_bot(&config),
+        "c" => create_bot(&config, black_personality),
+        "m" => create_mcts(&config),
+        "e" => create_engine(&config),
+        _ => create_bot(&config, black_personality),
     };
 
     let board = Board::new(dimension, side);
@@ -67,3 +121,93 @@ fn run_cli() {
     let game = GameService::new(board, player_white, player_black);
     hyperchess::interface::console::ConsoleInterface::run(game);
 }
+
+/// `mate <moves> fen|hfen <position>`: prove or refute a forced mate for
+/// the side to move and print the solution tree.
+#[allow(dead_code)]
+fn solve_mate(config: &AppConfig, args: &[String]) {
+    let usage = || -> ! {
+        eprintln!("Usage: hyperchess mate <moves> fen|hfen <position>");
+        std::process::exit(2);
+    };
+    let Some(moves) = args.first().and_then(|m| m.parse::<u32>().ok()) else {
+        usage();
+    };
+    let Some((board, player)) = parse_position(&args[1..]) else {
+        usage();
+    };
+
+    let mut solver = MateSolver::new(config);
+    let start = Instant::now();
+    let result = solver.solve(&board, player, moves);
+    let elapsed = start.elapsed();
+    match result {
+        MateResult::Mate(solution) => {
+            println!("Mate in {}:", solution.moves());
+            print!("{solution}");
+        }
+        MateResult::NoMate => println!("No mate in {moves}"),
+        MateResult::Unknown => println!("Unknown: node budget exhausted"),
+    }
+    println!("{} nodes in {:.2?}", solver.nodes(), elapsed);
+}
+
+/// `eval fen|hfen <position>`: every evaluation term for each side.
+#[allow(dead_code)]
+fn print_eval_trace(config: &AppConfig, args: &[String]) {
+    let Some((board, _)) = parse_position(args) else {
+        eprintln!("Usage: hyperchess eval fen|hfen <position>");
+        std::process::exit(2);
+    };
+    let params = config.eval_params(board.dimension(), board.side());
+    println!("{}", Evaluator::evaluate_trace(&board, &params));
+}
+
+/// `fen|hfen <position>` and the side to move; `None` on a missing or
+/// unknown format, exiting on a malformed position.
+#[allow(dead_code)]
+fn parse_position(args: &[String]) -> Option<(Board, Player)> {
+    let position = args.get(1..).unwrap_or_default().join(" ");
+    let parsed = match args.first().map(String::as_str) {
+        Some("fen") => Board::from_fen(&position)
+            .map(|board| {
+                let black = position.split_whitespace().nth(1) == Some("b");
+                (board, if black { Player::Black } else { Player::White })
+            })
+            .map_err(|e| e.to_string()),
+        Some("hfen") => Board::from_hfen(&position).map_err(|e| e.to_string()),
+        _ => return None,
+    };
+    Some(parsed.unwrap_or_else(|e| {
+        eprintln!("Invalid position: {e}");
+        std::process::exit(1);
+    }))
+}
+
+/// `memory <dimension> <side> [threads]`: how a search on that board would
+/// split `compute.memory`.
+#[allow(dead_code)]
+fn print_memory_plan(config: &AppConfig, args: &[String]) {
+    let arg = |i: usize| args.get(i).and_then(|a| a.parse::<usize>().ok());
+    let (Some(dimension), Some(side)) = (arg(0), arg(1)) else {
+        eprintln!("Usage: hyperchess memory <dimension> <side> [threads]");
+        std::process::exit(2);
+    };
+    let threads = arg(2).unwrap_or(config.compute.concurrency);
+    let budget = config.compute.memory;
+
+    match memory::plan(dimension, side, threads, budget) {
+        Ok(plan) => {
+            println!("{dimension}D side {side}, {threads} thread(s), budget {budget} MB:");
+            println!("{}", plan.estimate);
+            println!(
+                "history tables: 2^{} move pairs, 2^{} pawn entries",
+                plan.tables.pair_bits, plan.tables.pawn_bits
+            );
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
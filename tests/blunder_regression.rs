@@ -122,6 +122,7 @@ fn test_no_blunder_allows_forced_mate() {
 /// Position FEN: r2q1rk1/ppp3pp/3b4/nB2pp1b/8/P1PP1N1P/2P1QPP1/1RB2RK1 w - - 2 13
 #[test]
 fn test_king_shelter_eval_penalizes_g4() {
+    use hyperchess::infrastructure::ai::EvalParams;
     use hyperchess::infrastructure::ai::eval::Evaluator;
 
     let moves = [
@@ -141,11 +142,11 @@ fn test_king_shelter_eval_penalizes_g4() {
         "White king should be on g1"
     );
 
-    let eval_before = Evaluator::evaluate(&board);
+    let eval_before = Evaluator::evaluate(&board, &EvalParams::default());
 
     let mut board_after_g4 = board.clone();
     board_after_g4.apply_move(&uci("g2g4")).unwrap();
-    let eval_after = Evaluator::evaluate(&board_after_g4);
+    let eval_after = Evaluator::evaluate(&board_after_g4, &EvalParams::default());
 
     let delta = eval_after - eval_before;
     println!(
@@ -0,0 +1,228 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperchess::application::book_builder::{SelfPlayBookOptions, build_from_self_play};
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation::{format_move, parse_move};
+use hyperchess::domain::models::Player;
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::{EvalParams, MinimaxBot, SearchLimits};
+use hyperchess::infrastructure::book::{
+    BookBuilder, BookEntry, BookProbe, OpeningBook, parse_game, position_key,
+};
+use hyperchess::infrastructure::training_data::GameOutcome;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+const GAMES_2D: &str = "\
+1. e2e4 e7e5 2. g1f3 b8c6 3. f1c4 g8f6 4. e1g1 f8c5 1-0
+e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 1/2-1/2
+e2e4 c7c5 g1f3 d7d6 0-1
+d2d4 d7d5 c2c4 1-0";
+
+fn book_2d(depth: usize) -> OpeningBook {
+    let mut builder = BookBuilder::new(2, 8, depth);
+    for line in GAMES_2D.lines() {
+        let (moves, outcome) = parse_game(line, 2, 8).unwrap();
+        builder.add_game(&moves, outcome).unwrap();
+    }
+    assert_eq!(builder.games(), 4);
+    builder.build()
+}
+
+/// The position after `moves` from the start, with the side to move.
+fn after(moves: &str) -> (Board, Player) {
+    let mut board = Board::new(2, 8);
+    let mut player = Player::White;
+    for mv in moves.split_whitespace() {
+        let mv = parse_move(&mut board, player, mv).unwrap();
+        board.apply_move(&mv).unwrap();
+        player = player.opponent();
+    }
+    (board, player)
+}
+
+fn book_moves(book: &OpeningBook, moves: &str) -> Vec<(String, u16)> {
+    let (board, player) = after(moves);
+    book.moves(&board, player)
+        .iter()
+        .map(|(mv, w)| (format_move(mv), *w))
+        .collect()
+}
+
+#[test]
+fn test_weights_follow_results() {
+    let book = book_2d(16);
+    // e2e4 won one, drew one and lost one for White; d2d4 won.
+    assert_eq!(
+        book_moves(&book, ""),
+        vec![("e2e4".to_string(), 3), ("d2d4".to_string(), 2)]
+    );
+    // Black's c7c5 won; e7e5 lost once and drew once.
+    assert_eq!(
+        book_moves(&book, "e2e4"),
+        vec![("c7c5".to_string(), 2), ("e7e5".to_string(), 1)]
+    );
+    assert!(book_moves(&book, "a2a3").is_empty());
+
+    // A shallower book stops recording sooner.
+    let shallow = book_2d(2);
+    assert!(!book_moves(&shallow, "e2e4").is_empty());
+    assert!(book_moves(&shallow, "e2e4 e7e5").is_empty());
+}
+
+#[test]
+fn test_polyglot_layout_and_castling() {
+    let book = book_2d(16);
+    assert!(book.is_polyglot());
+    let mut bytes = Vec::new();
+    book.write(&mut bytes).unwrap();
+    assert_eq!(bytes.len(), 16 * book.entries().len());
+
+    // Entries are sorted by big-endian key; d2d4 from the start position
+    // encodes as to d4 (27) | from d2 (11) << 6.
+    let keys: Vec<u64> = bytes
+        .chunks(16)
+        .map(|e| u64::from_be_bytes(e[..8].try_into().unwrap()))
+        .collect();
+    assert!(keys.is_sorted());
+    let start = position_key(&Board::new(2, 8), Player::White);
+    let d2d4 = bytes
+        .chunks(16)
+        .find(|e| u64::from_be_bytes(e[..8].try_into().unwrap()) == start && e[11] == 2)
+        .unwrap();
+    assert_eq!(u16::from_be_bytes([d2d4[8], d2d4[9]]), 27 | 11 << 6);
+
+    let read = OpeningBook::read(bytes.as_slice()).unwrap();
+    assert_eq!(read.entries(), book.entries());
+
+    // Castling is stored as the king taking its rook, and played as castling.
+    let before_castling = "e2e4 e7e5 g1f3 b8c6 f1c4 g8f6";
+    let (board, player) = after(before_castling);
+    let entry = &read.lookup(position_key(&board, player))[0];
+    assert_eq!(format_move(&entry.mv), "e1h1");
+    assert_eq!(
+        book_moves(&read, before_castling),
+        vec![("e1g1".to_string(), 2)]
+    );
+}
+
+#[test]
+fn test_polyglot_keys() {
+    // Reference keys from the Polyglot book format description.
+    for (moves, key) in [
+        ("", 0x463b96181691fc9c),
+        ("e2e4", 0x823c9b50fd114196),
+        ("e2e4 d7d5", 0x0756b94461c50fb0),
+        ("e2e4 d7d5 e4e5", 0x662fafb965db29d4),
+        ("e2e4 d7d5 e4e5 f7f5", 0x22a48b5a8e47ff78),
+        ("e2e4 d7d5 e4e5 f7f5 e1e2", 0x652a607ca3f242c1),
+        ("e2e4 d7d5 e4e5 f7f5 e1e2 e8f7", 0x00fdd303c946bdd9),
+        ("a2a4 b7b5 h2h4 b5b4 c2c4", 0x3c8123ea7b067637),
+        ("a2a4 b7b5 h2h4 b5b4 c2c4 b4c3 a1a3", 0x5c3f9b829b279560),
+    ] {
+        let (board, player) = after(moves);
+        assert_eq!(position_key(&board, player), key, "{moves}");
+    }
+
+    // A book written by Polyglot tools plays here: e2e4 from the start.
+    let mut bytes = Vec::new();
+    bytes.extend(0x463b96181691fc9cu64.to_be_bytes());
+    bytes.extend((28u16 | 12 << 6).to_be_bytes());
+    bytes.extend(7u16.to_be_bytes());
+    bytes.extend(0u32.to_be_bytes());
+    let book = OpeningBook::read(bytes.as_slice()).unwrap();
+    assert_eq!(book_moves(&book, ""), vec![("e2e4".to_string(), 7)]);
+    assert!(matches!(book.entries(), [BookEntry { weight: 7, .. }]));
+
+    // Other boards keep their own hash.
+    let cube = Board::new(3, 4);
+    assert_eq!(position_key(&cube, Player::White), cube.state.hash);
+}
+
+#[test]
+fn test_hcbk_round_trip_3d() {
+    let mut builder = BookBuilder::new(3, 4, 8);
+    let mut board = Board::new(3, 4);
+    let mut player = Player::White;
+    let mut moves = Vec::new();
+    for _ in 0..4 {
+        let mv = Rules::generate_legal_moves(&mut board, player)[0].clone();
+        board.apply_move(&mv).unwrap();
+        moves.push(mv);
+        player = player.opponent();
+    }
+    builder.add_game(&moves, GameOutcome::Draw).unwrap();
+    let book = builder.build();
+    assert!(!book.is_polyglot());
+    assert_eq!(book.positions(), 4);
+
+    let mut bytes = Vec::new();
+    book.write(&mut bytes).unwrap();
+    assert_eq!(&bytes[..4], b"HCBK");
+    let read = OpeningBook::read(bytes.as_slice()).unwrap();
+    assert_eq!((read.dimension(), read.side()), (3, 4));
+    assert_eq!(read.entries(), book.entries());
+    let first = read.moves(&Board::new(3, 4), Player::White);
+    assert_eq!(first, vec![(moves[0].clone(), 1)]);
+
+    assert!(OpeningBook::read(&bytes[..bytes.len() - 3]).is_err());
+}
+
+#[test]
+fn test_variety_and_bot_probe() {
+    let book = Arc::new(book_2d(16));
+    let start = Board::new(2, 8);
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..20 {
+        let mv = book.pick(&start, Player::White, 0.0, &mut rng).unwrap();
+        assert_eq!(format_move(&mv), "e2e4");
+    }
+    let picks: Vec<String> = (0..200)
+        .map(|_| format_move(&book.pick(&start, Player::White, 1.0, &mut rng).unwrap()))
+        .collect();
+    assert!(picks.iter().any(|m| m == "d2d4") && picks.iter().any(|m| m == "e2e4"));
+
+    let mut bot = MinimaxBot::try_new_from_params(2, Duration::from_secs(10), 16, 1, 2, 8).unwrap();
+    bot.set_book(Some(BookProbe {
+        book,
+        depth: 2,
+        variety: 0.0,
+    }));
+    let limits = SearchLimits::depth(2);
+    let found = bot.search(&start, Player::White, &limits).unwrap();
+    assert!(found.book);
+    assert_eq!(format_move(&found.best_move), "e2e4");
+
+    // Past the book depth, and when analysing, the bot searches.
+    let (deep, player) = after("e2e4 c7c5");
+    assert!(!bot.search(&deep, player, &limits).unwrap().book);
+    let mate = SearchLimits {
+        mate: Some(1),
+        ..SearchLimits::default()
+    };
+    assert!(
+        !bot.search(&start, Player::White, &mate)
+            .is_some_and(|r| r.book)
+    );
+}
+
+#[test]
+fn test_self_play_book() {
+    let options = SelfPlayBookOptions {
+        games: 3,
+        depth: 4,
+        limits: SearchLimits::depth(1),
+        max_plies: 12,
+        ..SelfPlayBookOptions::default()
+    };
+    let mut games = 0;
+    let book = build_from_self_play(&options, &EvalParams::default(), |game| {
+        assert!(game.moves.len() <= 12);
+        games += 1;
+    })
+    .unwrap();
+    assert_eq!(games, 3);
+    assert!(book.positions() > 0);
+    assert!(book.entries().iter().all(|e| e.weight > 0));
+}
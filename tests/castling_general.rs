@@ -1,4 +1,5 @@
 use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation::parse_move;
 use hyperchess::domain::coordinate::Coordinate;
 use hyperchess::domain::models::{Piece, PieceType, Player};
 use hyperchess::domain::rules::Rules;
@@ -114,3 +115,20 @@ fn test_castling_3d_blocked() {
         "Castling should be blocked on 3D board path"
     );
 }
+
+#[test]
+fn test_hash_follows_castling_rights() {
+    // Both kings step off and back, giving up every right on the way.
+    let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let mut player = Player::White;
+    for mv in ["e1e2", "e8e7", "e2e1", "e7e8", "a1a2"] {
+        let mv = parse_move(&mut board, player, mv).unwrap();
+        board.apply_move(&mv).unwrap();
+        player = player.opponent();
+
+        let mut rehashed = board.clone();
+        rehashed.update_hash(player);
+        assert_eq!(board.state.hash, rehashed.state.hash, "{mv:?}");
+    }
+    assert_eq!(board.state.castling_rights, 0);
+}
@@ -0,0 +1,13 @@
+use hyperchess::config::AppConfig;
+
+/// Default settings on `concurrency` threads and a 16 MB table, searching to
+/// `depth` when given.
+pub fn small_config(concurrency: usize, depth: Option<usize>) -> AppConfig {
+    let mut config = AppConfig::default();
+    if let Some(depth) = depth {
+        config.minimax.depth = depth;
+    }
+    config.compute.concurrency = concurrency;
+    config.compute.memory = 16;
+    config
+}
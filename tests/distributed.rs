@@ -133,6 +133,15 @@ fn test_zobrist_different_for_different_sizes() {
     assert_ne!(keys_64.black_to_move, keys_16.black_to_move);
 }
 
+#[test]
+fn test_zobrist_keys_are_pinned() {
+    // HCBK books store these hashes, so the keys must not change with rand.
+    let keys = ZobristKeys::new(64);
+    assert_eq!(keys.piece_keys[0], 0x37aa_8c28_765d_c614);
+    assert_eq!(keys.black_to_move, 0x152d_18ca_794c_fb38);
+    assert_eq!(keys.castling_keys[15], 0xdeef_e842_2d86_fa0a);
+}
+
 #[test]
 fn test_zobrist_determinism_across_threads() {
     let handles: Vec<_> = (0..4)
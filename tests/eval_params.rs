@@ -0,0 +1,71 @@
+use hyperchess::config::AppConfig;
+use hyperchess::domain::board::Board;
+use hyperchess::infrastructure::ai::eval::Evaluator;
+use hyperchess::infrastructure::ai::eval_params::EvalOverride;
+use hyperchess::infrastructure::ai::{EvalParamSet, EvalParams, EvalParamsError, MinimaxBot};
+
+fn sample_set() -> EvalParamSet {
+    let mut set = EvalParamSet::default();
+    set.params.insert("tempo_bonus".into(), 0);
+    set.overrides.push(EvalOverride {
+        dimension: 3,
+        side: None,
+        params: [("king_zone_contact_mg".to_string(), 40)].into(),
+    });
+    set
+}
+
+#[test]
+fn test_param_files_round_trip() {
+    let dir = std::env::temp_dir().join(format!("hyperchess-eval-params-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["params.toml", "params.json"] {
+        let path = dir.join(name);
+        sample_set().save(&path).unwrap();
+        assert_eq!(EvalParamSet::load(&path).unwrap(), sample_set(), "{name}");
+    }
+
+    let bad = dir.join("bad.toml");
+    std::fs::write(&bad, "[params]\npawn_value = 100\n").unwrap();
+    assert!(matches!(
+        EvalParamSet::load(&bad),
+        Err(EvalParamsError::UnknownParam(_))
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_bots_carry_params_for_their_board() {
+    let mut config = AppConfig::default();
+    config.compute.memory = 16;
+    config.compute.concurrency = 1;
+    config.eval.set = sample_set();
+
+    let flat = MinimaxBot::new(&config, 2, 8);
+    assert_eq!(flat.eval_params().tempo_bonus, 0);
+    assert_eq!(
+        flat.eval_params().king_zone_contact_mg,
+        EvalParams::default().king_zone_contact_mg
+    );
+    let cube = MinimaxBot::new(&config, 3, 4);
+    assert_eq!(cube.eval_params().king_zone_contact_mg, 40);
+
+    let mut bot = MinimaxBot::new_from_params(1, std::time::Duration::from_secs(1), 16, 1);
+    assert_eq!(bot.eval_params(), &EvalParams::default());
+    bot.set_eval_params(config.eval_params(2, 8));
+    assert_eq!(bot.eval_params().tempo_bonus, 0);
+}
+
+#[test]
+fn test_weights_change_the_evaluation() {
+    let board = Board::new(2, 8);
+    let defaults = EvalParams::default();
+    let no_tempo = sample_set().resolve(2, 8);
+    assert_eq!(
+        Evaluator::evaluate(&board, &defaults) - Evaluator::evaluate(&board, &no_tempo),
+        defaults.tempo_bonus
+    );
+    for name in EvalParams::NAMES.iter().filter(|&&n| n != "tempo_bonus") {
+        assert_eq!(defaults.get(name), no_tempo.get(name), "{name}");
+    }
+}
@@ -0,0 +1,66 @@
+use hyperchess::domain::board::Board;
+use hyperchess::infrastructure::ai::EvalParams;
+use hyperchess::infrastructure::ai::eval::{EvalTerm, EvalTrace, Evaluator, TermScore};
+
+fn term(trace: &EvalTrace, term: EvalTerm) -> (TermScore, TermScore) {
+    let t = trace.terms.iter().find(|t| t.term == term).unwrap();
+    (t.white, t.black)
+}
+
+#[test]
+fn test_trace_adds_up_to_evaluate() {
+    let boards = [
+        Board::new(2, 8),
+        Board::new(3, 8),
+        Board::from_fen("r1bq1rk1/pp3ppp/2n1pn2/3p4/1bPP4/2N1PN2/PP1B1PPP/R2QKB1R w KQ - 4 8")
+            .unwrap(),
+        Board::from_fen("8/5k2/3p4/1p1Pp2p/pP2Pp1P/P4P1K/8/8 b - - 37 60").unwrap(),
+    ];
+    for board in &boards {
+        let trace = Evaluator::evaluate_trace(board, &EvalParams::default());
+        assert_eq!(
+            trace.value,
+            Evaluator::evaluate(board, &EvalParams::default())
+        );
+        assert_eq!(trace.terms.len(), EvalTerm::ALL.len());
+        assert_eq!(
+            trace.mg,
+            trace.terms.iter().map(|t| t.net().mg).sum::<i32>()
+        );
+        assert_eq!(
+            trace.eg,
+            trace.terms.iter().map(|t| t.net().eg).sum::<i32>()
+        );
+        assert_eq!(trace.value, trace.tapered + trace.tempo + trace.rule50);
+    }
+}
+
+#[test]
+fn test_start_position_is_symmetric() {
+    let trace = Evaluator::evaluate_trace(&Board::new(2, 8), &EvalParams::default());
+    for t in &trace.terms {
+        assert_eq!(t.white, t.black, "{}", t.term.name());
+    }
+    let (white, _) = term(&trace, EvalTerm::Material);
+    assert_eq!(white, TermScore { mg: 4000, eg: 4360 });
+    assert_eq!(trace.phase, trace.start_phase);
+    assert_eq!(trace.rule50, 0);
+}
+
+#[test]
+fn test_terms_are_credited_to_their_side() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/P7/4K3 w - - 10 40").unwrap();
+    let trace = Evaluator::evaluate_trace(&board, &EvalParams::default());
+
+    let (white, black) = term(&trace, EvalTerm::PassedPawns);
+    assert!(white.mg > 0 && white.eg > 0);
+    assert_eq!(black, TermScore::default());
+    let (white, black) = term(&trace, EvalTerm::IsolatedPawns);
+    assert!(white.mg < 0);
+    assert_eq!(black, TermScore::default());
+    assert!(trace.rule50 < 0);
+
+    let text = trace.to_string();
+    assert!(text.contains("passed_pawns"), "{text}");
+    assert!(text.ends_with(&format!("{:+} (White's view)", trace.value)));
+}
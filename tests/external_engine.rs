@@ -0,0 +1,181 @@
+use hyperchess::application::game_service::GameService;
+use hyperchess::config::EngineConfig;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::domain::rules::Rules;
+use hyperchess::domain::services::PlayerStrategy;
+use hyperchess::infrastructure::external_engine::{EngineError, ExternalEngine};
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// The scripted engine in `tests/support/engine_stub.rs`. It is an example
+/// rather than a binary so it is never installed. A plain `cargo test`
+/// builds it; runs that name targets, such as
+/// `cargo test --test external_engine`, need `--examples` as well.
+fn stub_path() -> &'static str {
+    static PATH: OnceLock<String> = OnceLock::new();
+    PATH.get_or_init(|| {
+        // This binary lives in `<target>/<profile>/deps`.
+        let exe = env::current_exe().unwrap();
+        let profile_dir: PathBuf = exe.ancestors().nth(2).unwrap().into();
+        let stub = profile_dir
+            .join("examples")
+            .join(format!("engine-stub{}", env::consts::EXE_SUFFIX));
+        assert!(
+            stub.exists(),
+            "engine-stub is not built at {}; add --examples to the cargo test command",
+            stub.display()
+        );
+        stub.display().to_string()
+    })
+}
+
+fn stub(args: &[&str]) -> ExternalEngine {
+    ExternalEngine::new(EngineConfig {
+        command: stub_path().to_string(),
+        args: args.iter().map(|s| s.to_string()).collect(),
+        move_time_ms: 50,
+        timeout_ms: 200,
+        ..EngineConfig::default()
+    })
+}
+
+fn assert_legal(board: &Board, player: Player, mv: &hyperchess::domain::models::Move) {
+    let legal = Rules::generate_legal_moves(&mut board.clone(), player);
+    assert!(legal.contains(mv), "engine move {:?} is not legal", mv);
+}
+
+#[test]
+fn test_plays_legal_move_over_uci() {
+    let board = Board::new(2, 8);
+    let mut engine = stub(&[]);
+    let mv = engine.get_move(&board, Player::White).expect("engine move");
+    assert_legal(&board, Player::White, &mv);
+    assert_eq!(engine.name(), Some("EngineStub"));
+}
+
+#[test]
+fn test_plays_black_from_fen_position() {
+    let mut board = Board::new(2, 8);
+    let first = Rules::generate_legal_moves(&mut board, Player::White)[0].clone();
+    board.apply_move(&first).unwrap();
+
+    let mut engine = stub(&[]);
+    let mv = engine.get_move(&board, Player::Black).expect("engine move");
+    assert_legal(&board, Player::Black, &mv);
+}
+
+#[test]
+fn test_nd_protocol_3d() {
+    let board = Board::new(3, 4);
+    let mut engine = stub(&["--nd"]);
+    let mv = engine.get_move(&board, Player::White).expect("engine move");
+    assert_legal(&board, Player::White, &mv);
+}
+
+#[test]
+fn test_nd_requires_engine_support() {
+    let board = Board::new(3, 4);
+    let mut engine = stub(&[]);
+    assert!(engine.get_move(&board, Player::White).is_none());
+    assert!(matches!(
+        engine.last_error(),
+        Some(EngineError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn test_restarts_after_crash() {
+    let board = Board::new(2, 8);
+    let mut engine = stub(&["--crash-on", "2"]);
+    assert!(engine.get_move(&board, Player::White).is_some());
+    // Second `go` kills the process; the adapter restarts and retries.
+    assert!(engine.get_move(&board, Player::White).is_some());
+    assert!(engine.last_error().is_none());
+}
+
+#[test]
+fn test_gives_up_when_engine_keeps_crashing() {
+    let board = Board::new(2, 8);
+    let mut engine = stub(&["--crash-on", "1"]);
+    assert!(engine.get_move(&board, Player::White).is_none());
+    assert!(matches!(engine.last_error(), Some(EngineError::Crashed)));
+}
+
+#[test]
+fn test_stop_recovers_overrunning_engine() {
+    let board = Board::new(2, 8);
+    let mut engine = stub(&["--wait-for-stop"]);
+    let mv = engine
+        .get_move(&board, Player::White)
+        .expect("move after stop");
+    assert_legal(&board, Player::White, &mv);
+}
+
+#[test]
+fn test_hung_engine_times_out() {
+    let board = Board::new(2, 8);
+    let mut engine = stub(&["--hang"]);
+    let start = Instant::now();
+    assert!(engine.get_move(&board, Player::White).is_none());
+    assert!(matches!(engine.last_error(), Some(EngineError::Timeout(_))));
+    // movetime + margin before `stop`, then one more margin.
+    assert!(start.elapsed() < Duration::from_secs(3));
+}
+
+#[test]
+fn test_illegal_reply_rejected() {
+    let board = Board::new(2, 8);
+    let mut engine = stub(&["--illegal"]);
+    assert!(engine.get_move(&board, Player::White).is_none());
+    assert!(matches!(
+        engine.last_error(),
+        Some(EngineError::IllegalMove(_))
+    ));
+}
+
+#[test]
+fn test_missing_binary_reports_spawn_error() {
+    let mut engine = ExternalEngine::new(EngineConfig {
+        command: "/nonexistent/engine".to_string(),
+        ..EngineConfig::default()
+    });
+    assert!(engine.get_move(&Board::new(2, 8), Player::White).is_none());
+    assert!(matches!(engine.last_error(), Some(EngineError::Spawn(_))));
+}
+
+#[test]
+fn test_game_service_engine_vs_engine() {
+    let board = Board::new(2, 8);
+    let mut game = GameService::new(board, Box::new(stub(&[])), Box::new(stub(&[])));
+    for _ in 0..6 {
+        game.perform_next_move().expect("engine move");
+    }
+    assert_eq!(game.turn(), Player::White);
+}
+
+#[test]
+fn test_hyperchess_uci_front_end() {
+    let mut options = std::collections::BTreeMap::new();
+    options.insert("Hash".to_string(), "16".to_string());
+    options.insert("Threads".to_string(), "1".to_string());
+    let mut engine = ExternalEngine::new(EngineConfig {
+        command: env!("CARGO_BIN_EXE_hyperchess-uci").to_string(),
+        move_time_ms: 200,
+        timeout_ms: 2000,
+        options,
+        ..EngineConfig::default()
+    });
+
+    let board = Board::new(2, 8);
+    let mv = engine.get_move(&board, Player::White).expect("2D move");
+    assert_legal(&board, Player::White, &mv);
+    assert!(engine.name().unwrap().starts_with("HyperChess"));
+
+    let board = Board::new(3, 4);
+    let mv = engine.get_move(&board, Player::White).expect("3D move");
+    assert_legal(&board, Player::White, &mv);
+}
@@ -0,0 +1,68 @@
+use hyperchess::application::match_runner::{MatchEngine, MatchGame, MatchOptions, play_match};
+use hyperchess::application::sprt::Sprt;
+use hyperchess::infrastructure::ai::SearchLimits;
+
+fn engine(name: &str, depth: usize) -> MatchEngine {
+    MatchEngine {
+        name: name.to_string(),
+        limits: SearchLimits::depth(depth),
+        ..MatchEngine::default()
+    }
+}
+
+fn short_games() -> MatchOptions {
+    MatchOptions {
+        pairs: 2,
+        concurrency: 2,
+        max_plies: 24,
+        seed: 7,
+        ..MatchOptions::default()
+    }
+}
+
+#[test]
+fn test_pairs_share_openings_with_colours_swapped() {
+    let (a, b) = (engine("a", 2), engine("b", 1));
+    let report = play_match(&a, &b, &short_games(), |_, _| {}).unwrap();
+
+    assert_eq!(report.score.games(), 4);
+    assert_eq!(report.games.len(), 4);
+    for pair in report.games.chunks(2) {
+        assert_eq!(pair[0].pair, pair[1].pair);
+        assert_eq!(pair[0].opening, pair[1].opening);
+        assert!(pair[0].a_is_white && !pair[1].a_is_white);
+        assert!(pair.iter().all(|g| g.plies <= 24));
+    }
+    let points: f64 = report.games.iter().map(|g| g.points_for_a()).sum();
+    assert_eq!(points, report.score.score() * 4.0);
+
+    // The same seed gives the same openings.
+    let again = play_match(&a, &b, &short_games(), |_, _| {}).unwrap();
+    let openings =
+        |games: &[MatchGame]| -> Vec<String> { games.iter().map(|g| g.opening.clone()).collect() };
+    assert_eq!(openings(&report.games), openings(&again.games));
+}
+
+#[test]
+fn test_sprt_verdict_stops_the_match() {
+    let options = MatchOptions {
+        pairs: 50,
+        concurrency: 1,
+        // Bounds of zero: the first game decides.
+        sprt: Some(Sprt {
+            alpha: 0.5,
+            beta: 0.5,
+            ..Sprt::default()
+        }),
+        ..short_games()
+    };
+    let mut seen = 0;
+    let report = play_match(&engine("a", 1), &engine("b", 1), &options, |_, score| {
+        seen = score.games();
+    })
+    .unwrap();
+
+    assert!(report.verdict.is_some());
+    assert!(report.score.games() <= 2);
+    assert_eq!(seen, report.score.games());
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::{Board, notation};
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::interface::uci::UciInterface;
+
+const MATE_IN_TWO: &str = "7k/8/8/5K2/8/8/8/6Q1 w - - 0 1";
+
+#[test]
+fn test_mate_distance_survives_the_tt() {
+    let mut board = Board::from_fen(MATE_IN_TWO).unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+
+    let first = bot
+        .search(&board, Player::White, &SearchLimits::depth(5))
+        .unwrap();
+    assert_eq!(first.mate, Some(2));
+    // Deeper, with the mate already in the TT from shallower plies.
+    let again = bot
+        .search(&board, Player::White, &SearchLimits::depth(7))
+        .unwrap();
+    assert_eq!(again.mate, Some(2));
+
+    for mv in &first.pv[..2] {
+        board.apply_move(mv).unwrap();
+    }
+    let next = bot
+        .search(&board, Player::White, &SearchLimits::depth(5))
+        .unwrap();
+    assert_eq!(next.mate, Some(1));
+    assert_eq!(notation::format_move(&next.best_move), "g1g7");
+}
+
+#[test]
+fn test_mated_side_sees_negative_distance() {
+    let mut board = Board::from_fen(MATE_IN_TWO).unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let mv = notation::parse_move(&mut board, Player::White, "f5f6").unwrap();
+    board.apply_move(&mv).unwrap();
+
+    let result = bot
+        .search(&board, Player::Black, &SearchLimits::depth(4))
+        .unwrap();
+    assert_eq!(result.mate, Some(-1));
+}
+
+#[test]
+fn test_uci_reports_score_mate() {
+    let mut uci = UciInterface::new(small_config(1, None));
+    let input = b"position fen 7k/8/8/5K2/8/8/8/6Q1 w - - 0 1\n\
+        go depth 5\n";
+    let mut output = Vec::new();
+    uci.run(&input[..], &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let last_info = output
+        .lines()
+        .rfind(|l| l.starts_with("info depth"))
+        .unwrap();
+    assert!(
+        last_info.contains(" score mate 2 "),
+        "output was:\n{output}"
+    );
+}
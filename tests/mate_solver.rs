@@ -0,0 +1,51 @@
+use hyperchess::domain::board::{Board, notation};
+use hyperchess::domain::models::{Move, Player};
+use hyperchess::infrastructure::ai::{MateResult, MateSolver};
+
+fn line(moves: &[Move]) -> Vec<String> {
+    moves.iter().map(notation::format_move).collect()
+}
+
+#[test]
+fn test_mate_in_one() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    let MateResult::Mate(solution) =
+        MateSolver::with_node_limit(100_000).solve(&board, Player::White, 3)
+    else {
+        panic!("back-rank mate not found");
+    };
+    assert_eq!(solution.moves(), 1);
+    assert_eq!(line(&solution.main_line()), ["a1a8"]);
+    assert!(solution.defences.is_empty());
+}
+
+#[test]
+fn test_mate_in_two_covers_every_defence() {
+    let board = Board::from_fen("7k/8/8/5K2/8/8/8/6Q1 w - - 0 1").unwrap();
+    let MateResult::Mate(solution) =
+        MateSolver::with_node_limit(100_000).solve(&board, Player::White, 2)
+    else {
+        panic!("mate in 2 not found");
+    };
+    assert_eq!(solution.moves(), 2);
+    assert_eq!(line(&solution.main_line()), ["f5f6", "h8h7", "g1g7"]);
+    assert_eq!(solution.to_string(), "f5f6\n  h8h7\n    g1g7#\n");
+}
+
+#[test]
+fn test_no_mate_is_proven() {
+    let mut solver = MateSolver::with_node_limit(1_000_000);
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/r4PPP/R5K1 w - - 0 1").unwrap();
+    assert_eq!(solver.solve(&board, Player::White, 2), MateResult::NoMate);
+
+    // The 3D opening position has no mate in one either.
+    let board = Board::new(3, 8);
+    assert_eq!(solver.solve(&board, Player::White, 1), MateResult::NoMate);
+}
+
+#[test]
+fn test_node_budget() {
+    let board = Board::from_fen("7k/8/8/5K2/8/8/8/6Q1 w - - 0 1").unwrap();
+    let mut solver = MateSolver::with_node_limit(50);
+    assert_eq!(solver.solve(&board, Player::White, 2), MateResult::Unknown);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation;
+use hyperchess::domain::models::Player;
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::{MctsBot, SearchLimits};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn playouts(n: u64) -> SearchLimits {
+    SearchLimits {
+        nodes: Some(n),
+        ..SearchLimits::default()
+    }
+}
+
+#[test]
+fn test_mcts_finds_back_rank_mate() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    let mut bot = MctsBot::new(&small_config(2, None));
+
+    let result = bot.search(&board, Player::White, &playouts(2000)).unwrap();
+    assert_eq!(notation::format_move(&result.best_move), "a1a8");
+    assert!(result.nodes >= 2000);
+}
+
+#[test]
+fn test_mcts_takes_hanging_queen() {
+    let board = Board::from_fen("4k3/8/8/3q4/8/2N5/8/4K3 w - - 0 1").unwrap();
+    let mut bot = MctsBot::new(&small_config(2, None));
+
+    let result = bot.search(&board, Player::White, &playouts(500)).unwrap();
+    assert_eq!(notation::format_move(&result.best_move), "c3d5");
+    assert!(result.score > 300, "score {}", result.score);
+}
+
+#[test]
+fn test_mcts_plays_legal_4d_move_in_time() {
+    let mut board = Board::new(4, 8);
+    let mut bot = MctsBot::new(&small_config(2, None));
+
+    let start = Instant::now();
+    let limits = SearchLimits::movetime(Duration::from_millis(300));
+    let result = bot.search(&board, Player::White, &limits).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(3));
+    assert!(Rules::generate_legal_moves(&mut board, Player::White).contains(&result.best_move));
+}
+
+#[test]
+fn test_mcts_keeps_5d_movetime() {
+    let board = Board::new(5, 8);
+    let mut bot = MctsBot::new(&small_config(2, None));
+
+    let start = Instant::now();
+    let limits = SearchLimits::movetime(Duration::from_millis(100));
+    bot.search(&board, Player::White, &limits).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_mcts_stops_on_flag() {
+    let board = Board::new(3, 8);
+    let mut bot = MctsBot::new(&small_config(2, None));
+    let stop = bot.stop_flag();
+
+    let limits = SearchLimits {
+        infinite: true,
+        ..SearchLimits::default()
+    };
+    let start = Instant::now();
+    let stopper = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(200));
+        stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    });
+    assert!(bot.search(&board, Player::White, &limits).is_some());
+    stopper.join().unwrap();
+    assert!(start.elapsed() < Duration::from_secs(3));
+}
+
+#[test]
+fn test_mcts_moves_in_drawn_positions() {
+    // Knights out and back: the position is already in the history.
+    let mut board = Board::from_fen("4k1n1/p7/8/8/8/8/PP6/4K1N1 w - - 0 1").unwrap();
+    let mut player = Player::White;
+    for mv in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+        let mv = notation::parse_move(&mut board, player, mv).unwrap();
+        board.apply_move(&mv).unwrap();
+        player = player.opponent();
+    }
+    assert!(board.is_repetition());
+    let mut bot = MctsBot::new(&small_config(2, None));
+    let result = bot.search(&board, Player::White, &playouts(100)).unwrap();
+    assert!(Rules::generate_legal_moves(&mut board, Player::White).contains(&result.best_move));
+
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80").unwrap();
+    let result = bot.search(&board, Player::White, &playouts(100)).unwrap();
+    assert!(Rules::generate_legal_moves(&mut board, Player::White).contains(&result.best_move));
+}
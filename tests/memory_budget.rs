@@ -0,0 +1,34 @@
+mod common;
+
+use common::small_config;
+use hyperchess::infrastructure::ai::MinimaxBot;
+use hyperchess::interface::uci::UciInterface;
+
+#[test]
+fn test_bot_refuses_oversized_board() {
+    assert!(MinimaxBot::try_new(&small_config(1, None), 2, 8).is_ok());
+
+    let Err(err) = MinimaxBot::try_new(&small_config(64, None), 5, 8) else {
+        panic!("a 5D board with 64 threads fit in 16 MB");
+    };
+    assert_eq!(err.budget_mb, 16);
+    assert!(err.to_string().contains("5D side-8"), "{err}");
+}
+
+#[test]
+fn test_uci_keeps_settings_that_fit() {
+    let mut uci = UciInterface::new(small_config(1, None));
+    let input = b"setoption name Dimension value 5\n\
+        go depth 1\n";
+    let mut output = Vec::new();
+    uci.run(&input[..], &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("info string a 5D"), "output was:\n{output}");
+    // Still searching the 2D board.
+    assert!(
+        output
+            .lines()
+            .any(|l| l.starts_with("bestmove ") && l != "bestmove (none)")
+    );
+}
@@ -1,10 +1,13 @@
+mod common;
+
+use common::small_config;
 use hyperchess::config::AppConfig;
 use hyperchess::domain::board::Board;
 use hyperchess::domain::coordinate::Coordinate;
 use hyperchess::domain::models::{Piece, PieceType, Player};
 use hyperchess::domain::rules::Rules;
 use hyperchess::domain::services::PlayerStrategy;
-use hyperchess::infrastructure::ai::MinimaxBot;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
 
 fn coord(x: usize, y: usize) -> Coordinate {
     Coordinate::new(vec![x as u8, y as u8])
@@ -177,3 +180,14 @@ fn test_verify_mate_validity() {
 
 #[test]
 fn test_avoid_immediate_mate() {}
+
+#[test]
+fn test_4d_search_history_fits() {
+    // 4096 cells: quadratic history tables would need over a gigabyte.
+    let board = Board::new(4, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 4, 8);
+    let result = bot
+        .search(&board, Player::White, &SearchLimits::depth(2))
+        .unwrap();
+    assert_eq!(result.depth, 2);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation;
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::interface::uci::UciInterface;
+
+fn multi_pv(k: usize) -> SearchLimits {
+    SearchLimits {
+        multi_pv: k,
+        ..SearchLimits::default()
+    }
+}
+
+#[test]
+fn test_analyze_returns_top_k_lines() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, Some(3)), 2, 8);
+    let lines = bot.analyze(&board, Player::White, &multi_pv(4));
+    assert_eq!(lines.len(), 4);
+    for (i, line) in lines.iter().enumerate() {
+        assert_eq!(line.multipv, i + 1);
+        assert_eq!(line.depth, 3);
+        assert!(!line.pv.is_empty());
+    }
+    assert!(lines.windows(2).all(|w| w[0].score >= w[1].score));
+
+    for (i, a) in lines.iter().enumerate() {
+        for b in &lines[i + 1..] {
+            assert_ne!(a.pv[0], b.pv[0], "lines must start with distinct moves");
+        }
+    }
+}
+
+#[test]
+fn test_winning_capture_ranked_first() {
+    let board = Board::from_fen("4k3/8/8/3q4/8/2N5/8/4K3 w - - 0 1").unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, Some(3)), 2, 8);
+    let lines = bot.analyze(&board, Player::White, &multi_pv(3));
+    assert_eq!(lines.len(), 3);
+    assert_eq!(notation::format_move(&lines[0].pv[0]), "c3d5");
+    // The other lines are scored exactly, not as fail-low bounds.
+    assert!(lines[0].score > lines[1].score + 500);
+    assert!(lines[1].score > -3000);
+}
+
+#[test]
+fn test_fewer_legal_moves_than_k() {
+    let board = Board::from_fen("7k/8/8/8/8/8/8/K7 w - - 0 1").unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, Some(2)), 2, 8);
+    let lines = bot.analyze(&board, Player::White, &multi_pv(10));
+    assert_eq!(lines.len(), 3);
+}
+
+#[test]
+fn test_uci_multipv_option() {
+    let mut uci = UciInterface::new(small_config(1, Some(2)));
+    let input = b"setoption name MultiPV value 3\nposition startpos\ngo depth 2\n";
+    let mut output = Vec::new();
+    uci.run(&input[..], &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let last_depth: Vec<&str> = output
+        .lines()
+        .filter(|l| l.starts_with("info depth 2 "))
+        .collect();
+    assert_eq!(last_depth.len(), 3, "output was:\n{output}");
+    for (i, line) in last_depth.iter().enumerate() {
+        assert!(line.contains(&format!(" multipv {} ", i + 1)));
+    }
+    assert!(output.lines().last().unwrap().starts_with("bestmove "));
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::{Move, Player};
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::eval::Evaluator;
+use hyperchess::infrastructure::ai::nnue_trainer::{NnueTrainer, TrainerOptions, TrainingSet};
+use hyperchess::infrastructure::ai::{
+    EvalParams, MinimaxBot, Network, NnueAccumulator, NnueError, SearchLimits,
+};
+use hyperchess::infrastructure::training_data::{GameOutcome, TrainingRecord};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+fn random_network(dimension: usize, side: usize, seed: u64) -> Arc<Network> {
+    let options = TrainerOptions {
+        hidden: 16,
+        seed,
+        ..TrainerOptions::default()
+    };
+    Arc::new(NnueTrainer::new(dimension, side, options).network())
+}
+
+/// Play random legal moves, checking the incremental accumulators against a
+/// full refresh after every move and again as each is unmade.
+fn check_incremental(dimension: usize, side: usize) {
+    let network = random_network(dimension, side, 7);
+    let mut board = network.attach(&Board::new(dimension, side));
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut player = Player::White;
+    let mut made = Vec::new();
+    let mut frames = vec![NnueAccumulator::of(&board).unwrap().current().to_vec()];
+
+    for _ in 0..40 {
+        let moves = Rules::generate_legal_moves(&mut board, player);
+        let Some(mv) = moves.choose(&mut rng).cloned() else {
+            break;
+        };
+        let info = board.apply_move(&mv).unwrap();
+        made.push((mv, info));
+        player = player.opponent();
+
+        let incremental = NnueAccumulator::of(&board).unwrap();
+        let fresh = network.attach(&board);
+        assert_eq!(
+            incremental.current(),
+            NnueAccumulator::of(&fresh).unwrap().current()
+        );
+        assert_eq!(incremental.evaluate(), network.evaluate(&board));
+        frames.push(incremental.current().to_vec());
+    }
+    assert!(made.len() > 10);
+
+    while let Some((mv, info)) = made.pop() {
+        frames.pop();
+        board.unmake_move(&mv, info);
+        let restored = NnueAccumulator::of(&board).unwrap();
+        assert_eq!(restored.current(), frames.last().unwrap().as_slice());
+    }
+}
+
+#[test]
+fn test_incremental_accumulators_match_refresh_2d() {
+    check_incremental(2, 8);
+}
+
+#[test]
+fn test_incremental_accumulators_match_refresh_3d() {
+    check_incremental(3, 4);
+}
+
+#[test]
+fn test_network_round_trip() {
+    let network = random_network(3, 4, 3);
+    let mut bytes = Vec::new();
+    network.write(&mut bytes).unwrap();
+    assert_eq!(&bytes[..4], b"HCNN");
+    assert_eq!(Network::read(bytes.as_slice()).unwrap(), *network);
+
+    assert!(matches!(
+        Network::read(&b"HCTD\x01\x00"[..]),
+        Err(NnueError::BadMagic)
+    ));
+    assert!(matches!(
+        Network::read(&bytes[..bytes.len() - 1]),
+        Err(NnueError::BadShape(_))
+    ));
+    assert!(network.check_board(2, 8).is_err());
+}
+
+/// Positions after random play, labelled by the classical evaluation.
+fn labelled_records(count: usize) -> Vec<TrainingRecord> {
+    let mut rng = StdRng::seed_from_u64(5);
+    let params = EvalParams::default();
+    let mut records = Vec::new();
+    while records.len() < count {
+        let mut board = Board::new(2, 8);
+        let mut player = Player::White;
+        for _ in 0..30 {
+            let moves = Rules::generate_legal_moves(&mut board, player);
+            let Some(mv) = moves.choose(&mut rng).cloned() else {
+                break;
+            };
+            board.apply_move(&mv).unwrap();
+            player = player.opponent();
+            let score = Evaluator::evaluate(&board, &params);
+            records.push(TrainingRecord {
+                hfen: board.to_hfen(player),
+                score: score.clamp(-2000, 2000) as i16,
+                best_move: mv,
+                result: match score {
+                    s if s > 100 => GameOutcome::WhiteWin,
+                    s if s < -100 => GameOutcome::BlackWin,
+                    _ => GameOutcome::Draw,
+                },
+            });
+        }
+    }
+    records
+}
+
+#[test]
+fn test_trainer_reduces_loss() {
+    let records = labelled_records(600);
+    let mut set = TrainingSet::from_records(2, 8, &records, 0.3).unwrap();
+    let options = TrainerOptions {
+        hidden: 8,
+        epochs: 15,
+        batch_size: 32,
+        learning_rate: 0.01,
+        ..TrainerOptions::default()
+    };
+    let mut trainer = NnueTrainer::new(2, 8, options);
+    let before = trainer.loss(&set.samples);
+    let mut epochs = 0;
+    trainer.train(&mut set.samples, |_| epochs += 1);
+    let after = trainer.loss(&set.samples);
+    assert_eq!(epochs, 15);
+    assert!(after < before * 0.8, "loss {before} -> {after}");
+
+    // The quantised network follows the float one's sign on a clear case.
+    let network = trainer.network();
+    let (board, _) = records
+        .iter()
+        .find(|r| r.score > 500)
+        .unwrap()
+        .position()
+        .unwrap();
+    assert!(network.evaluate(&board) > 0);
+}
+
+#[test]
+fn test_bot_searches_with_network() {
+    let board = Board::new(2, 8);
+    let mut bot =
+        MinimaxBot::try_new_from_params(3, std::time::Duration::from_secs(10), 16, 2, 2, 8)
+            .unwrap();
+    bot.set_network(Some(random_network(2, 8, 1)));
+    let found = bot
+        .search(&board, Player::White, &SearchLimits::depth(3))
+        .unwrap();
+    let legal: Vec<Move> =
+        Rules::generate_legal_moves(&mut board.clone(), Player::White).into_vec();
+    assert!(legal.contains(&found.best_move));
+    assert!(found.pv.len() >= 2);
+
+    // A network for another board leaves the classical evaluation in place.
+    bot.set_network(Some(random_network(3, 4, 1)));
+    assert!(
+        bot.search(&board, Player::White, &SearchLimits::depth(2))
+            .is_some()
+    );
+}
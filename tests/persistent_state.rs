@@ -0,0 +1,50 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::transposition::{Flag, LockFreeTT};
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+
+fn nodes(bot: &mut MinimaxBot, board: &Board) -> u64 {
+    bot.search(board, Player::White, &SearchLimits::depth(5))
+        .unwrap()
+        .nodes
+}
+
+#[test]
+fn test_tables_persist_across_searches() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+
+    let first = nodes(&mut bot, &board);
+    let second = nodes(&mut bot, &board);
+    assert!(second < first, "first {first} nodes, second {second}");
+}
+
+#[test]
+fn test_new_game_clears_tables() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+
+    let fresh = nodes(&mut bot, &board);
+    nodes(&mut bot, &board);
+    bot.new_game();
+    assert_eq!(nodes(&mut bot, &board), fresh);
+}
+
+#[test]
+fn test_tt_generations() {
+    let tt = LockFreeTT::new(1);
+    tt.store(42, 100, 5, Flag::Exact, None);
+    assert!(tt.get(42).is_some());
+
+    // Entries from earlier searches stay usable.
+    tt.new_search();
+    assert_eq!(tt.generation(), 1);
+    assert!(tt.get(42).is_some());
+
+    tt.clear();
+    assert_eq!(tt.generation(), 0);
+    assert!(tt.get(42).is_none());
+}
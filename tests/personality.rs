@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation::{format_move, parse_move};
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::{EvalParams, MinimaxBot, Personality, SearchLimits};
+
+fn bot(personality: Personality) -> MinimaxBot {
+    let time = Duration::from_secs(60);
+    let mut bot = MinimaxBot::try_new_from_params(4, time, 16, 1, 2, 8).unwrap();
+    bot.set_personality(personality);
+    bot
+}
+
+#[test]
+fn test_names_round_trip() {
+    for personality in Personality::ALL {
+        assert_eq!(personality.name().parse(), Ok(personality));
+        let json = serde_json::to_string(&personality).unwrap();
+        assert_eq!(json, format!("\"{personality}\""));
+    }
+    assert_eq!("Attacking".parse(), Ok(Personality::Attacking));
+    let err = "reckless".parse::<Personality>().unwrap_err();
+    assert!(err.to_string().contains("balanced, attacking"), "{err}");
+}
+
+#[test]
+fn test_presets() {
+    let defaults = EvalParams::default();
+    assert_eq!(Personality::Balanced.weights(&defaults), defaults);
+    assert_eq!(Personality::Balanced.contempt(), 0);
+    for personality in &Personality::ALL[1..] {
+        assert_ne!(personality.weights(&defaults), defaults, "{personality}");
+    }
+
+    let attacking = Personality::Attacking.weights(&defaults);
+    assert_eq!(
+        attacking.king_zone_contact_mg,
+        2 * defaults.king_zone_contact_mg
+    );
+    assert_eq!(attacking.pawn_mg, defaults.pawn_mg);
+    let materialistic = Personality::Materialistic.weights(&defaults);
+    assert!(materialistic.queen_mg > defaults.queen_mg);
+    let simplifying = Personality::Simplifying.weights(&defaults);
+    assert!(simplifying.trade_bonus_mg > 0);
+    assert!(Personality::Simplifying.contempt() < 0 && Personality::Attacking.contempt() > 0);
+
+    // Presets scale the configured weights rather than replace them.
+    let mut tuned = defaults.clone();
+    tuned.king_zone_contact_mg = 40;
+    let mut bot = bot(Personality::Attacking);
+    bot.set_eval_params(tuned);
+    assert_eq!(bot.eval_params().king_zone_contact_mg, 80);
+    assert_eq!(bot.contempt(), Personality::Attacking.contempt());
+    bot.set_personality(Personality::Balanced);
+    assert_eq!(bot.eval_params().king_zone_contact_mg, 40);
+    bot.set_contempt(50);
+    assert_eq!(bot.contempt(), 50);
+}
+
+#[test]
+fn test_contempt_avoids_repetition() {
+    // Black, a pawn down, can repeat the position by retreating the knight.
+    let mut board = Board::from_fen("4k1n1/p7/8/8/8/8/PP6/4K1N1 w - - 0 1").unwrap();
+    let mut player = Player::White;
+    for mv in ["g1f3", "g8f6", "f3g1"] {
+        let mv = parse_move(&mut board, player, mv).unwrap();
+        board.apply_move(&mv).unwrap();
+        player = player.opponent();
+    }
+
+    let limits = SearchLimits::depth(3);
+    let result = bot(Personality::Balanced).search(&board, Player::Black, &limits);
+    let result = result.unwrap();
+    assert_eq!(format_move(&result.best_move), "f6g8");
+    assert_eq!(result.score, 0);
+
+    let mut stubborn = bot(Personality::Balanced);
+    stubborn.set_contempt(300);
+    let result = stubborn.search(&board, Player::Black, &limits).unwrap();
+    assert_ne!(format_move(&result.best_move), "f6g8");
+    assert!(result.score > -300, "{}", result.score);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::interface::uci::UciInterface;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_ponder_suspends_time_limit() {
+    let board = Board::new(2, 8);
+    let bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let limits = SearchLimits {
+        movetime: Some(Duration::from_millis(50)),
+        ponder: true,
+        ..SearchLimits::default()
+    };
+
+    let handle = bot.start_search(&board, Player::White, limits);
+    thread::sleep(Duration::from_millis(300));
+    assert!(handle.is_pondering());
+    assert!(!handle.is_finished());
+
+    handle.ponderhit();
+    assert!(!handle.is_pondering());
+    let result = handle.wait().unwrap();
+    assert!(result.time >= Duration::from_millis(300));
+    assert!(!handle.was_stopped());
+}
+
+#[test]
+fn test_ponder_search_waits_for_ponderhit() {
+    let board = Board::new(2, 8);
+    let bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let limits = SearchLimits {
+        ponder: true,
+        ..SearchLimits::depth(2)
+    };
+
+    let handle = bot.start_search(&board, Player::White, limits);
+    let result = handle.wait().unwrap();
+    assert_eq!(result.depth, 2);
+    // Finished early, but the move is only due after the ponderhit.
+    assert!(handle.is_pondering());
+    handle.ponderhit();
+    assert!(!handle.is_pondering());
+}
+
+#[test]
+fn test_uci_ponder() {
+    let mut uci = UciInterface::new(small_config(1, None));
+    let input = b"position startpos moves e2e4\n\
+        go ponder movetime 100\n\
+        ponderhit\n\
+        go ponder depth 2\n";
+    let mut output = Vec::new();
+    uci.run(&input[..], &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let best: Vec<&str> = output
+        .lines()
+        .filter(|l| l.starts_with("bestmove "))
+        .collect();
+    // The second search is stopped at EOF since no ponderhit can follow.
+    assert_eq!(best.len(), 2, "output was:\n{output}");
+    assert!(best[0].contains(" ponder "), "output was:\n{output}");
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation;
+use hyperchess::domain::models::{Move, Player};
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchEvent, SearchLimits};
+use std::sync::{Arc, mpsc};
+
+fn assert_pv_legal(board: &Board, player: Player, pv: &[Move]) {
+    let mut board = board.clone();
+    let mut player = player;
+    for mv in pv {
+        let legal = Rules::generate_legal_moves(&mut board, player);
+        assert!(
+            legal.contains(mv),
+            "illegal PV move {}",
+            notation::format_move(mv)
+        );
+        board.apply_move(mv).unwrap();
+        player = player.opponent();
+    }
+}
+
+#[test]
+fn test_search_result_carries_full_pv() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, Some(4)), 2, 8);
+
+    let result = bot
+        .search(&board, Player::White, &SearchLimits::default())
+        .expect("result");
+    assert_eq!(result.depth, 4);
+    assert_eq!(result.pv.first(), Some(&result.best_move));
+    assert!(result.pv.len() >= 4, "pv too short: {:?}", result.pv);
+    assert_pv_legal(&board, Player::White, &result.pv);
+    assert!(result.nodes > 0);
+    assert_eq!(result.mate, None);
+}
+
+#[test]
+fn test_mate_in_one_reported() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, Some(3)), 2, 8);
+
+    let result = bot
+        .search(&board, Player::White, &SearchLimits::default())
+        .expect("result");
+    assert_eq!(notation::format_move(&result.best_move), "a1a8");
+    assert_eq!(result.mate, Some(1));
+    assert_eq!(result.pv.len(), 1);
+}
+
+#[test]
+fn test_iteration_pvs_are_legal() {
+    let mut board = Board::new(2, 8);
+    let e4 = notation::parse_move(&mut board, Player::White, "e2e4").unwrap();
+    board.apply_move(&e4).unwrap();
+
+    let mut bot = MinimaxBot::new(&small_config(1, Some(4)), 2, 8);
+    let (tx, rx) = mpsc::channel();
+    bot.set_observer(Some(Arc::new(tx)));
+    bot.search(&board, Player::Black, &SearchLimits::default())
+        .expect("result");
+
+    let mut iterations = 0;
+    for event in rx.try_iter() {
+        if let SearchEvent::Iteration(info) = event {
+            iterations += 1;
+            assert!(!info.pv.is_empty());
+            assert_pv_legal(&board, Player::Black, &info.pv);
+        }
+    }
+    assert_eq!(iterations, 4);
+}
+
+#[test]
+fn test_analysis_lines_have_pvs() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, Some(3)), 2, 8);
+    let limits = SearchLimits {
+        multi_pv: 3,
+        ..SearchLimits::default()
+    };
+    for line in bot.analyze(&board, Player::White, &limits) {
+        assert!(line.pv.len() >= 2, "line {} too short", line.multipv);
+        assert_pv_legal(&board, Player::White, &line.pv);
+    }
+}
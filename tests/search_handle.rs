@@ -0,0 +1,149 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchEvent, SearchLimits};
+use std::future::Future;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+
+/// Wakes the test thread blocked in `block_on`.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_stop_infinite_search() {
+    let board = Board::new(2, 8);
+    let bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let limits = SearchLimits {
+        infinite: true,
+        ..SearchLimits::default()
+    };
+
+    let start = Instant::now();
+    let handle = bot.start_search(&board, Player::White, limits);
+    thread::sleep(Duration::from_millis(300));
+    assert!(!handle.is_finished());
+    assert!(handle.best_so_far().is_some());
+
+    handle.stop();
+    let result = handle.wait().unwrap();
+    assert!(handle.was_stopped());
+    assert!(handle.is_finished());
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(result.depth >= 1);
+}
+
+#[test]
+fn test_await_handle() {
+    let board = Board::new(2, 8);
+    let bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let handle = bot.start_search(&board, Player::White, SearchLimits::depth(3));
+
+    let result = block_on(handle.clone()).unwrap();
+    assert_eq!(result.depth, 3);
+    assert!(!handle.was_stopped());
+    assert_eq!(handle.best_so_far().unwrap().pv[0], result.best_move);
+}
+
+#[test]
+fn test_panicking_search_releases_waiters() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    bot.set_observer(Some(Arc::new(|_: &SearchEvent| panic!("observer failed"))));
+
+    let handle = bot.start_search(&board, Player::White, SearchLimits::depth(3));
+    assert!(block_on(handle.clone()).is_none());
+    assert!(handle.wait().is_none());
+    assert!(handle.is_finished());
+}
+
+#[test]
+fn test_uci_stop() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_hyperchess-uci"))
+        .env("HYPERCHESS_COMPUTE_MEMORY", "16")
+        .env("HYPERCHESS_COMPUTE_CONCURRENCY", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    writeln!(stdin, "position startpos\ngo infinite\nisready").unwrap();
+    let mut line = String::new();
+    stdout.read_line(&mut line).unwrap();
+    while line.trim() != "readyok" {
+        assert!(!line.starts_with("bestmove"), "search ended before stop");
+        line.clear();
+        stdout.read_line(&mut line).unwrap();
+    }
+
+    thread::sleep(Duration::from_millis(200));
+    writeln!(stdin, "stop").unwrap();
+    line.clear();
+    while !line.starts_with("bestmove") {
+        line.clear();
+        assert!(stdout.read_line(&mut line).unwrap() > 0, "no bestmove");
+    }
+
+    drop(stdin);
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_uci_infinite_waits_for_stop() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_hyperchess-uci"))
+        .env("HYPERCHESS_COMPUTE_MEMORY", "16")
+        .env("HYPERCHESS_COMPUTE_CONCURRENCY", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+
+    // The node limit ends the search long before the `isready`.
+    writeln!(stdin, "position startpos\ngo infinite nodes 1000").unwrap();
+    thread::sleep(Duration::from_millis(500));
+    writeln!(stdin, "isready").unwrap();
+    let mut line = String::new();
+    while line.trim() != "readyok" {
+        assert!(!line.starts_with("bestmove"), "bestmove sent before stop");
+        line.clear();
+        assert!(stdout.read_line(&mut line).unwrap() > 0, "no readyok");
+    }
+
+    writeln!(stdin, "stop").unwrap();
+    while !line.starts_with("bestmove") {
+        line.clear();
+        assert!(stdout.read_line(&mut line).unwrap() > 0, "no bestmove");
+    }
+
+    drop(stdin);
+    assert!(child.wait().unwrap().success());
+}
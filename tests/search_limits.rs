@@ -0,0 +1,118 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation;
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::interface::uci::UciInterface;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_depth_limit() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let result = bot
+        .search(&board, Player::White, &SearchLimits::depth(3))
+        .unwrap();
+    assert_eq!(result.depth, 3);
+}
+
+#[test]
+fn test_node_limit() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let limits = SearchLimits {
+        nodes: Some(5_000),
+        ..SearchLimits::default()
+    };
+    let result = bot.search(&board, Player::White, &limits).unwrap();
+    assert!(result.nodes < 6_000, "searched {} nodes", result.nodes);
+    assert!(result.depth >= 1);
+}
+
+#[test]
+fn test_movetime_limit() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let start = Instant::now();
+    let result = bot
+        .search(
+            &board,
+            Player::White,
+            &SearchLimits::movetime(Duration::from_millis(200)),
+        )
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(result.depth >= 1);
+}
+
+#[test]
+fn test_search_moves_restricts_root() {
+    let mut board = Board::new(2, 8);
+    let a3 = notation::parse_move(&mut board, Player::White, "a2a3").unwrap();
+    let h3 = notation::parse_move(&mut board, Player::White, "h2h3").unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let limits = SearchLimits {
+        depth: Some(3),
+        search_moves: vec![a3.clone(), h3.clone()],
+        ..SearchLimits::default()
+    };
+    let result = bot.search(&board, Player::White, &limits).unwrap();
+    assert!(result.best_move == a3 || result.best_move == h3);
+}
+
+#[test]
+fn test_mate_target_stops_early() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1").unwrap();
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let limits = SearchLimits {
+        mate: Some(1),
+        ..SearchLimits::default()
+    };
+    let result = bot.search(&board, Player::White, &limits).unwrap();
+    assert_eq!(notation::format_move(&result.best_move), "a1a8");
+    assert!(result.mate.is_some_and(|m| m > 0));
+    assert!(result.depth <= 2);
+}
+
+#[test]
+fn test_infinite_until_stopped() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, None), 2, 8);
+    let stop = bot.stop_flag();
+    let limits = SearchLimits {
+        infinite: true,
+        ..SearchLimits::default()
+    };
+
+    let start = Instant::now();
+    let result = thread::scope(|scope| {
+        let search = scope.spawn(|| bot.search(&board, Player::White, &limits));
+        thread::sleep(Duration::from_millis(300));
+        stop.store(true, Ordering::Relaxed);
+        search.join().unwrap()
+    });
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(300));
+    assert!(elapsed < Duration::from_secs(5));
+    assert!(result.unwrap().depth >= 1);
+}
+
+#[test]
+fn test_uci_go_limits() {
+    let mut uci = UciInterface::new(small_config(1, None));
+    let input = b"position startpos\ngo searchmoves a2a3 h2h3 depth 2\ngo nodes 2000\n";
+    let mut output = Vec::new();
+    uci.run(&input[..], &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let best: Vec<&str> = output
+        .lines()
+        .filter(|l| l.starts_with("bestmove "))
+        .collect();
+    assert_eq!(best.len(), 2, "output was:\n{output}");
+    assert!(best[0].starts_with("bestmove a2a3") || best[0].starts_with("bestmove h2h3"));
+}
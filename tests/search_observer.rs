@@ -0,0 +1,71 @@
+mod common;
+
+use common::small_config;
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::domain::services::PlayerStrategy;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchEvent};
+use hyperchess::interface::uci::UciInterface;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+
+#[test]
+fn test_channel_receives_each_iteration() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, Some(3)), 2, 8);
+    let (tx, rx) = mpsc::channel();
+    bot.set_observer(Some(Arc::new(tx)));
+
+    let mv = bot.get_move(&board, Player::White).expect("move");
+
+    let iterations: Vec<_> = rx
+        .try_iter()
+        .filter_map(|e| match e {
+            SearchEvent::Iteration(info) => Some(info),
+            SearchEvent::Progress { .. } => None,
+        })
+        .collect();
+    let depths: Vec<usize> = iterations.iter().map(|i| i.depth).collect();
+    assert_eq!(depths, vec![1, 2, 3]);
+
+    let last = iterations.last().unwrap();
+    assert_eq!(last.pv.first(), Some(&mv));
+    assert!(last.nodes > 0);
+    assert!(last.seldepth >= last.depth);
+    assert!(last.hashfull <= 1000);
+    assert!(iterations.windows(2).all(|w| w[0].nodes <= w[1].nodes));
+}
+
+#[test]
+fn test_closure_observer() {
+    let board = Board::new(2, 8);
+    let mut bot = MinimaxBot::new(&small_config(1, Some(2)), 2, 8);
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = count.clone();
+    bot.set_observer(Some(Arc::new(move |event: &SearchEvent| {
+        if matches!(event, SearchEvent::Iteration(_)) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    })));
+
+    bot.get_move(&board, Player::White).expect("move");
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_uci_streams_info_lines() {
+    let mut uci = UciInterface::new(small_config(1, Some(3)));
+    let input = b"position startpos moves e2e4\ngo depth 3\n";
+    let mut output = Vec::new();
+    uci.run(&input[..], &mut output).unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    let info_lines: Vec<&str> = output
+        .lines()
+        .filter(|l| l.starts_with("info depth"))
+        .collect();
+    assert_eq!(info_lines.len(), 3, "output was:\n{output}");
+    assert!(info_lines[2].starts_with("info depth 3 seldepth "));
+    assert!(info_lines[2].contains(" pv "));
+    assert!(output.lines().last().unwrap().starts_with("bestmove "));
+}
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::{MAX_SKILL, MinimaxBot, SearchInfo, SearchLimits, Skill};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn bot(skill: u8) -> MinimaxBot {
+    let time = Duration::from_secs(60);
+    let mut bot = MinimaxBot::try_new_from_params(6, time, 16, 1, 2, 8).unwrap();
+    bot.set_skill(Skill::new(skill).unwrap());
+    bot
+}
+
+#[test]
+fn test_levels() {
+    assert!(Skill::new(MAX_SKILL + 1).is_err());
+    assert_eq!(Skill::default(), Skill::new(MAX_SKILL).unwrap());
+    assert!(!Skill::default().is_weakened());
+    assert_eq!(Skill::default().elo(), None);
+
+    let levels: Vec<Skill> = (0..MAX_SKILL).map(|l| Skill::new(l).unwrap()).collect();
+    for pair in levels.windows(2) {
+        let (weaker, stronger) = (pair[0], pair[1]);
+        assert!(weaker.max_depth() <= stronger.max_depth());
+        assert!(weaker.max_nodes() <= stronger.max_nodes());
+        assert!(weaker.noise() > stronger.noise());
+        assert!(weaker.blunder_chance() >= stronger.blunder_chance());
+        assert!(weaker.elo() < stronger.elo());
+    }
+
+    // Caps only ever tighten the requested limits.
+    let beginner = levels[0];
+    let limits = beginner.limit(&SearchLimits::default());
+    assert_eq!(limits.depth, Some(1));
+    assert_eq!(limits.nodes, Some(200));
+    assert_eq!(limits.multi_pv, 6);
+    let limits = levels[19].limit(&SearchLimits::depth(3));
+    assert_eq!(limits.depth, Some(3));
+    let full = Skill::default().limit(&SearchLimits::depth(3));
+    assert_eq!((full.depth, full.nodes, full.multi_pv), (Some(3), None, 0));
+}
+
+#[test]
+fn test_pick() {
+    let line = |score| SearchInfo {
+        depth: 1,
+        seldepth: 1,
+        multipv: 1,
+        score,
+        pv: Vec::new(),
+        nodes: 0,
+        nps: 0,
+        hashfull: 0,
+        time: Duration::ZERO,
+    };
+    let lines = [line(50), line(40), line(30), line(-900)];
+    let mut rng = StdRng::seed_from_u64(1);
+    let pick = |skill: u8, rng: &mut StdRng| Skill::new(skill).unwrap().pick(&lines, rng);
+
+    assert!((0..100).all(|_| pick(MAX_SKILL, &mut rng) == 0));
+    // Strong levels choose among close lines but never the lost one.
+    let strong: HashSet<usize> = (0..500).map(|_| pick(18, &mut rng)).collect();
+    assert!(strong.len() > 1 && !strong.contains(&3));
+    // Beginners sometimes blunder it away.
+    let blunders = (0..1000).filter(|_| pick(0, &mut rng) == 3).count();
+    assert!((100..300).contains(&blunders), "{blunders}");
+}
+
+#[test]
+fn test_weakened_search() {
+    let board = Board::new(2, 8);
+    let mut beginner = bot(0);
+    let mut played = HashSet::new();
+    for _ in 0..10 {
+        let result = beginner.search(&board, Player::White, &SearchLimits::default());
+        let result = result.unwrap();
+        assert!(result.nodes < 1000, "{}", result.nodes);
+        assert_eq!(result.depth, 1);
+        assert_eq!(result.pv[0], result.best_move);
+        assert!(result.lines.iter().any(|l| l.pv[0] == result.best_move));
+        played.insert(result.best_move);
+    }
+    assert!(played.len() > 1);
+
+    // Analysis is never weakened: the mate is found.
+    let mate = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let player = Player::White;
+    let analysis = SearchLimits {
+        mate: Some(1),
+        ..SearchLimits::default()
+    };
+    let result = beginner.search(&mate, player, &analysis).unwrap();
+    assert_eq!(result.mate, Some(1));
+    let mut after = mate.clone();
+    after.apply_move(&result.best_move).unwrap();
+    assert!(Rules::generate_legal_moves(&mut after, player.opponent()).is_empty());
+}
@@ -0,0 +1,113 @@
+//! Scripted stand-in engine for exercising `ExternalEngine`.
+//!
+//! Speaks just enough UCI (plus the N-D `hfen` extension) to play the first
+//! legal move. Flags select misbehaviour:
+//!
+//!   --nd            advertise the Dimension/Side options
+//!   --crash-on N    exit without replying on the N-th `go`
+//!   --hang          never answer `go`, even after `stop`
+//!   --wait-for-stop answer `go` only once `stop` arrives
+//!   --illegal       reply with a move that is not legal
+
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation;
+use hyperchess::domain::models::Player;
+use hyperchess::domain::rules::Rules;
+use std::env;
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let flag = |name: &str| args.iter().any(|a| a == name);
+    let crash_on: Option<usize> = args
+        .iter()
+        .position(|a| a == "--crash-on")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|s| s.parse().ok());
+
+    let mut dimension = 2;
+    let mut side = 8;
+    let mut board = Board::new(dimension, side);
+    let mut player = Player::White;
+    let mut go_count = 0;
+    let mut pending_go = false;
+
+    let stdin = io::stdin();
+    let mut out = io::stdout();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.first().copied() {
+            Some("uci") => {
+                writeln!(out, "id name EngineStub").unwrap();
+                if flag("--nd") {
+                    writeln!(out, "option name Dimension type spin default 2 min 2 max 6").unwrap();
+                    writeln!(out, "option name Side type spin default 8 min 4 max 16").unwrap();
+                }
+                writeln!(out, "uciok").unwrap();
+            }
+            Some("isready") => writeln!(out, "readyok").unwrap(),
+            Some("setoption") => {
+                let value = tokens.last().and_then(|v| v.parse().ok());
+                match (tokens.get(2).copied(), value) {
+                    (Some("Dimension"), Some(v)) => dimension = v,
+                    (Some("Side"), Some(v)) => side = v,
+                    _ => {}
+                }
+            }
+            Some("ucinewgame") => board = Board::new(dimension, side),
+            Some("position") => match tokens.get(1).copied() {
+                Some("fen") => {
+                    board = Board::from_fen(&tokens[2..].join(" ")).unwrap();
+                    player = if tokens.get(3) == Some(&"b") {
+                        Player::Black
+                    } else {
+                        Player::White
+                    };
+                }
+                Some("hfen") => (board, player) = Board::from_hfen(&tokens[2..].join(" ")).unwrap(),
+                _ => {
+                    board = Board::new(dimension, side);
+                    player = Player::White;
+                }
+            },
+            Some("go") => {
+                go_count += 1;
+                if crash_on == Some(go_count) {
+                    std::process::exit(1);
+                }
+                if flag("--hang") {
+                    continue;
+                }
+                if flag("--wait-for-stop") {
+                    pending_go = true;
+                    continue;
+                }
+                reply(&mut out, &mut board, player, flag("--illegal"));
+            }
+            Some("stop") if pending_go => {
+                pending_go = false;
+                reply(&mut out, &mut board, player, flag("--illegal"));
+            }
+            Some("quit") => break,
+            _ => {}
+        }
+        out.flush().unwrap();
+    }
+}
+
+fn reply(out: &mut impl Write, board: &mut Board, player: Player, illegal: bool) {
+    let moves = Rules::generate_legal_moves(board, player);
+    let best = if illegal {
+        let mut mv = moves[0].clone();
+        mv.to = mv.from.clone();
+        notation::format_move(&mv)
+    } else {
+        moves
+            .first()
+            .map(notation::format_move)
+            .unwrap_or_else(|| "(none)".to_string())
+    };
+    writeln!(out, "info depth 1 score cp 0").unwrap();
+    writeln!(out, "bestmove {}", best).unwrap();
+}
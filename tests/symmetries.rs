@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use hyperchess::domain::board::Board;
+use hyperchess::domain::coordinate::Coordinate;
+use hyperchess::domain::models::{Piece, PieceType, Player};
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::EvalParams;
+use hyperchess::infrastructure::ai::eval::Evaluator;
+use hyperchess::infrastructure::symmetries::SymmetryHandler;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+#[test]
+fn test_rule_preserving_group() {
+    for (dimension, side, count) in [(2, 8, 2), (3, 4, 2), (4, 4, 4), (5, 3, 12)] {
+        let handler = SymmetryHandler::rule_preserving(dimension, side);
+        assert_eq!(handler.symmetries.len(), count);
+        assert_eq!(handler.maps.len(), count);
+        let total_cells = side.pow(dimension as u32);
+        assert_eq!(handler.maps[0], (0..total_cells).collect::<Vec<_>>());
+        for symmetry in &handler.symmetries {
+            assert_eq!(&symmetry.permutation[..2], &[0, 1]);
+            let inverse = symmetry.inverse();
+            let coords: Vec<u8> = (0..dimension as u8).map(|c| c % side as u8).collect();
+            assert_eq!(
+                inverse
+                    .apply(&symmetry.apply(&coords, side), side)
+                    .as_slice(),
+                coords
+            );
+        }
+    }
+
+    // The full table still holds every permutation and reflection.
+    let all = SymmetryHandler::new(3, 4);
+    assert_eq!(all.maps.len(), 6 * 8);
+    let start = Board::new(3, 4);
+    assert_eq!(
+        all.symmetries
+            .iter()
+            .filter(|s| s.preserves_rules(&start))
+            .count(),
+        2
+    );
+}
+
+/// Play random legal moves, checking every symmetric image of each position
+/// against the original: keys, legal moves and evaluation must all agree.
+fn check_random_play(dimension: usize, side: usize, seed: u64) {
+    let handler = SymmetryHandler::rule_preserving(dimension, side);
+    let params = EvalParams::default();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut board = Board::new(dimension, side);
+    let mut player = Player::White;
+
+    for _ in 0..30 {
+        let canonical = handler.canonical_key(&board);
+        let legal: HashSet<_> = Rules::generate_legal_moves(&mut board, player)
+            .into_iter()
+            .collect();
+        for i in 0..handler.symmetries.len() {
+            if !handler.symmetries[i].preserves_rules(&board) {
+                continue;
+            }
+            let mut image = handler.transform_board(i, &board);
+            assert_eq!(image.state.hash, handler.transformed_key(i, &board));
+            image.update_hash(player);
+            assert_eq!(image.state.hash, handler.transformed_key(i, &board));
+            assert_eq!(handler.canonical_key(&image), canonical);
+
+            let image_legal: HashSet<_> = Rules::generate_legal_moves(&mut image, player)
+                .into_iter()
+                .collect();
+            let mapped: HashSet<_> = legal
+                .iter()
+                .map(|mv| handler.transform_move(i, mv, side))
+                .collect();
+            assert_eq!(image_legal, mapped);
+            for mv in &image_legal {
+                assert!(legal.contains(&handler.inverse_move(i, mv, side)));
+            }
+
+            assert_eq!(
+                Evaluator::evaluate(&image, &params),
+                Evaluator::evaluate(&board, &params)
+            );
+        }
+
+        let Some(mv) = legal
+            .into_iter()
+            .collect::<Vec<_>>()
+            .choose(&mut rng)
+            .cloned()
+        else {
+            break;
+        };
+        board.apply_move(&mv).unwrap();
+        player = player.opponent();
+    }
+}
+
+#[test]
+fn test_symmetric_positions_agree_3d() {
+    check_random_play(3, 4, 1);
+}
+
+#[test]
+fn test_symmetric_positions_agree_4d() {
+    check_random_play(4, 4, 2);
+}
+
+#[test]
+fn test_file_mirror_needs_no_castling() {
+    let handler = SymmetryHandler::rule_preserving(2, 8);
+    let mirror = 1;
+    assert_eq!(handler.symmetries[mirror].reflections, 0b10);
+
+    let start = Board::new(2, 8);
+    assert!(!handler.symmetries[mirror].preserves_rules(&start));
+    assert_eq!(handler.canonical(&start), (start.state.hash, 0));
+
+    // A king and rook ending and its file mirror share a key.
+    let place = |cells: &[([u8; 2], PieceType, Player)]| {
+        let mut board = Board::new_empty(2, 8);
+        for &(coords, piece_type, owner) in cells {
+            let piece = Piece { piece_type, owner };
+            board
+                .set_piece(&Coordinate::new(coords.to_vec()), piece)
+                .unwrap();
+        }
+        board
+    };
+    let board = place(&[
+        ([0, 1], PieceType::King, Player::White),
+        ([3, 2], PieceType::Rook, Player::White),
+        ([6, 5], PieceType::King, Player::Black),
+    ]);
+    let mirrored = place(&[
+        ([0, 6], PieceType::King, Player::White),
+        ([3, 5], PieceType::Rook, Player::White),
+        ([6, 2], PieceType::King, Player::Black),
+    ]);
+    assert_ne!(board.state.hash, mirrored.state.hash);
+    assert_eq!(
+        handler.canonical_key(&board),
+        handler.canonical_key(&mirrored)
+    );
+    assert_eq!(
+        handler.transform_board(mirror, &board).state.hash,
+        mirrored.state.hash
+    );
+    let params = EvalParams::default();
+    assert_eq!(
+        Evaluator::evaluate(&board, &params),
+        Evaluator::evaluate(&mirrored, &params)
+    );
+}
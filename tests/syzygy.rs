@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use hyperchess::domain::board::Board;
+use hyperchess::domain::board::notation::format_move;
+use hyperchess::domain::models::Player;
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::transposition::{score_from_tt, score_to_tt};
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::infrastructure::syzygy::{SyzygyTables, TB_WIN_SCORE, Wdl, to_chess, wdl_score};
+use shakmaty::fen::Fen;
+use shakmaty::{CastlingMode, EnPassantMode, Position};
+
+/// The few small tables these tests need; see the README there.
+const TABLES: &str = "tests/data/syzygy";
+
+fn position(fen: &str) -> (Board, Player) {
+    let board = Board::from_fen(fen).unwrap();
+    let player = if fen.split_whitespace().nth(1) == Some("w") {
+        Player::White
+    } else {
+        Player::Black
+    };
+    (board, player)
+}
+
+#[test]
+fn test_positions_convert() {
+    for fen in [
+        "8/8/8/4k3/8/8/8/KQ6 w - - 0 1",
+        "4k3/8/8/8/8/8/1r6/K3R3 b - - 12 40",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w - f6 0 3",
+        "8/8/8/8/3pP3/8/8/K6k b - e3 0 1",
+        "8/4P3/8/8/8/8/k6p/4K3 w - - 0 1",
+    ] {
+        let (mut board, player) = position(fen);
+        let chess = to_chess(&board, player).unwrap();
+        assert_eq!(
+            Fen::from_position(&chess, EnPassantMode::Legal).to_string(),
+            fen
+        );
+
+        let ours: HashSet<String> = Rules::generate_legal_moves(&mut board, player)
+            .iter()
+            .map(format_move)
+            .collect();
+        let theirs: HashSet<String> = chess
+            .legal_moves()
+            .iter()
+            .map(|m| m.to_uci(CastlingMode::Standard).to_string())
+            .collect();
+        assert_eq!(ours, theirs, "{fen}");
+    }
+
+    // Tables hold no positions with castling rights, and only 2D 8×8 boards.
+    let (board, player) = position("r3k3/8/8/8/8/8/8/4K3 b q - 0 1");
+    assert!(to_chess(&board, player).is_none());
+    assert!(to_chess(&Board::new(3, 8), Player::White).is_none());
+}
+
+#[test]
+fn test_scores() {
+    assert_eq!(wdl_score(Wdl::Win, 3), TB_WIN_SCORE - 3);
+    assert_eq!(wdl_score(Wdl::Loss, 4), -TB_WIN_SCORE + 4);
+    // Saved by the 50-move rule.
+    assert_eq!(wdl_score(Wdl::CursedWin, 1), 0);
+    assert_eq!(wdl_score(Wdl::BlessedLoss, 1), 0);
+    assert_eq!(
+        hyperchess::infrastructure::ai::result::mate_in(TB_WIN_SCORE),
+        None
+    );
+    // A win stored in the TT at one ply reads back shifted at another.
+    let stored = score_to_tt(wdl_score(Wdl::Win, 5), 5);
+    assert_eq!(score_from_tt(stored, 2), wdl_score(Wdl::Win, 2));
+    let stored = score_to_tt(wdl_score(Wdl::Loss, 5), 5);
+    assert_eq!(score_from_tt(stored, 2), wdl_score(Wdl::Loss, 2));
+
+    let empty = std::env::temp_dir().join(format!("hyperchess-syzygy-{}", std::process::id()));
+    std::fs::create_dir_all(&empty).unwrap();
+    assert!(SyzygyTables::open(&empty).is_err());
+    std::fs::remove_dir(&empty).unwrap();
+}
+
+#[test]
+fn test_tables_filter_and_score() {
+    let tables = SyzygyTables::open(TABLES).unwrap();
+    assert!(tables.max_pieces() >= 4);
+
+    let (board, player) = position("8/8/8/4k3/8/8/8/KQ6 w - - 0 1");
+    assert_eq!(tables.probe(&board, player), Some(Wdl::Win));
+    assert_eq!(tables.probe(&board, player.opponent()), Some(Wdl::Loss));
+    let (board, player) = position("8/8/8/4k3/8/8/8/KN6 w - - 0 1");
+    assert_eq!(tables.probe(&board, player), Some(Wdl::Draw));
+
+    // Only winning moves are searched, and only the fastest to convert.
+    let (mut board, player) = position("8/8/8/4k3/8/8/8/KQ6 w - - 0 1");
+    let moves = Rules::generate_legal_moves(&mut board, player).into_vec();
+    let kept = tables.root_moves(&board, player, &moves).unwrap();
+    assert!(!kept.is_empty() && kept.len() < moves.len());
+    let mut direct = shakmaty_syzygy::Tablebase::<shakmaty::Chess>::new();
+    direct.add_directory(TABLES).unwrap();
+    for mv in &kept {
+        let mut child = board.clone();
+        child.apply_move(mv).unwrap();
+        let chess = to_chess(&child, player.opponent()).unwrap();
+        assert!(chess.is_checkmate() || direct.probe_wdl(&chess).unwrap().signum() < 0);
+    }
+
+    // Capturing the rook reaches the tables: the search scores the win
+    // there without looking further.
+    let (board, player) = position("3r3k/8/8/8/8/8/8/3Q1NK1 w - - 0 1");
+    let time = std::time::Duration::from_secs(60);
+    let mut bot = MinimaxBot::try_new_from_params(2, time, 16, 1, 2, 8).unwrap();
+    bot.set_syzygy(Some(std::sync::Arc::new(tables)));
+    let result = bot.search(&board, player, &SearchLimits::depth(2)).unwrap();
+    assert_eq!(format_move(&result.best_move), "d1d8");
+    assert_eq!(result.score, TB_WIN_SCORE - 1);
+    assert_eq!(result.mate, None);
+}
@@ -0,0 +1,270 @@
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::{Piece, PieceType, Player};
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::{MinimaxBot, SearchLimits};
+use hyperchess::infrastructure::tablebase::{
+    Material, Tablebase, TablebaseError, Tablebases, TbValue,
+};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn generate(dimension: usize, side: usize, materials: &[&str]) -> (Tablebases, Vec<String>) {
+    let mut tables = Tablebases::new(dimension, side);
+    let mut generated = Vec::new();
+    for material in materials {
+        let material = Material::parse(material).unwrap();
+        tables
+            .generate(&material, &mut |t| generated.push(t.material().to_string()))
+            .unwrap();
+    }
+    (tables, generated)
+}
+
+/// A board with `pieces` on `cells`, or `None` if two share a cell.
+fn place(dimension: usize, side: usize, pieces: &[Piece], cells: &[usize]) -> Option<Board> {
+    let mut board = Board::new_empty(dimension, side);
+    for (&piece, &cell) in pieces.iter().zip(cells) {
+        if board.pieces.all_occupancy.get_bit(cell) {
+            return None;
+        }
+        board.pieces.place_piece_at_index(cell, piece);
+    }
+    Some(board)
+}
+
+/// The value of a position from its moves, one ply deep into the tables.
+fn backed_up(tables: &Tablebases, board: &Board, player: Player) -> TbValue {
+    let moves = Rules::generate_legal_moves(&mut board.clone(), player);
+    if moves.is_empty() {
+        return if Rules::is_in_check(board, player) {
+            TbValue::Loss(0)
+        } else {
+            TbValue::Draw
+        };
+    }
+    let (_, value) = tables.best_move(board, player, &moves).unwrap();
+    value
+}
+
+/// Check that every legal position of `material` (or `samples` random ones)
+/// holds the value its moves back up.
+fn check_consistent(tables: &Tablebases, material: &str, samples: Option<usize>) {
+    let material = Material::parse(material).unwrap();
+    let pieces: Vec<Piece> = material
+        .white
+        .iter()
+        .map(|&piece_type| Piece {
+            piece_type,
+            owner: Player::White,
+        })
+        .chain(material.black.iter().map(|&piece_type| Piece {
+            piece_type,
+            owner: Player::Black,
+        }))
+        .collect();
+    let (dimension, side) = (tables.dimension(), tables.side());
+    let cells = side.pow(dimension as u32);
+    let total = cells.pow(pieces.len() as u32);
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut checked = 0;
+    let mut decisive = 0;
+    for n in 0..samples.unwrap_or(total) {
+        let mut code = if samples.is_some() {
+            rng.gen_range(0..total)
+        } else {
+            n
+        };
+        let placement: Vec<usize> = (0..pieces.len())
+            .map(|_| {
+                let cell = code % cells;
+                code /= cells;
+                cell
+            })
+            .collect();
+        let Some(board) = place(dimension, side, &pieces, &placement) else {
+            continue;
+        };
+        for player in [Player::White, Player::Black] {
+            if Rules::is_in_check(&board, player.opponent()) {
+                continue;
+            }
+            let promoting = pieces.iter().zip(&placement).any(|(p, &cell)| {
+                let coords = board.index_to_coords(cell);
+                let far = if p.owner == Player::White {
+                    side - 1
+                } else {
+                    0
+                };
+                p.piece_type == PieceType::Pawn
+                    && (0..dimension).all(|a| a == 1 || coords[a] as usize == far)
+            });
+            if promoting {
+                continue;
+            }
+            let value = tables.probe(&board, player).unwrap();
+            assert_eq!(
+                value,
+                backed_up(tables, &board, player),
+                "{placement:?} {player:?}"
+            );
+            checked += 1;
+            if value != TbValue::Draw {
+                decisive += 1;
+            }
+        }
+    }
+    assert!(checked > 0 && decisive > 0);
+}
+
+#[test]
+fn test_material_notation() {
+    let material = Material::parse("KvKRq").unwrap();
+    assert_eq!(material.to_string(), "KvKQR");
+    assert_eq!(material.flipped().to_string(), "KQRvK");
+    assert_eq!(material.pieces(), 4);
+    assert!(matches!(
+        Material::parse("KQK"),
+        Err(TablebaseError::InvalidMaterial(_))
+    ));
+    assert!(Material::parse("KQvQ").is_err());
+    assert!(Material::parse("KPvKP").is_err());
+
+    let successors: Vec<String> = Material::parse("KPvKN")
+        .unwrap()
+        .successors()
+        .iter()
+        .map(|m| m.to_string())
+        .collect();
+    assert_eq!(
+        successors,
+        ["KvKN", "KPvK", "KQvKN", "KRvKN", "KBvKN", "KNvKN"]
+    );
+}
+
+#[test]
+fn test_tables_are_consistent_2d() {
+    let (tables, generated) = generate(2, 4, &["KRvK", "KQvKN"]);
+    assert_eq!(generated, ["KRvK", "KvKN", "KQvK", "KQvKN"]);
+    check_consistent(&tables, "KRvK", None);
+    check_consistent(&tables, "KQvKN", Some(4000));
+    // Lookups through the colour swap.
+    check_consistent(&tables, "KvKR", None);
+}
+
+#[test]
+fn test_tables_are_consistent_3d() {
+    let (tables, generated) = generate(3, 3, &["KQRvK"]);
+    assert_eq!(generated, ["KRvK", "KQvK", "KQRvK"]);
+    // A lone king cannot be mated by a queen or a rook in 3D.
+    for table in tables.tables() {
+        let summary = table.summary();
+        if table.material().pieces() == 3 {
+            assert_eq!((summary.wins, summary.losses), (0, 0));
+        } else {
+            assert!(summary.wins > 0 && summary.losses > 0 && summary.draws > 0);
+            assert_eq!(summary.longest % 2, 0);
+        }
+    }
+    check_consistent(&tables, "KQRvK", Some(3000));
+}
+
+/// A random position of `pieces` whose value is `wanted`.
+fn find(tables: &Tablebases, pieces: &[Piece], wanted: TbValue) -> (Board, Player) {
+    let (dimension, side) = (tables.dimension(), tables.side());
+    let cells = side.pow(dimension as u32);
+    let mut rng = StdRng::seed_from_u64(9);
+    loop {
+        let placement: Vec<usize> = pieces.iter().map(|_| rng.gen_range(0..cells)).collect();
+        let Some(board) = place(dimension, side, pieces, &placement) else {
+            continue;
+        };
+        for player in [Player::White, Player::Black] {
+            if !Rules::is_in_check(&board, player.opponent())
+                && tables.probe(&board, player) == Some(wanted)
+            {
+                return (board, player);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_search_agrees_and_uses_tables() {
+    let (tables, _) = generate(3, 3, &["KQRvK"]);
+    let tables = std::sync::Arc::new(tables);
+    let piece = |piece_type, owner| Piece { piece_type, owner };
+    let pieces = [
+        piece(PieceType::King, Player::White),
+        piece(PieceType::Queen, Player::White),
+        piece(PieceType::Rook, Player::White),
+        piece(PieceType::King, Player::Black),
+    ];
+    let (board, player) = find(&tables, &pieces, TbValue::Win(3));
+    assert_eq!(TbValue::Win(3).score(0), 30000 - 3);
+
+    // The search finds the mate the table promises on its own...
+    let time = std::time::Duration::from_secs(60);
+    let mut bot = MinimaxBot::try_new_from_params(4, time, 16, 1, 3, 3).unwrap();
+    let searched = bot.search(&board, player, &SearchLimits::depth(4)).unwrap();
+    assert_eq!(searched.mate, Some(2));
+
+    // ...and with the tables plays a fastest mate without searching.
+    bot.set_tablebases(Some(tables.clone()));
+    let probed = bot.search(&board, player, &SearchLimits::depth(4)).unwrap();
+    assert_eq!(probed.mate, Some(2));
+    assert_eq!(probed.nodes, 0);
+    let mut after = board.clone();
+    after.apply_move(&probed.best_move).unwrap();
+    assert_eq!(
+        tables.probe(&after, player.opponent()),
+        Some(TbValue::Loss(2))
+    );
+    assert_eq!(probed.pv.len(), 3);
+
+    // Analysis searches instead, scoring the positions it reaches from the
+    // tables: one ply shows a mate that takes four.
+    let (board, player) = find(&tables, &pieces, TbValue::Loss(4));
+    let analysis = SearchLimits {
+        mate: Some(5),
+        ..SearchLimits::depth(1)
+    };
+    let probed = bot.search(&board, player, &analysis).unwrap();
+    assert_eq!(probed.mate, Some(-2));
+    bot.set_tablebases(None);
+    bot.new_game();
+    let searched = bot.search(&board, player, &analysis).unwrap();
+    assert_eq!(searched.mate, None);
+}
+
+#[test]
+fn test_pawn_tables_promote() {
+    let (tables, generated) = generate(2, 4, &["KPvK"]);
+    assert!(generated.contains(&"KQvK".to_string()));
+    assert_eq!(generated.last().unwrap(), "KPvK");
+    check_consistent(&tables, "KPvK", None);
+    check_consistent(&tables, "KvKP", None);
+}
+
+#[test]
+fn test_round_trip() {
+    let (tables, _) = generate(2, 4, &["KRvK"]);
+    let table = tables.tables().next().unwrap();
+    let mut bytes = Vec::new();
+    table.write(&mut bytes).unwrap();
+    assert_eq!(&bytes[..4], b"HCTB");
+    let read = Tablebase::read(bytes.as_slice()).unwrap();
+    assert_eq!(read.material(), table.material());
+    assert_eq!(read.summary(), table.summary());
+    assert_eq!(table.file_name(), "2d4-KRvK.hctb");
+
+    assert!(Tablebase::read(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Tablebase::read(&b"HCBK\x01\x00"[..]).is_err());
+
+    let dir = std::env::temp_dir().join(format!("hyperchess-tb-{}", std::process::id()));
+    tables.save_dir(&dir).unwrap();
+    let loaded = Tablebases::load_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].len(), 1);
+    assert_eq!((loaded[0].dimension(), loaded[0].side()), (2, 4));
+}
@@ -0,0 +1,117 @@
+use hyperchess::application::datagen::{DatagenOptions, generate};
+use hyperchess::domain::board::Board;
+use hyperchess::domain::coordinate::Coordinate;
+use hyperchess::domain::models::{Move, PieceType, Player};
+use hyperchess::domain::rules::Rules;
+use hyperchess::infrastructure::ai::tuner::read_labelled_positions;
+use hyperchess::infrastructure::ai::{EvalParams, SearchLimits};
+use hyperchess::infrastructure::training_data::{
+    GameOutcome, TrainingDataError, TrainingDataReader, TrainingDataWriter, TrainingRecord,
+};
+
+fn record(board: &Board, score: i16, mv: Move, result: GameOutcome) -> TrainingRecord {
+    TrainingRecord {
+        hfen: board.to_hfen(Player::White),
+        score,
+        best_move: mv,
+        result,
+    }
+}
+
+#[test]
+fn test_records_round_trip() {
+    let board = Board::new(3, 4);
+    let records = vec![
+        record(
+            &board,
+            -29998,
+            Move {
+                from: Coordinate::new(vec![1, 0, 2]),
+                to: Coordinate::new(vec![2, 0, 2]),
+                promotion: None,
+            },
+            GameOutcome::BlackWin,
+        ),
+        record(
+            &board,
+            35,
+            Move {
+                from: Coordinate::new(vec![2, 3, 3]),
+                to: Coordinate::new(vec![3, 3, 3]),
+                promotion: Some(PieceType::Knight),
+            },
+            GameOutcome::Draw,
+        ),
+    ];
+
+    let mut writer = TrainingDataWriter::new(Vec::new(), 3, 4).unwrap();
+    for r in &records {
+        writer.write(r).unwrap();
+    }
+    let bytes = writer.finish().unwrap();
+    assert_eq!(&bytes[..4], b"HCTD");
+
+    let reader = TrainingDataReader::new(bytes.as_slice()).unwrap();
+    assert_eq!((reader.dimension(), reader.side()), (3, 4));
+    let read: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+    assert_eq!(read, records);
+    assert_eq!(read[0].position().unwrap().0.dimension(), 3);
+
+    // A truncated record is reported, not silently dropped.
+    let mut reader = TrainingDataReader::new(&bytes[..bytes.len() - 1]).unwrap();
+    assert!(reader.next().unwrap().is_ok());
+    assert!(matches!(
+        reader.next(),
+        Some(Err(TrainingDataError::Corrupt { record: 1, .. }))
+    ));
+}
+
+#[test]
+fn test_rejects_other_files_and_versions() {
+    assert!(matches!(
+        TrainingDataReader::new(&b"1x8 rnbq"[..]),
+        Err(TrainingDataError::BadMagic)
+    ));
+    assert!(matches!(
+        TrainingDataReader::new(&b"HCTD\x02\x00\x02\x08"[..]),
+        Err(TrainingDataError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn test_self_play_positions_feed_the_tuner() {
+    let options = DatagenOptions {
+        games: 2,
+        limits: SearchLimits::depth(1),
+        max_plies: 30,
+        seed: 3,
+        ..DatagenOptions::default()
+    };
+    let mut writer = TrainingDataWriter::new(Vec::new(), 2, 8).unwrap();
+    let mut games = 0;
+    let written = generate(&options, &EvalParams::default(), &mut writer, |_| {
+        games += 1
+    })
+    .unwrap();
+    let bytes = writer.finish().unwrap();
+    assert_eq!(games, 2);
+    assert!(written > 0);
+
+    let records: Vec<_> = TrainingDataReader::new(bytes.as_slice())
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(records.len(), written);
+    for r in &records {
+        let (mut board, player) = r.position().unwrap();
+        let legal = Rules::generate_legal_moves(&mut board, player);
+        assert!(legal.contains(&r.best_move), "{}", r.hfen);
+    }
+
+    let path = std::env::temp_dir().join(format!("hyperchess-datagen-{}.hctd", std::process::id()));
+    std::fs::write(&path, &bytes).unwrap();
+    let positions = read_labelled_positions(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(positions.len(), written);
+    assert_eq!(positions[0].result, records[0].result.white_points());
+}
@@ -0,0 +1,109 @@
+use hyperchess::domain::board::Board;
+use hyperchess::domain::models::Player;
+use hyperchess::infrastructure::ai::EvalParams;
+use hyperchess::infrastructure::ai::tuner::{
+    TuneError, Tuner, parse_labelled_position, read_labelled_positions,
+};
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -";
+
+#[test]
+fn test_parses_fen_epd_and_hfen_lines() {
+    let fen = parse_labelled_position(&format!("{START} 0 1 ; 1/2-1/2")).unwrap();
+    assert_eq!(fen.result, 0.5);
+
+    let epd = parse_labelled_position(&format!("{START} c9 \"0-1\";")).unwrap();
+    assert_eq!(epd.result, 0.0);
+    assert_eq!(epd.board.side(), 8);
+
+    let hfen = format!("{} ; 0.75", fen.board.to_hfen(Player::White));
+    let parsed = parse_labelled_position(&hfen).unwrap();
+    assert_eq!(parsed.result, 0.75);
+    assert_eq!(parsed.board.dimension(), 2);
+
+    assert!(parse_labelled_position(&format!("{START} 0 1 ; 2")).is_err());
+    assert!(parse_labelled_position(START).is_err());
+}
+
+#[test]
+fn test_reads_files_of_one_board_size() {
+    let dir = std::env::temp_dir().join(format!("hyperchess-tuner-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let cube = Board::new(3, 4).to_hfen(Player::White);
+
+    let same = dir.join("same.txt");
+    std::fs::write(
+        &same,
+        format!(
+            "# 3D
+{cube} ; 1-0
+
+{cube} ; 0.5
+"
+        ),
+    )
+    .unwrap();
+    let positions = read_labelled_positions(&same).unwrap();
+    assert_eq!(positions.len(), 2);
+    assert_eq!(positions[1].board.dimension(), 3);
+
+    let mixed = dir.join("mixed.txt");
+    std::fs::write(
+        &mixed,
+        format!(
+            "{cube} ; 1-0
+{START} 0 1 ; 0-1
+"
+        ),
+    )
+    .unwrap();
+    assert!(matches!(
+        read_labelled_positions(&mixed),
+        Err(TuneError::MixedGeometry { line: 2 })
+    ));
+
+    let empty = dir.join("empty.txt");
+    std::fs::write(
+        &empty,
+        "# nothing yet
+",
+    )
+    .unwrap();
+    assert!(matches!(
+        read_labelled_positions(&empty),
+        Err(TuneError::NoPositions)
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_tuning_lowers_the_error() {
+    // The side a knight up always wins, and by more than the default
+    // weights predict.
+    let lines = [
+        "rnbqkb1r/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ; 1-0",
+        "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ; 1-0",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1 ; 0-1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 1 ; 0-1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 ; 1/2-1/2",
+    ];
+    let positions: Vec<_> = lines
+        .iter()
+        .map(|line| parse_labelled_position(line).unwrap())
+        .collect();
+
+    let start = EvalParams::default();
+    let mut tuner = Tuner::new(&positions);
+    tuner.fit_scale(&start);
+    let before = tuner.error(&start);
+
+    let mut epochs = 0;
+    let tuned = tuner.tune(&start, &["knight_mg", "knight_eg"], 16, 10, |report| {
+        epochs = report.epoch;
+        assert!(report.error <= before);
+    });
+    assert!(epochs > 0);
+    assert!(tuner.error(&tuned) < before);
+    assert!(tuned.knight_mg > start.knight_mg);
+    assert_eq!(tuned.pawn_mg, start.pawn_mg);
+}
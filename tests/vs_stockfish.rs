@@ -7,6 +7,7 @@ use hyperchess::domain::coordinate::Coordinate;
 use hyperchess::domain::models::{Move, PieceType, Player};
 use hyperchess::domain::rules::Rules;
 use hyperchess::domain::services::PlayerStrategy;
+use hyperchess::infrastructure::ai::EvalParams;
 use hyperchess::infrastructure::ai::MinimaxBot;
 use hyperchess::infrastructure::ai::eval::Evaluator;
 use hyperchess::infrastructure::display::render_board;
@@ -198,7 +199,7 @@ fn print_position(
     println!("{}", rendered);
 
     // Eval
-    let eval = Evaluator::evaluate(board);
+    let eval = Evaluator::evaluate(board, &EvalParams::default());
     let bar = eval_bar(eval);
     println!("  Eval: {} {}", format_eval(eval), bar);
     println!();